@@ -0,0 +1,359 @@
+//! Derive a split repo's Cargo.lock from the monorepo's lockfile
+//!
+//! The split gets the subgraph of the mono `Cargo.lock` reachable from its crates, with
+//! exactly the versions (and checksums) the monorepo resolved and tested. Workspace crates
+//! that are not part of the split (they become registry or git dependencies there) are left
+//! out, but their dependencies stay pinned so cargo prefers the same versions when it fills
+//! them in.
+//!
+//! Output follows cargo's own formatting (package order, dependency references that are
+//! only as specific as needed) so a fresh `cargo build` leaves the file untouched.
+
+use crate::cargo::transform::CargoTransform;
+use crate::core::config::{SplitMode, WorkspaceMode};
+use crate::core::error::{RailError, RailResult, ResultExt};
+use crate::core::vcs::SystemGit;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
+
+/// A lockfile to write in the split repo and the crates it locks
+pub struct LockfileTarget {
+  /// Path of the lockfile in the split repo
+  pub path: PathBuf,
+  /// Crates whose dependency subgraph goes into this lockfile
+  pub roots: HashSet<String>,
+}
+
+/// Where a split's lockfiles go
+///
+/// Single and workspace-mode splits get one root `Cargo.lock`. Standalone combined splits
+/// have no root workspace, so every crate directory gets its own.
+pub fn lockfile_targets(
+  transform: &CargoTransform,
+  mode: &SplitMode,
+  workspace_mode: &WorkspaceMode,
+  crate_paths: &[PathBuf],
+) -> Vec<LockfileTarget> {
+  match (mode, workspace_mode) {
+    (SplitMode::Single, _) => vec![LockfileTarget {
+      path: PathBuf::from("Cargo.lock"),
+      roots: transform.crate_names_for_paths(&crate_paths[..1]),
+    }],
+    (SplitMode::Combined, WorkspaceMode::Workspace) => vec![LockfileTarget {
+      path: PathBuf::from("Cargo.lock"),
+      roots: transform.crate_names_for_paths(crate_paths),
+    }],
+    (SplitMode::Combined, WorkspaceMode::Standalone) => crate_paths
+      .iter()
+      .map(|crate_path| LockfileTarget {
+        path: crate_path.join("Cargo.lock"),
+        roots: transform.crate_names_for_paths(std::slice::from_ref(crate_path)),
+      })
+      .collect(),
+  }
+}
+
+/// Write the split's lockfiles derived from the mono `Cargo.lock` at `mono_sha`
+///
+/// `local_crates` are all workspace crates shipped in the split. Targets whose crates the
+/// mono lockfile doesn't cover yet (e.g. early history) are skipped, as is everything when
+/// the mono has no committed lockfile. Returns the number of lockfiles written.
+pub fn write_split_lockfiles(
+  mono_git: &SystemGit,
+  mono_sha: &str,
+  target_repo_path: &Path,
+  targets: &[LockfileTarget],
+  local_crates: &HashSet<String>,
+) -> RailResult<usize> {
+  let Some(mono_lock) = mono_git.get_file_at_commit(mono_sha, Path::new("Cargo.lock"))? else {
+    return Ok(0);
+  };
+  let mono_lock = String::from_utf8(mono_lock)?;
+
+  let mut written = 0;
+  for target in targets {
+    if let Ok(lockfile) = derive_split_lockfile(&mono_lock, &target.roots, local_crates) {
+      let path = target_repo_path.join(&target.path);
+      if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+      }
+      std::fs::write(path, lockfile)?;
+      written += 1;
+    }
+  }
+  Ok(written)
+}
+
+/// A `[[package]]` entry from a Cargo.lock
+#[derive(Debug, Clone)]
+struct LockedPackage {
+  name: String,
+  version: String,
+  source: Option<String>,
+  checksum: Option<String>,
+  /// Raw dependency references (`name`, `name version` or `name version (source)`)
+  dependencies: Vec<String>,
+}
+
+impl LockedPackage {
+  /// Workspace/path packages carry no source in the lockfile
+  fn is_local(&self) -> bool {
+    self.source.is_none()
+  }
+}
+
+/// Derive the lockfile for a split from the mono lockfile contents
+///
+/// `roots` are the crates the lockfile is for; `local_crates` are all workspace crates
+/// shipped in the split repo (including bundled dependencies), which stay in the lockfile
+/// as path packages. Returns an error if a root is missing from the mono lockfile.
+pub fn derive_split_lockfile(
+  mono_lock: &str,
+  roots: &HashSet<String>,
+  local_crates: &HashSet<String>,
+) -> RailResult<String> {
+  let doc: DocumentMut = mono_lock.parse().context("Failed to parse monorepo Cargo.lock")?;
+  let lock_version = doc.get("version").and_then(|v| v.as_integer());
+  let packages = parse_packages(&doc);
+
+  // Resolve every dependency reference to a package index up front
+  let edges: Vec<Vec<usize>> = packages
+    .iter()
+    .map(|pkg| {
+      pkg
+        .dependencies
+        .iter()
+        .filter_map(|dep| resolve_ref(&packages, dep))
+        .collect()
+    })
+    .collect();
+
+  let mut queue = VecDeque::new();
+  for name in roots {
+    let idx = packages
+      .iter()
+      .position(|pkg| pkg.is_local() && &pkg.name == name)
+      .ok_or_else(|| {
+        RailError::with_help(
+          format!("Crate '{}' not found in the monorepo Cargo.lock", name),
+          "Run `cargo generate-lockfile` (or any cargo build) in the monorepo first",
+        )
+      })?;
+    queue.push_back(idx);
+  }
+
+  // Walk the dependency subgraph; local crates outside the split are traversed but omitted
+  let mut reachable = HashSet::new();
+  while let Some(idx) = queue.pop_front() {
+    if !reachable.insert(idx) {
+      continue;
+    }
+    queue.extend(edges[idx].iter().copied());
+  }
+
+  let kept: Vec<usize> = (0..packages.len())
+    .filter(|idx| reachable.contains(idx))
+    .filter(|idx| !packages[*idx].is_local() || local_crates.contains(&packages[*idx].name))
+    .collect();
+  let kept_set: HashSet<usize> = kept.iter().copied().collect();
+
+  // How specific a reference must be, based on what remains in the split lockfile
+  let mut name_counts: HashMap<&str, usize> = HashMap::new();
+  let mut name_version_counts: HashMap<(&str, &str), usize> = HashMap::new();
+  for idx in &kept {
+    let pkg = &packages[*idx];
+    *name_counts.entry(&pkg.name).or_default() += 1;
+    *name_version_counts.entry((&pkg.name, &pkg.version)).or_default() += 1;
+  }
+
+  let mut out =
+    String::from("# This file is automatically @generated by Cargo.\n# It is not intended for manual editing.\n");
+  if let Some(version) = lock_version {
+    out.push_str(&format!("version = {}\n", version));
+  }
+
+  for idx in &kept {
+    let pkg = &packages[*idx];
+    out.push_str("\n[[package]]\n");
+    out.push_str(&format!("name = \"{}\"\n", pkg.name));
+    out.push_str(&format!("version = \"{}\"\n", pkg.version));
+    if let Some(ref source) = pkg.source {
+      out.push_str(&format!("source = \"{}\"\n", source));
+    }
+    if let Some(ref checksum) = pkg.checksum {
+      out.push_str(&format!("checksum = \"{}\"\n", checksum));
+    }
+
+    let mut deps: Vec<String> = edges[*idx]
+      .iter()
+      .filter(|dep| kept_set.contains(dep))
+      .map(|dep| {
+        let dep = &packages[*dep];
+        if name_counts[dep.name.as_str()] == 1 {
+          dep.name.clone()
+        } else if name_version_counts[&(dep.name.as_str(), dep.version.as_str())] == 1 {
+          format!("{} {}", dep.name, dep.version)
+        } else {
+          format!(
+            "{} {} ({})",
+            dep.name,
+            dep.version,
+            dep.source.as_deref().unwrap_or_default()
+          )
+        }
+      })
+      .collect();
+    deps.sort();
+    deps.dedup();
+
+    if !deps.is_empty() {
+      out.push_str("dependencies = [\n");
+      for dep in deps {
+        out.push_str(&format!(" \"{}\",\n", dep));
+      }
+      out.push_str("]\n");
+    }
+  }
+
+  Ok(out)
+}
+
+fn parse_packages(doc: &DocumentMut) -> Vec<LockedPackage> {
+  let Some(tables) = doc.get("package").and_then(|p| p.as_array_of_tables()) else {
+    return Vec::new();
+  };
+
+  tables
+    .iter()
+    .filter_map(|table| {
+      let get = |key: &str| table.get(key).and_then(|v| v.as_str()).map(String::from);
+      Some(LockedPackage {
+        name: get("name")?,
+        version: get("version")?,
+        source: get("source"),
+        checksum: get("checksum"),
+        dependencies: table
+          .get("dependencies")
+          .and_then(|d| d.as_array())
+          .map(|deps| deps.iter().filter_map(|d| d.as_str().map(String::from)).collect())
+          .unwrap_or_default(),
+      })
+    })
+    .collect()
+}
+
+/// Resolve a lockfile dependency reference to a package index
+fn resolve_ref(packages: &[LockedPackage], reference: &str) -> Option<usize> {
+  let mut parts = reference.splitn(3, ' ');
+  let name = parts.next()?;
+  let version = parts.next();
+  let source = parts.next().map(|s| s.trim_start_matches('(').trim_end_matches(')'));
+
+  packages.iter().position(|pkg| {
+    pkg.name == name
+      && version.is_none_or(|v| pkg.version == v)
+      && source.is_none_or(|s| pkg.source.as_deref() == Some(s))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MONO_LOCK: &str = r#"# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 4
+
+[[package]]
+name = "anyhow"
+version = "1.0.90"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "aaaa"
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "anyhow",
+ "internal",
+ "rand 0.8.5",
+]
+
+[[package]]
+name = "internal"
+version = "0.1.0"
+dependencies = [
+ "serde",
+]
+
+[[package]]
+name = "other"
+version = "0.1.0"
+dependencies = [
+ "rand 0.9.0",
+]
+
+[[package]]
+name = "rand"
+version = "0.8.5"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "bbbb"
+
+[[package]]
+name = "rand"
+version = "0.9.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "cccc"
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "dddd"
+"#;
+
+  #[test]
+  fn test_derive_keeps_only_reachable_subgraph() {
+    let split: HashSet<String> = ["app".to_string()].into_iter().collect();
+    let lock = derive_split_lockfile(MONO_LOCK, &split, &split).unwrap();
+
+    assert!(lock.starts_with("# This file is automatically @generated by Cargo."));
+    assert!(lock.contains("version = 4"));
+    assert!(lock.contains("name = \"app\""));
+    assert!(lock.contains("name = \"anyhow\""));
+    // Transitive deps of a workspace crate outside the split stay pinned
+    assert!(lock.contains("name = \"serde\""));
+    // ...but the workspace crate itself is resolved from its registry/git source instead
+    assert!(!lock.contains("name = \"internal\""));
+    // Unrelated crates and versions are dropped
+    assert!(!lock.contains("name = \"other\""));
+    assert!(!lock.contains("0.9.0"));
+  }
+
+  #[test]
+  fn test_derive_simplifies_now_unique_references() {
+    let split: HashSet<String> = ["app".to_string()].into_iter().collect();
+    let lock = derive_split_lockfile(MONO_LOCK, &split, &split).unwrap();
+
+    // Only one rand version remains, so cargo would write a bare name
+    assert!(lock.contains(" \"rand\",\n"), "got:\n{}", lock);
+    assert!(!lock.contains("\"rand 0.8.5\""));
+    assert!(!lock.contains(" \"internal\",\n"));
+  }
+
+  #[test]
+  fn test_derive_keeps_bundled_workspace_crates() {
+    let split: HashSet<String> = ["app".to_string(), "internal".to_string()].into_iter().collect();
+    let lock = derive_split_lockfile(MONO_LOCK, &split, &split).unwrap();
+
+    assert!(lock.contains("name = \"internal\""));
+    assert!(lock.contains(" \"internal\",\n"));
+  }
+
+  #[test]
+  fn test_derive_errors_on_unknown_crate() {
+    let split: HashSet<String> = ["missing".to_string()].into_iter().collect();
+    assert!(derive_split_lockfile(MONO_LOCK, &split, &split).is_err());
+  }
+}
@@ -1,5 +1,5 @@
 use crate::core::error::RailResult;
-use cargo_metadata::{MetadataCommand, Package};
+use cargo_metadata::{MetadataCommand, Package, TargetKind};
 use std::path::Path;
 
 /// Workspace introspection using cargo_metadata
@@ -30,6 +30,18 @@ impl WorkspaceMetadata {
       .find(|pkg| pkg.name == name)
   }
 
+  /// Check if any crate at the given paths (relative to workspace root) has a bin target
+  pub fn has_bin_target(&self, crate_paths: &[std::path::PathBuf]) -> bool {
+    self.list_crates().into_iter().any(|pkg| {
+      let in_paths = pkg
+        .manifest_path
+        .parent()
+        .and_then(|dir| dir.strip_prefix(self.workspace_root()).ok())
+        .is_some_and(|rel| crate_paths.iter().any(|p| rel.as_std_path() == p.as_path()));
+      in_paths && pkg.targets.iter().any(|t| t.kind.contains(&TargetKind::Bin))
+    })
+  }
+
   pub fn workspace_root(&self) -> &std::path::Path {
     self.metadata.workspace_root.as_std_path()
   }
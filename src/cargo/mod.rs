@@ -6,8 +6,10 @@
 //! - **transform**: Transform Cargo.toml files (flatten workspace inheritance, convert path deps)
 //! - **files**: Discover and copy auxiliary files (.cargo, rust-toolchain, etc.)
 //! - **helpers**: Cargo-specific utility functions
+//! - **lockfile**: Derive split repo lockfiles from the monorepo's Cargo.lock
 
 pub mod files;
 pub mod helpers;
+pub mod lockfile;
 pub mod metadata;
 pub mod transform;
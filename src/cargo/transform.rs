@@ -1,8 +1,9 @@
 use crate::cargo::metadata::WorkspaceMetadata;
 use crate::core::error::{RailError, RailResult, ResultExt};
-use std::collections::HashMap;
-use std::path::PathBuf;
-use toml_edit::{DocumentMut, Item, Value};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use toml_edit::{Array, DocumentMut, Item, Table, TableLike, Value};
 
 /// Context for transformation operations
 pub struct TransformContext {
@@ -14,14 +15,29 @@ pub struct TransformContext {
   pub workspace_root: PathBuf,
 }
 
+/// Where workspace crates outside the split come from, when not from a registry
+#[derive(Debug, Clone, Default)]
+pub struct DependencyOverrides {
+  /// Crates bundled into the split repo: path dependencies on them are kept
+  pub bundled: HashSet<String>,
+  /// Crates pulled from their own split remotes: name → (git url, branch)
+  pub git: HashMap<String, (String, String)>,
+}
+
 /// Cargo-specific transformations for Cargo.toml
 /// Handles: path ↔ version, workspace flattening
+///
+/// Cloning is cheap: the workspace lookups are shared, so one transform built per run can
+/// serve every split (each clone with its own dependency overrides).
+#[derive(Clone)]
 pub struct CargoTransform {
-  workspace_metadata: WorkspaceMetadata,
+  workspace_metadata: Arc<WorkspaceMetadata>,
   /// Map of crate name -> version from workspace
-  workspace_versions: HashMap<String, String>,
+  workspace_versions: Arc<HashMap<String, String>>,
   /// Map of crate name -> relative path from workspace root
-  workspace_paths: HashMap<String, String>,
+  workspace_paths: Arc<HashMap<String, String>>,
+  /// Bundled / git sources for unpublished workspace dependencies
+  overrides: DependencyOverrides,
 }
 
 impl CargoTransform {
@@ -42,10 +58,46 @@ impl CargoTransform {
     }
 
     Self {
-      workspace_metadata,
-      workspace_versions,
-      workspace_paths,
+      workspace_metadata: Arc::new(workspace_metadata),
+      workspace_versions: Arc::new(workspace_versions),
+      workspace_paths: Arc::new(workspace_paths),
+      overrides: DependencyOverrides::default(),
+    }
+  }
+
+  /// Use bundled / git sources for the given workspace dependencies instead of versions
+  pub fn with_dependency_overrides(mut self, overrides: DependencyOverrides) -> Self {
+    self.overrides = overrides;
+    self
+  }
+
+  /// Rewrite a path dependency on a workspace crate that is not part of the split
+  ///
+  /// Bundled crates keep their path, git-sourced crates point at their split remote,
+  /// everything else becomes a registry version dependency.
+  fn rewrite_path_dependency(&self, dep_name: &str, dep_table: &mut dyn TableLike) -> RailResult<()> {
+    if self.overrides.bundled.contains(dep_name) {
+      return Ok(());
     }
+
+    let Some(version) = self.workspace_versions.get(dep_name) else {
+      // Path dependency to non-workspace crate - ERROR
+      return Err(RailError::with_help(
+        format!(
+          "Cannot split: dependency '{}' has path to non-workspace crate",
+          dep_name
+        ),
+        "Convert to version dependency first",
+      ));
+    };
+
+    dep_table.remove("path");
+    if let Some((url, branch)) = self.overrides.git.get(dep_name) {
+      dep_table.insert("git", Item::Value(Value::from(url.clone())));
+      dep_table.insert("branch", Item::Value(Value::from(branch.clone())));
+    }
+    dep_table.insert("version", Item::Value(Value::from(version.clone())));
+    Ok(())
   }
 
   /// Flatten workspace = true fields with actual values
@@ -178,21 +230,7 @@ impl CargoTransform {
             if let Some(dep_table) = dep.as_table_like_mut()
               && dep_table.contains_key("path")
             {
-              // Check if it's a workspace path dependency
-              if let Some(version) = self.workspace_versions.get(&dep_name) {
-                // Remove path, add version
-                dep_table.remove("path");
-                dep_table.insert("version", Item::Value(Value::from(version.clone())));
-              } else {
-                // Path dependency to non-workspace crate - ERROR
-                return Err(RailError::with_help(
-                  format!(
-                    "Cannot split: dependency '{}' has path to non-workspace crate",
-                    dep_name
-                  ),
-                  "Convert to version dependency first",
-                ));
-              }
+              self.rewrite_path_dependency(&dep_name, dep_table)?;
             }
           }
         }
@@ -204,6 +242,183 @@ impl CargoTransform {
 }
 
 impl CargoTransform {
+  /// Resolve the workspace crate names that live at the given paths (relative to workspace root)
+  pub fn crate_names_for_paths(&self, paths: &[PathBuf]) -> HashSet<String> {
+    self
+      .workspace_paths
+      .iter()
+      .filter(|(_, rel_path)| paths.iter().any(|p| Path::new(rel_path.as_str()) == p.as_path()))
+      .map(|(name, _)| name.clone())
+      .collect()
+  }
+
+  /// Transform a member manifest for a workspace-mode combined split
+  ///
+  /// Unlike `transform_to_split`, `workspace = true` inheritance is kept (the split repo
+  /// gets its own root manifest) and path dependencies on crates included in the split
+  /// stay path dependencies. Path dependencies on workspace crates outside the split
+  /// become version dependencies.
+  pub fn transform_to_split_member(
+    &self,
+    content: &str,
+    _context: &TransformContext,
+    included_crates: &HashSet<String>,
+  ) -> RailResult<String> {
+    let mut doc: DocumentMut = content.parse().context("Failed to parse Cargo.toml")?;
+
+    let dep_sections = ["dependencies", "dev-dependencies", "build-dependencies"];
+    for section in dep_sections {
+      if let Some(deps) = doc.get_mut(section).and_then(|d| d.as_table_like_mut()) {
+        let dep_names: Vec<String> = deps.iter().map(|(k, _)| k.to_string()).collect();
+
+        for dep_name in dep_names {
+          let Some(dep_table) = deps.get_mut(&dep_name).and_then(|d| d.as_table_like_mut()) else {
+            continue;
+          };
+          if !dep_table.contains_key("path") || included_crates.contains(&dep_name) {
+            continue;
+          }
+
+          self.rewrite_path_dependency(&dep_name, dep_table)?;
+        }
+      }
+    }
+
+    doc.remove("workspace");
+
+    Ok(doc.to_string())
+  }
+
+  /// Reverse `transform_to_split_member` for remote → mono syncs
+  ///
+  /// Version dependencies on workspace crates that are not part of the split are
+  /// turned back into path dependencies relative to the member's mono directory.
+  pub fn transform_member_to_mono(&self, content: &str, included_crates: &HashSet<String>) -> RailResult<String> {
+    let mut doc: DocumentMut = content.parse().context("Failed to parse Cargo.toml")?;
+
+    let member_dir = doc
+      .get("package")
+      .and_then(|p| p.get("name"))
+      .and_then(|n| n.as_str())
+      .and_then(|name| self.workspace_paths.get(name))
+      .map(PathBuf::from);
+
+    let Some(member_dir) = member_dir else {
+      return Ok(doc.to_string());
+    };
+
+    let dep_sections = ["dependencies", "dev-dependencies", "build-dependencies"];
+    for section in dep_sections {
+      if let Some(deps) = doc.get_mut(section).and_then(|d| d.as_table_like_mut()) {
+        let dep_names: Vec<String> = deps.iter().map(|(k, _)| k.to_string()).collect();
+
+        for dep_name in dep_names {
+          if included_crates.contains(&dep_name) {
+            continue;
+          }
+          let Some(dep_path) = self.workspace_paths.get(&dep_name) else {
+            continue;
+          };
+          if let Some(dep_table) = deps.get_mut(&dep_name).and_then(|d| d.as_table_like_mut())
+            && dep_table.contains_key("version")
+            && !dep_table.contains_key("path")
+            && !dep_table.contains_key("workspace")
+          {
+            let relative = relative_path(&member_dir, Path::new(dep_path));
+            strip_git_source(dep_table);
+            dep_table.insert(
+              "path",
+              Item::Value(Value::from(relative.to_string_lossy().replace('\\', "/"))),
+            );
+          }
+        }
+      }
+    }
+
+    Ok(doc.to_string())
+  }
+
+  /// Generate the root `Cargo.toml` for a workspace-mode combined split
+  ///
+  /// Copies `resolver`, `[workspace.package]` and `[workspace.lints]` from the mono's root
+  /// manifest, and trims `[workspace.dependencies]` to the entries the members inherit.
+  /// `member_manifests` are the members' manifests as they appear in the split.
+  pub fn generate_split_workspace_manifest(
+    &self,
+    member_paths: &[PathBuf],
+    member_manifests: &[String],
+    included_crates: &HashSet<String>,
+  ) -> RailResult<String> {
+    let workspace_toml_path = self.workspace_metadata.workspace_root().join("Cargo.toml");
+    let workspace_content =
+      std::fs::read_to_string(&workspace_toml_path).context("Failed to read workspace Cargo.toml")?;
+    let mono_doc = workspace_content
+      .parse::<DocumentMut>()
+      .context("Failed to parse workspace Cargo.toml")?;
+    let mono_workspace = mono_doc.get("workspace").and_then(|w| w.as_table());
+
+    // Collect dependency names the members inherit from the workspace
+    let mut inherited = BTreeSet::new();
+    for manifest in member_manifests {
+      let doc: DocumentMut = manifest.parse().context("Failed to parse member Cargo.toml")?;
+      for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        if let Some(deps) = doc.get(section).and_then(|d| d.as_table_like()) {
+          for (name, dep) in deps.iter() {
+            if dep.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+              inherited.insert(name.to_string());
+            }
+          }
+        }
+      }
+    }
+
+    let mut workspace = Table::new();
+
+    let mut members = Array::new();
+    for path in member_paths {
+      members.push(path.to_string_lossy().replace('\\', "/"));
+    }
+    workspace.insert("members", Item::Value(Value::Array(members)));
+
+    if let Some(resolver) = mono_workspace.and_then(|w| w.get("resolver")) {
+      workspace.insert("resolver", resolver.clone());
+    }
+
+    if let Some(package) = mono_workspace.and_then(|w| w.get("package")) {
+      workspace.insert("package", package.clone());
+    }
+
+    if let Some(mono_deps) = mono_workspace
+      .and_then(|w| w.get("dependencies"))
+      .and_then(|d| d.as_table_like())
+    {
+      let mut deps = Table::new();
+      for name in &inherited {
+        let Some(dep) = mono_deps.get(name) else {
+          continue;
+        };
+        let mut dep = dep.clone();
+        if !included_crates.contains(name)
+          && let Some(dep_table) = dep.as_table_like_mut()
+          && dep_table.contains_key("path")
+        {
+          self.rewrite_path_dependency(name, dep_table)?;
+        }
+        deps.insert(name, dep);
+      }
+      workspace.insert("dependencies", Item::Table(deps));
+    }
+
+    if let Some(lints) = mono_workspace.and_then(|w| w.get("lints")) {
+      workspace.insert("lints", lints.clone());
+    }
+
+    let mut doc = DocumentMut::new();
+    doc.insert("workspace", Item::Table(workspace));
+
+    Ok(doc.to_string())
+  }
+
   pub fn transform_to_split(&self, content: &str, _context: &TransformContext) -> RailResult<String> {
     let mut doc: DocumentMut = content.parse().context("Failed to parse Cargo.toml")?;
 
@@ -239,6 +454,7 @@ impl CargoTransform {
             {
               // Replace version with path
               dep_table.remove("version");
+              strip_git_source(dep_table);
               let relative_path = format!("../{}", path);
               dep_table.insert("path", Item::Value(Value::from(relative_path)));
             }
@@ -259,6 +475,29 @@ impl CargoTransform {
   }
 }
 
+/// Drop the `git`/`branch` keys added for git-sourced workspace dependencies
+fn strip_git_source(dep_table: &mut dyn TableLike) {
+  if dep_table.remove("git").is_some() {
+    dep_table.remove("branch");
+  }
+}
+
+/// Compute the relative path from one workspace-relative directory to another
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+  let from: Vec<Component> = from.components().collect();
+  let to: Vec<Component> = to.components().collect();
+  let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+  let mut result = PathBuf::new();
+  for _ in common..from.len() {
+    result.push("..");
+  }
+  for component in &to[common..] {
+    result.push(component);
+  }
+  result
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -394,6 +633,53 @@ tokio = { version = "1.0", features = ["full"] }
     assert!(output.contains("[dev-dependencies]"));
   }
 
+  #[test]
+  fn test_split_member_keeps_included_path_deps() {
+    let metadata = create_test_metadata();
+    let transformer = CargoTransform::new(metadata);
+
+    let input = r#"
+[package]
+name = "test-crate"
+version.workspace = true
+
+[dependencies]
+sibling = { path = "../sibling" }
+cargo-rail = { path = "../cargo-rail" }
+anyhow = { workspace = true }
+"#;
+
+    let included: HashSet<String> = ["sibling".to_string()].into_iter().collect();
+    let output = transformer
+      .transform_to_split_member(
+        input,
+        &TransformContext {
+          crate_name: "test-crate".to_string(),
+          workspace_root: PathBuf::from("/test"),
+        },
+        &included,
+      )
+      .unwrap();
+
+    // Inheritance and included path deps are kept, other workspace crates become versions
+    assert!(output.contains("version.workspace = true"));
+    assert!(output.contains(r#"sibling = { path = "../sibling" }"#));
+    assert!(output.contains("anyhow = { workspace = true }"));
+    assert!(!output.contains(r#"path = "../cargo-rail""#));
+  }
+
+  #[test]
+  fn test_relative_path() {
+    assert_eq!(
+      relative_path(Path::new("crates/a"), Path::new("crates/b")),
+      PathBuf::from("../b")
+    );
+    assert_eq!(
+      relative_path(Path::new("crates/a"), Path::new("libs/nested/c")),
+      PathBuf::from("../../libs/nested/c")
+    );
+  }
+
   #[test]
   fn test_roundtrip_simple_manifest() {
     let metadata = create_test_metadata();
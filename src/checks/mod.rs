@@ -7,7 +7,7 @@
 //! # Built-in Checks
 //!
 //! - **workspace-validity**: Validates workspace structure and rail.toml configuration
-//! - **ssh-keys**: Checks SSH key existence, permissions, and connectivity
+//! - **ssh-keys**: Checks credentials for each split's auth method (SSH keys, HTTPS tokens, credential helpers)
 //! - **git-notes**: Validates git-notes mappings integrity
 //! - **remote-access**: Tests accessibility of configured remote repositories
 //!
@@ -1,6 +1,7 @@
 //! Remote repository accessibility checks
 
 use super::trait_def::{Check, CheckContext, CheckResult};
+use crate::core::auth::GitAuth;
 use crate::core::config::RailConfig;
 use crate::core::error::RailResult;
 use crate::ui::progress::FileProgress;
@@ -53,25 +54,40 @@ impl Check for RemoteAccessCheck {
       config.splits.iter().collect::<Vec<_>>()
     };
 
+    // Every remote of each split: the primary, then its mirrors
+    let remotes_to_check: Vec<(String, String, GitAuth)> = crates_to_check
+      .iter()
+      .flat_map(|split_config| {
+        let mirrored = !split_config.remotes.is_empty();
+        let auth = GitAuth::for_split(split_config, &config.security, &ctx.workspace_root);
+        split_config.all_remotes().into_iter().map(move |remote| {
+          let label = if mirrored {
+            format!("'{}' ({})", split_config.name, remote.name)
+          } else {
+            format!("'{}'", split_config.name)
+          };
+          let auth = auth.for_remote(&remote, &ctx.workspace_root);
+          (label, remote.url, auth)
+        })
+      })
+      .collect();
+
     // Show progress bar for remote access checks (network operations are slow)
-    let mut progress = if !crates_to_check.is_empty() {
+    let mut progress = if !remotes_to_check.is_empty() {
       Some(FileProgress::new(
-        crates_to_check.len(),
-        format!("Checking access to {} remotes", crates_to_check.len()),
+        remotes_to_check.len(),
+        format!("Checking access to {} remotes", remotes_to_check.len()),
       ))
     } else {
       None
     };
 
-    for split_config in crates_to_check {
+    for (label, url, auth) in &remotes_to_check {
       checked += 1;
 
       // Validate remote URL format
-      if !is_valid_remote_url(&split_config.remote) {
-        issues.push(format!(
-          "'{}': Invalid remote URL format: {}",
-          split_config.name, split_config.remote
-        ));
+      if !is_valid_remote_url(url) {
+        issues.push(format!("{}: Invalid remote URL format: {}", label, url));
         if let Some(ref mut p) = progress {
           p.inc();
         }
@@ -79,21 +95,15 @@ impl Check for RemoteAccessCheck {
       }
 
       // Test connectivity
-      match test_remote_access(&split_config.remote) {
+      match test_remote_access(url, auth) {
         Ok(true) => {
           // Remote is accessible
         }
         Ok(false) => {
-          issues.push(format!(
-            "'{}': Cannot access remote: {}",
-            split_config.name, split_config.remote
-          ));
+          issues.push(format!("{}: Cannot access remote: {}", label, url));
         }
         Err(err) => {
-          issues.push(format!(
-            "'{}': Error testing remote {}: {}",
-            split_config.name, split_config.remote, err
-          ));
+          issues.push(format!("{}: Error testing remote {}: {}", label, url, err));
         }
       }
 
@@ -146,7 +156,7 @@ fn is_valid_remote_url(url: &str) -> bool {
 }
 
 /// Test if we can access a remote repository
-fn test_remote_access(url: &str) -> RailResult<bool> {
+fn test_remote_access(url: &str, auth: &GitAuth) -> RailResult<bool> {
   // For local paths, just check if directory exists
   if utils::is_local_path(url) {
     let path = std::path::Path::new(url);
@@ -154,7 +164,9 @@ fn test_remote_access(url: &str) -> RailResult<bool> {
   }
 
   // For remote URLs, use git ls-remote
-  let output = Command::new("git").arg("ls-remote").arg("--heads").arg(url).output()?;
+  let mut cmd = Command::new("git");
+  auth.apply(&mut cmd);
+  let output = cmd.arg("ls-remote").arg("--heads").arg(url).output()?;
 
   Ok(output.status.success())
 }
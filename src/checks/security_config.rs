@@ -3,7 +3,7 @@
 use super::trait_def::{Check, CheckContext, CheckResult};
 use crate::core::config::RailConfig;
 use crate::core::error::RailResult;
-use std::process::Command;
+use crate::core::security::{SecurityValidator, public_key_for};
 
 /// Check that validates security configuration (branch protection, signing, etc.)
 pub struct SecurityConfigCheck;
@@ -50,30 +50,27 @@ impl Check for SecurityConfigCheck {
     if config.security.require_signed_commits {
       info.push("Commit signing: REQUIRED".to_string());
 
-      // Check if git is configured for signing
-      if ctx.thorough
-        && let Ok(signing_configured) = check_git_signing_configured()
-      {
-        if !signing_configured {
-          warnings.push(
-            "Commit signing required but git not configured for signing. \
-             Run: git config --global commit.gpgsign true"
-              .to_string(),
-          );
-        } else {
-          info.push("Git signing configured: YES".to_string());
-        }
-      }
-
-      // Check signing key path if specified
-      if let Some(ref key_path) = config.security.signing_key_path {
-        if !key_path.exists() {
-          warnings.push(format!(
-            "Signing key path specified but not found: {}",
-            key_path.display()
-          ));
-        } else {
+      // Recreated commits are signed with the signing key (or the SSH key), not git's global config
+      match SecurityValidator::new(config.security.clone()).signing_key_path() {
+        None => warnings
+          .push("Commit signing required but no signing key found. Set signing_key_path in [security]".to_string()),
+        Some(key_path) if !key_path.exists() => warnings.push(format!(
+          "Signing key path specified but not found: {}",
+          key_path.display()
+        )),
+        Some(key_path) => {
           info.push(format!("Signing key: {}", key_path.display()));
+          if ctx.thorough {
+            match public_key_for(&key_path) {
+              Ok(public_key) if public_key.starts_with("ecdsa-") => warnings.push(
+                "ECDSA signatures are randomized - re-splitting won't reproduce the same commit SHAs. \
+                 Use an Ed25519 signing key"
+                  .to_string(),
+              ),
+              Ok(_) => {}
+              Err(e) => warnings.push(e.to_string()),
+            }
+          }
         }
       }
     } else {
@@ -132,17 +129,3 @@ impl Check for SecurityConfigCheck {
     false
   }
 }
-
-/// Check if git is configured for commit signing
-fn check_git_signing_configured() -> RailResult<bool> {
-  let output = Command::new("git")
-    .args(["config", "--get", "commit.gpgsign"])
-    .output()?;
-
-  if output.status.success() {
-    let value = String::from_utf8_lossy(&output.stdout);
-    Ok(value.trim() == "true")
-  } else {
-    Ok(false)
-  }
-}
@@ -1,7 +1,10 @@
-//! SSH key validation checks
+//! Credential checks for each split's auth method (SSH keys, HTTPS tokens, credential helpers)
 
 use super::trait_def::{Check, CheckContext, CheckResult};
+use crate::core::auth::{self, GitAuth};
+use crate::core::config::{AuthMethod, RailConfig};
 use crate::core::error::RailResult;
+use crate::utils;
 #[cfg(unix)]
 use std::fs;
 #[cfg(unix)]
@@ -9,7 +12,10 @@ use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
 use std::process::Command;
 
-/// Check that validates SSH keys and connectivity
+/// Check that validates the credentials each split's `auth` method needs
+///
+/// Kept under its original name; SSH keys are only required when a split with a network
+/// remote uses `auth = "ssh"` (or there is no rail.toml to tell).
 pub struct SshKeyCheck;
 
 impl Check for SshKeyCheck {
@@ -18,10 +24,95 @@ impl Check for SshKeyCheck {
   }
 
   fn description(&self) -> &str {
-    "Validates SSH key existence and permissions"
+    "Validates credentials for each split's auth method (SSH keys, HTTPS tokens, credential helpers)"
   }
 
   fn run(&self, ctx: &CheckContext) -> RailResult<CheckResult> {
+    let Ok(config) = RailConfig::load(&ctx.workspace_root) else {
+      return self.check_ssh_keys(ctx);
+    };
+
+    // Splits that reach a remote over the network
+    let remote_splits: Vec<_> = config
+      .splits
+      .iter()
+      .filter(|s| ctx.crate_name.as_ref().is_none_or(|name| &s.name == name))
+      .filter(|s| s.all_remotes().iter().any(|r| !utils::is_local_path(&r.url)))
+      .collect();
+
+    let mut issues = Vec::new();
+    let mut https_ok = Vec::new();
+    for split in &remote_splits {
+      let auth = GitAuth::for_split(split, &config.security, &ctx.workspace_root);
+      match split.auth {
+        AuthMethod::Ssh => {
+          for remote in split.all_remotes() {
+            if let Some(known_hosts) = remote.known_hosts_in(&ctx.workspace_root)
+              && !known_hosts.is_file()
+            {
+              issues.push(format!(
+                "'{}' ({}): pinned known_hosts not found: {}",
+                split.name,
+                remote.name,
+                known_hosts.display()
+              ));
+            }
+          }
+        }
+        AuthMethod::Https if auth::has_token(auth.token_env()) => {
+          https_ok.push(format!("'{}' (token in ${})", split.name, auth.token_env()));
+        }
+        AuthMethod::Https => issues.push(format!(
+          "'{}': auth = \"https\" but ${} is not set",
+          split.name,
+          auth.token_env()
+        )),
+        AuthMethod::Helper if auth::has_credential_helper() => {
+          https_ok.push(format!("'{}' (credential helper)", split.name));
+        }
+        AuthMethod::Helper => issues.push(format!(
+          "'{}': auth = \"helper\" but no git credential.helper is configured",
+          split.name
+        )),
+      }
+    }
+
+    if !issues.is_empty() {
+      return Ok(CheckResult::error(
+        self.name(),
+        format!("Missing credentials:\n{}", issues.join("\n")),
+        Some(
+          "Export each token (e.g. from a CI secret), configure git's credential.helper, or create the pinned known_hosts files with ssh-keyscan",
+        ),
+      ));
+    }
+
+    let needs_ssh = remote_splits.is_empty() || remote_splits.iter().any(|s| s.auth == AuthMethod::Ssh);
+    if needs_ssh {
+      return self.check_ssh_keys(ctx);
+    }
+
+    Ok(CheckResult::pass(
+      self.name(),
+      format!(
+        "No SSH keys needed; HTTPS credentials found for {}",
+        https_ok.join(", ")
+      ),
+    ))
+  }
+
+  fn is_expensive(&self) -> bool {
+    false // Basic check is fast, thorough mode tests connectivity
+  }
+
+  fn requires_crate(&self) -> bool {
+    false
+  }
+}
+
+impl SshKeyCheck {
+  /// Keys in ~/.ssh, their permissions, and (thorough mode) GitHub connectivity
+  fn check_ssh_keys(&self, ctx: &CheckContext) -> RailResult<CheckResult> {
     let home = match std::env::var("HOME") {
       Ok(h) => PathBuf::from(h),
       Err(_) => {
@@ -76,6 +167,12 @@ impl Check for SshKeyCheck {
       }
     }
 
+    if found_keys.is_empty() && std::env::var_os("SSH_AUTH_SOCK").is_some() {
+      return Ok(CheckResult::pass(
+        self.name(),
+        "No key files in ~/.ssh; ssh will use the keys offered by ssh-agent",
+      ));
+    }
     if found_keys.is_empty() {
       return Ok(CheckResult::error(
         self.name(),
@@ -127,14 +224,6 @@ impl Check for SshKeyCheck {
       ))
     }
   }
-
-  fn is_expensive(&self) -> bool {
-    false // Basic check is fast, thorough mode tests connectivity
-  }
-
-  fn requires_crate(&self) -> bool {
-    false
-  }
 }
 
 /// Test SSH connectivity to a host
@@ -6,7 +6,7 @@
 //!
 //! Built-in checks include:
 //! - Workspace validity (Cargo.toml, structure)
-//! - SSH key validation (existence, permissions, connectivity)
+//! - Credential validation (SSH keys, HTTPS tokens, credential helpers)
 //! - Git-notes integrity (mappings valid, no orphans)
 //! - Remote accessibility (can reach remotes)
 //!
@@ -0,0 +1,240 @@
+//! `cargo rail cache` - inspect and prune the managed clones of split repos
+
+use serde::Serialize;
+use std::path::Path;
+
+use crate::core::cache::{self, CacheEntry};
+use crate::core::context::WorkspaceContext;
+use crate::core::error::{RailResult, ResultExt};
+use crate::core::journal::{SyncJournal, SyncLock};
+use crate::core::resolve::ConflictState;
+use crate::utils;
+
+/// What a cached clone is still used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloneUse {
+  /// A split of this workspace syncs through it
+  InUse,
+  /// Made by this workspace for a split that's no longer configured (or no longer remote)
+  Unconfigured,
+  /// Belongs to another workspace that still exists
+  OtherWorkspace,
+  /// Its workspace has been deleted
+  WorkspaceGone,
+}
+
+/// A cached clone and how it's used
+#[derive(Debug, Clone, Serialize)]
+pub struct CachedClone {
+  #[serde(flatten)]
+  pub entry: CacheEntry,
+  pub usage: CloneUse,
+}
+
+/// Run `cargo rail cache ls`
+pub fn run_cache_ls(ctx: &WorkspaceContext, all: bool, json: bool) -> RailResult<()> {
+  let clones: Vec<CachedClone> = cached_clones(ctx)?
+    .into_iter()
+    .filter(|clone| all || clone.usage != CloneUse::OtherWorkspace && clone.usage != CloneUse::WorkspaceGone)
+    .collect();
+
+  if json {
+    println!("{}", serde_json::to_string_pretty(&clones)?);
+    return Ok(());
+  }
+
+  let config = ctx.require_config()?;
+  println!(
+    "\n📦 Split repo cache: {}\n",
+    cache::cache_root(ctx.workspace_root(), &config.cache)?.display()
+  );
+  if clones.is_empty() {
+    println!("   No cached clones");
+    return Ok(());
+  }
+
+  println!("{:<20} {:>10} {:>9} {:<16} PATH", "SPLIT", "SIZE", "UNPUSHED", "STATUS");
+  println!("{:-<100}", "");
+  for clone in &clones {
+    println!(
+      "{:<20} {:>10} {:>9} {:<16} {}",
+      clone.entry.split,
+      human_size(clone.entry.bytes),
+      clone.entry.unpushed.map_or_else(|| "?".to_string(), |n| n.to_string()),
+      usage_label(clone.usage),
+      clone.entry.path.display()
+    );
+  }
+  println!();
+
+  Ok(())
+}
+
+/// Run `cargo rail cache prune`
+///
+/// Removes clones no configured split uses and clones of deleted workspaces; `--all` also
+/// removes this workspace's clones in use (they're cloned again on the next sync). Clones
+/// with unpushed commits or local changes (or that git can't inspect), and clones whose split
+/// is being synced or has a stopped or interrupted sync, are kept.
+pub fn run_cache_prune(ctx: &WorkspaceContext, all: bool, apply: bool) -> RailResult<()> {
+  let workspace_root = ctx.workspace_root();
+  let mut removed = 0;
+  let mut freed = 0;
+
+  for clone in cached_clones(ctx)? {
+    let prunable = match clone.usage {
+      CloneUse::Unconfigured | CloneUse::WorkspaceGone => true,
+      CloneUse::InUse => all,
+      CloneUse::OtherWorkspace => false,
+    };
+    if !prunable {
+      continue;
+    }
+
+    let path = &clone.entry.path;
+    if let Some(reason) = keep_reason(workspace_root, &clone)? {
+      println!("   ⚠️  Keeping {} ({})", path.display(), reason);
+      continue;
+    }
+
+    if apply {
+      std::fs::remove_dir_all(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+      println!("   🗑️  Removed {} ({})", path.display(), human_size(clone.entry.bytes));
+      // Drop the workspace directory once only its marker is left
+      if let Some(parent) = path.parent() {
+        remove_if_only_marker(parent)?;
+      }
+    } else {
+      println!("   Would remove {} ({})", path.display(), human_size(clone.entry.bytes));
+    }
+    removed += 1;
+    freed += clone.entry.bytes;
+  }
+
+  if removed == 0 {
+    println!("✅ Nothing to prune");
+  } else if apply {
+    println!("\n✅ Pruned {} clone(s), {} freed", removed, human_size(freed));
+  } else {
+    println!(
+      "\n🔍 DRY-RUN: {} clone(s), {} - add --apply to remove them",
+      removed,
+      human_size(freed)
+    );
+  }
+
+  Ok(())
+}
+
+/// Every clone in the cache, classified against this workspace's configuration
+fn cached_clones(ctx: &WorkspaceContext) -> RailResult<Vec<CachedClone>> {
+  let workspace_root = ctx.workspace_root();
+  let config = ctx.require_config()?;
+  let root = cache::cache_root(workspace_root, &config.cache)?;
+  let own_dir = cache::workspace_cache_dir(workspace_root, &config.cache)?;
+
+  Ok(
+    cache::entries(&root)?
+      .into_iter()
+      .map(|entry| {
+        let usage = if entry.path.parent() == Some(own_dir.as_path()) {
+          let in_use = config
+            .splits
+            .iter()
+            .any(|split| split.name == entry.split && !utils::is_local_path(&split.remote));
+          if in_use {
+            CloneUse::InUse
+          } else {
+            CloneUse::Unconfigured
+          }
+        } else if entry.workspace.as_deref().is_some_and(|path| !path.exists()) {
+          CloneUse::WorkspaceGone
+        } else {
+          CloneUse::OtherWorkspace
+        };
+        CachedClone { entry, usage }
+      })
+      .collect(),
+  )
+}
+
+/// Why a prunable clone must be kept, if it must
+fn keep_reason(workspace_root: &Path, clone: &CachedClone) -> RailResult<Option<String>> {
+  let entry = &clone.entry;
+  match entry.unpushed {
+    None => {
+      return Ok(Some(
+        "couldn't check it for unpushed commits; remove it by hand".to_string(),
+      ));
+    }
+    Some(n) if n > 0 => {
+      return Ok(Some(format!("{} unpushed commit(s); push or remove it by hand", n)));
+    }
+    Some(_) => {}
+  }
+  match entry.dirty {
+    None => {
+      return Ok(Some(
+        "couldn't check it for local changes; remove it by hand".to_string(),
+      ));
+    }
+    Some(true) => return Ok(Some("uncommitted changes; commit or remove it by hand".to_string())),
+    Some(false) => {}
+  }
+
+  // Sync state of this workspace's clones (others' state lives in their own .git/rail)
+  if matches!(clone.usage, CloneUse::InUse | CloneUse::Unconfigured) {
+    if SyncLock::is_held(workspace_root, &entry.split)? {
+      return Ok(Some(format!("a sync of '{}' is running", entry.split)));
+    }
+    if ConflictState::load(workspace_root, &entry.split)?.is_some() {
+      return Ok(Some(format!(
+        "a sync of '{}' is stopped on conflicts; finish or abort it first",
+        entry.split
+      )));
+    }
+    if SyncJournal::load(workspace_root, &entry.split)?.is_some() {
+      return Ok(Some(format!(
+        "a sync of '{}' was interrupted; resume or abort it first",
+        entry.split
+      )));
+    }
+  }
+  Ok(None)
+}
+
+fn remove_if_only_marker(dir: &Path) -> RailResult<()> {
+  let has_clones = std::fs::read_dir(dir)
+    .with_context(|| format!("Failed to read {}", dir.display()))?
+    .flatten()
+    .any(|entry| entry.path().is_dir());
+  if !has_clones {
+    std::fs::remove_dir_all(dir).with_context(|| format!("Failed to remove {}", dir.display()))?;
+  }
+  Ok(())
+}
+
+fn usage_label(usage: CloneUse) -> &'static str {
+  match usage {
+    CloneUse::InUse => "in use",
+    CloneUse::Unconfigured => "unconfigured",
+    CloneUse::OtherWorkspace => "other workspace",
+    CloneUse::WorkspaceGone => "workspace gone",
+  }
+}
+
+fn human_size(bytes: u64) -> String {
+  const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+  let mut size = bytes as f64;
+  let mut unit = 0;
+  while size >= 1024.0 && unit < UNITS.len() - 1 {
+    size /= 1024.0;
+    unit += 1;
+  }
+  if unit == 0 {
+    format!("{} B", bytes)
+  } else {
+    format!("{:.1} {}", size, UNITS[unit])
+  }
+}
@@ -3,7 +3,9 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
 use crate::cargo::metadata::WorkspaceMetadata;
-use crate::core::config::{CratePath, RailConfig, SplitConfig, SplitMode, WorkspaceMode};
+use crate::core::config::{
+  AuthMethod, CratePath, RailConfig, SplitConfig, SplitMode, UnpublishedDepsPolicy, WorkspaceMode,
+};
 use crate::core::error::{RailError, RailResult, ResultExt};
 
 /// Run the init command to set up cargo-rail configuration
@@ -97,9 +99,17 @@ pub fn run_init(all: bool) -> RailResult<()> {
     config.splits.push(SplitConfig {
       name: pkg.name.to_string(),
       remote: String::new(), // Empty - user will fill this in
+      remotes: Vec::new(),
+      auth: AuthMethod::default(),
+      token_env: None,
+      helper_env: Vec::new(),
+      known_hosts: None,
       branch: "main".to_string(),
       mode: SplitMode::Single,
       workspace_mode: WorkspaceMode::default(), // Standalone by default
+      unpublished_deps: UnpublishedDepsPolicy::default(),
+      lockfile: None, // Auto: on for crates with bin targets
+      target_dir: None,
       paths: vec![CratePath { path: package_path }],
       include: include_patterns,
       exclude: vec![],
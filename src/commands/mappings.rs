@@ -5,7 +5,6 @@ use crate::core::context::WorkspaceContext;
 use crate::core::error::{ConfigError, RailError, RailResult};
 use crate::core::mapping::MappingStore;
 use crate::ui::progress::FileProgress;
-use crate::utils;
 
 /// A single SHA mapping
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +70,7 @@ pub fn run_mappings(ctx: &WorkspaceContext, crate_name: String, check: bool, jso
   mapping_store.load(ctx.workspace_root())?;
 
   let raw_mappings = mapping_store.all_mappings();
+  let target_path = Some(config.target_repo_path(ctx.workspace_root(), split_config)?).filter(|path| path.exists());
 
   // Convert to our structure
   let mut mappings = Vec::new();
@@ -95,8 +95,8 @@ pub fn run_mappings(ctx: &WorkspaceContext, crate_name: String, check: bool, jso
     if check {
       // Verify both commits exist
       let mono_exists = commit_exists(ctx.workspace_root(), mono_sha)?;
-      let remote_exists = if let Some(target_path) = get_target_path(ctx.workspace_root(), split_config) {
-        commit_exists(&target_path, remote_sha)?
+      let remote_exists = if let Some(ref target_path) = target_path {
+        commit_exists(target_path, remote_sha)?
       } else {
         false
       };
@@ -162,30 +162,6 @@ fn commit_exists(repo_path: &std::path::Path, sha: &str) -> RailResult<bool> {
   Ok(output.status.success())
 }
 
-/// Get target repository path for a split config
-fn get_target_path(
-  current_dir: &std::path::Path,
-  split_config: &crate::core::config::SplitConfig,
-) -> Option<std::path::PathBuf> {
-  let target_repo_path = if utils::is_local_path(&split_config.remote) {
-    std::path::PathBuf::from(&split_config.remote)
-  } else {
-    let remote_name = split_config
-      .remote
-      .rsplit('/')
-      .next()
-      .unwrap_or(&split_config.name)
-      .trim_end_matches(".git");
-    current_dir.join("..").join(remote_name)
-  };
-
-  if target_repo_path.exists() {
-    Some(target_repo_path)
-  } else {
-    None
-  }
-}
-
 /// Print mappings as a formatted table
 fn print_mappings_table(crate_mappings: &CrateMappings, show_check: bool) {
   println!("\n🗺️  Git-Notes Mappings for '{}'", crate_mappings.crate_name);
@@ -7,10 +7,15 @@
 //! - **doctor**: Run health checks and validation
 //! - **status**: Show split/sync status for all crates
 //! - **mappings**: View git commit mappings for split crates
+//! - **cache**: List and prune the managed clones of split repos
 //!
 //! ## Split & Sync (Pillar 2)
 //! - **split**: Split monorepo crates to separate repositories
 //! - **sync**: Bidirectional sync between monorepo and split repos
+//! - **resolve**: Settle the conflicts a sync stopped on, then continue it
+//! - **undo**: Reverse an applied split, sync or release
+//! - **watch**: Poll and sync splits continuously until stopped
+//! - **retire**: Stop splitting a crate, archiving its split repo and mapping notes
 //!
 //! ## Graph Operations (Pillar 1)
 //! - **affected**: Find crates affected by changes
@@ -27,6 +32,7 @@
 //! All commands accept `&WorkspaceContext` to avoid redundant workspace loads.
 
 pub mod affected;
+pub mod cache;
 pub mod check;
 pub mod clippy;
 pub mod doctor;
@@ -35,12 +41,17 @@ pub mod lint;
 pub mod mappings;
 pub mod quality;
 pub mod release;
+pub mod resolve;
+pub mod retire;
 pub mod split;
 pub mod status;
 pub mod sync;
 pub mod test;
+pub mod undo;
+pub mod watch;
 
 pub use affected::run_affected;
+pub use cache::{run_cache_ls, run_cache_prune};
 pub use check::run_check;
 pub use clippy::run_clippy;
 pub use doctor::run_doctor;
@@ -49,7 +60,11 @@ pub use lint::{run_lint_deps, run_lint_manifest, run_lint_versions};
 pub use mappings::run_mappings;
 pub use quality::{apply_fixes, run_quality};
 pub use release::{run_release_apply, run_release_plan};
+pub use resolve::run_resolve;
+pub use retire::run_retire;
 pub use split::run_split;
 pub use status::run_status;
 pub use sync::run_sync;
 pub use test::run_test;
+pub use undo::run_undo;
+pub use watch::run_watch;
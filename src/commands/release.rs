@@ -8,6 +8,8 @@
 use crate::core::config::ReleaseConfig;
 use crate::core::context::WorkspaceContext;
 use crate::core::error::{RailError, RailResult};
+use crate::core::history::{self, AppliedPlan, Effect};
+use crate::core::plan::{OperationType, PlanId};
 use crate::quality::changelog::{Changelog, ChangelogFormat, ConventionalCommit};
 use crate::release::{ReleasePlan, ReleaseTracker, VersionBump};
 
@@ -121,6 +123,16 @@ pub fn run_release_apply(
   // Apply the release
   println!("✅ Applying release...");
 
+  // Snapshot everything the release rewrites so it can be undone
+  let root = ctx.workspace_root();
+  let mut effects = vec![history::snapshot_file(
+    &root.join(&release.crate_path).join("Cargo.toml"),
+  )?];
+  if let Some(changelog_path) = &release.changelog {
+    effects.push(history::snapshot_file(&root.join(changelog_path))?);
+  }
+  effects.push(history::snapshot_file(&root.join("rail.toml"))?);
+
   // 1. Update Cargo.toml version
   update_crate_version(
     ctx.workspace_root(),
@@ -147,6 +159,18 @@ pub fn run_release_apply(
   create_git_tag(ctx.workspace_root(), &name, &plan.proposed_version.to_string())?;
   println!("   Created tag: {}-v{}", name, plan.proposed_version);
 
+  // 6. Record the release for `cargo rail undo`
+  effects.push(Effect::Tag {
+    repo: root.to_path_buf(),
+    name: format!("{}-v{}", name, plan.proposed_version),
+  });
+  let plan_id = PlanId::from_contents(format!("release:{}:{}:{}", name, plan.proposed_version, head_sha).as_bytes());
+  AppliedPlan::record(root, &plan_id, OperationType::Release, Some(name.clone()), effects)?;
+  println!(
+    "   Recorded as plan {} (undo with: cargo rail undo {})",
+    plan_id, plan_id
+  );
+
   println!();
   println!("✅ Release {} completed!", plan.proposed_version);
   println!();
@@ -0,0 +1,216 @@
+//! Resolve command implementation
+//!
+//! Settles the conflicts a `--strategy manual` sync stopped on and continues it, like
+//! `git rebase --continue`. Paths are relative to the workspace root.
+
+use crate::commands::sync::run_sync;
+use crate::core::context::WorkspaceContext;
+use crate::core::error::{RailError, RailResult};
+use crate::core::resolve::{ConflictState, Resolution};
+use std::path::{Path, PathBuf};
+
+/// How to settle the selected files
+#[derive(Debug, Clone)]
+pub enum ResolveAction {
+  /// Take one side, or both (union)
+  Pick(Resolution),
+  /// Open `$VISUAL`/`$EDITOR`
+  Edit,
+  /// Run a git-configured mergetool (default: `merge.tool`)
+  Tool(Option<String>),
+}
+
+/// Run the resolve command
+pub fn run_resolve(
+  ctx: &WorkspaceContext,
+  crate_name: Option<String>,
+  paths: Vec<PathBuf>,
+  action: Option<ResolveAction>,
+  continue_sync: bool,
+  json: bool,
+) -> RailResult<()> {
+  let workspace_root = ctx.workspace_root();
+  let mut states = ConflictState::list(workspace_root)?;
+
+  let mut state = match crate_name {
+    Some(name) => ConflictState::load(workspace_root, &name)?.ok_or_else(|| {
+      RailError::with_help(
+        format!("No sync of '{}' is stopped on conflicts", name),
+        "List stopped syncs with `cargo rail resolve`",
+      )
+    })?,
+    None if action.is_none() && !continue_sync => return list_stopped(workspace_root, &states, json),
+    None if states.len() == 1 => states.remove(0),
+    None if states.is_empty() => return Err(RailError::message("No sync is stopped on conflicts")),
+    None => {
+      return Err(RailError::with_help(
+        "Several syncs are stopped on conflicts",
+        "Name the crate: cargo rail resolve <crate> ...",
+      ));
+    }
+  };
+
+  if let Some(action) = action {
+    let targets = if paths.is_empty() {
+      state
+        .unresolved(workspace_root)?
+        .iter()
+        .map(|file| file.path.clone())
+        .collect()
+    } else {
+      paths
+    };
+
+    for path in &targets {
+      let settled = match action {
+        ResolveAction::Pick(resolution) => {
+          state.pick(workspace_root, path, resolution)?;
+          true
+        }
+        ResolveAction::Edit => state.edit(workspace_root, path)?,
+        ResolveAction::Tool(ref tool) => state.mergetool(workspace_root, path, tool.as_deref())?,
+      };
+
+      if settled {
+        println!("   ✅ Resolved {}", path.display());
+      } else {
+        println!("   ⚠️  {} still has conflict markers", path.display());
+      }
+    }
+  }
+
+  if continue_sync {
+    return continue_stopped(ctx, state);
+  }
+
+  if json {
+    println!(
+      "{}",
+      serde_json::to_string_pretty(&state_json(workspace_root, &state)?)?
+    );
+  } else {
+    print_state(workspace_root, &state)?;
+  }
+  Ok(())
+}
+
+/// Commit the stopped commit and resume the sync from its journal
+fn continue_stopped(ctx: &WorkspaceContext, state: ConflictState) -> RailResult<()> {
+  let unresolved = state.unresolved(ctx.workspace_root())?;
+  if !unresolved.is_empty() {
+    let paths: Vec<String> = unresolved.iter().map(|f| f.path.display().to_string()).collect();
+    return Err(RailError::with_help(
+      format!("Conflict markers remain in: {}", paths.join(", ")),
+      format!(
+        "Pick a side with `cargo rail resolve {} <path> --ours|--theirs|--union`, or edit the files",
+        state.crate_name
+      ),
+    ));
+  }
+
+  println!("▶️  Continuing sync of '{}'", state.crate_name);
+  // Branch protection was checked when the sync started; it carries on where it stopped
+  run_sync(
+    ctx,
+    Some(state.crate_name.clone()),
+    false,
+    Some(state.remote_url.clone()),
+    true,
+    false,
+    state.strategy.clone(),
+    None,
+    None,
+    true,
+    false,
+    true,
+    false,
+    0,
+    false,
+    true,
+    false,
+  )
+}
+
+/// List every stopped sync
+fn list_stopped(workspace_root: &Path, states: &[ConflictState], json: bool) -> RailResult<()> {
+  if json {
+    let states = states
+      .iter()
+      .map(|state| state_json(workspace_root, state))
+      .collect::<RailResult<Vec<_>>>()?;
+    println!("{}", serde_json::to_string_pretty(&states)?);
+    return Ok(());
+  }
+
+  if states.is_empty() {
+    println!("No sync is stopped on conflicts");
+    return Ok(());
+  }
+
+  for state in states {
+    print_state(workspace_root, state)?;
+    println!();
+  }
+  Ok(())
+}
+
+fn print_state(workspace_root: &Path, state: &ConflictState) -> RailResult<()> {
+  let unresolved = state.unresolved(workspace_root)?;
+
+  println!(
+    "⚠️  Sync of '{}' stopped replaying {} \"{}\" onto '{}'",
+    state.crate_name,
+    &state.commit.sha[..state.commit.sha.len().min(7)],
+    state.commit.message.lines().next().unwrap_or_default(),
+    state.branch
+  );
+  for file in &state.files {
+    let status = if unresolved.iter().any(|u| u.path == file.path) {
+      "conflict markers".to_string()
+    } else {
+      match file.resolution {
+        Some(resolution) => format!("resolved: {}", resolution),
+        None => "resolved".to_string(),
+      }
+    };
+    println!("   {} ({})", file.path.display(), status);
+  }
+
+  if unresolved.is_empty() {
+    println!("\n💡 All conflicts resolved. Continue with:");
+    println!("   cargo rail resolve {} --continue", state.crate_name);
+  } else {
+    println!("\n💡 Settle each file, then continue:");
+    println!(
+      "   cargo rail resolve {} [<path>...] --ours|--theirs|--union|--edit|--tool [<name>]",
+      state.crate_name
+    );
+    println!("   cargo rail resolve {} --continue", state.crate_name);
+  }
+  Ok(())
+}
+
+fn state_json(workspace_root: &Path, state: &ConflictState) -> RailResult<serde_json::Value> {
+  let unresolved = state.unresolved(workspace_root)?;
+  let files: Vec<serde_json::Value> = state
+    .files
+    .iter()
+    .map(|file| {
+      serde_json::json!({
+        "path": file.path,
+        "message": file.message,
+        "resolution": file.resolution,
+        "resolved": !unresolved.iter().any(|u| u.path == file.path),
+      })
+    })
+    .collect();
+
+  Ok(serde_json::json!({
+    "crate": state.crate_name,
+    "remote": state.remote_url,
+    "branch": state.branch,
+    "commit": state.commit.sha,
+    "summary": state.commit.message.lines().next().unwrap_or_default(),
+    "files": files,
+  }))
+}
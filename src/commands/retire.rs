@@ -0,0 +1,256 @@
+//! Retire command implementation
+//!
+//! Stops splitting a crate: optionally commits an archival README to the split repo, archives
+//! its mapping notes and removes its `[[splits]]` entry (and `[[releases]]` links) from
+//! rail.toml. Like split and sync, it's a `Plan` shown as a dry-run until `--apply`.
+
+use std::path::Path;
+
+use crate::core::auth::GitAuth;
+use crate::core::cache;
+use crate::core::closure;
+use crate::core::config::{RailConfig, RemoteConfig, SplitConfig};
+use crate::core::context::WorkspaceContext;
+use crate::core::error::{ConfigError, RailError, RailResult, ResultExt};
+use crate::core::executor::PlanExecutor;
+use crate::core::journal::{SyncJournal, SyncLock};
+use crate::core::mapping::MappingStore;
+use crate::core::plan::{Operation, OperationType, Plan};
+use crate::core::resolve::ConflictState;
+use crate::core::retire;
+use crate::core::sync::unsynced_mono_commits;
+use crate::core::vcs::SystemGit;
+use crate::utils;
+
+/// Retire command parameters
+pub struct RetireParams {
+  pub crate_name: String,
+  /// Push a final commit with an archival README to the split repo
+  pub archive: bool,
+  /// Monorepo URL the README links to (default: the monorepo's `origin`)
+  pub mono_url: Option<String>,
+  pub apply: bool,
+  pub json: bool,
+}
+
+/// Run the retire command
+pub fn run_retire(ctx: &WorkspaceContext, params: RetireParams) -> RailResult<()> {
+  let RetireParams {
+    crate_name,
+    archive,
+    mono_url,
+    apply,
+    json,
+  } = params;
+  let workspace_root = ctx.workspace_root();
+  let config = ctx.require_config()?;
+
+  let split_config = config.splits.iter().find(|s| s.name == crate_name).ok_or_else(|| {
+    RailError::Config(ConfigError::CrateNotFound {
+      name: crate_name.clone(),
+    })
+  })?;
+  ensure_not_syncing(workspace_root, &crate_name)?;
+
+  let target_repo_path = config.target_repo_path(workspace_root, split_config)?;
+  let mono_url = mono_url.or_else(|| origin_url(workspace_root));
+  let plan = retire_plan(
+    ctx,
+    config,
+    split_config,
+    &target_repo_path,
+    archive,
+    mono_url.as_deref(),
+  )?;
+
+  if !apply {
+    if json {
+      println!("{}", plan.to_json()?);
+    } else {
+      println!("\n🔍 DRY-RUN MODE - No changes will be made");
+      println!("   Add --apply to retire the split\n");
+      println!("{}", plan.to_human_readable());
+      if archive && !target_repo_path.exists() && !utils::is_local_path(&split_config.remote) {
+        println!("   Split repo will be cloned into the cache first\n");
+      }
+      println!("💡 To execute this plan, run:");
+      println!(
+        "   cargo rail retire {}{} --apply",
+        crate_name,
+        if archive { " --archive" } else { "" }
+      );
+    }
+    return Ok(());
+  }
+
+  if archive && !target_repo_path.exists() {
+    if utils::is_local_path(&split_config.remote) {
+      return Err(RailError::with_help(
+        format!("Split repo not found at {}", target_repo_path.display()),
+        format!("Retire without --archive: cargo rail retire {} --apply", crate_name),
+      ));
+    }
+    println!("   Cloning {} into {}", split_config.remote, target_repo_path.display());
+    cache::clone_split(config, workspace_root, split_config, &target_repo_path)?;
+  }
+
+  // Keeps a sync (or watcher round) from starting while the split is taken apart
+  let _lock = SyncLock::acquire(workspace_root, &crate_name)?;
+  println!("🗄️  Retiring split '{}'", crate_name);
+  PlanExecutor::new(ctx).execute(&plan)?;
+
+  println!("\n✅ Retired '{}'", crate_name);
+  println!("\nNext steps:");
+  let config_path = RailConfig::find_config_path(workspace_root).unwrap_or_else(|| workspace_root.join("rail.toml"));
+  let config_file = config_path.strip_prefix(workspace_root).unwrap_or(&config_path);
+  println!(
+    "  git commit {} -m \"Retire the {} split\"",
+    config_file.display(),
+    crate_name
+  );
+  if archive && !utils::is_local_path(&split_config.remote) {
+    println!("  Archive {} on its host to make it read-only", split_config.remote);
+  }
+  if target_repo_path.starts_with(cache::cache_root(workspace_root, &config.cache)?) {
+    println!("  cargo rail cache prune --apply  # drop the cached clone");
+  }
+
+  Ok(())
+}
+
+/// Plan retiring one split
+fn retire_plan(
+  ctx: &WorkspaceContext,
+  config: &RailConfig,
+  split_config: &SplitConfig,
+  target_repo_path: &Path,
+  archive: bool,
+  mono_url: Option<&str>,
+) -> RailResult<Plan> {
+  let workspace_root = ctx.workspace_root();
+  let name = &split_config.name;
+  let mut plan = Plan::new(OperationType::Retire, Some(name.clone()));
+  let mut summary = vec![format!("Retire split '{}': it will no longer be synced", name)];
+
+  if archive {
+    let crate_paths: Vec<String> = split_config
+      .paths
+      .iter()
+      .map(|p| p.path.display().to_string())
+      .collect();
+    let readme_path = split_config
+      .target_dir
+      .as_ref()
+      .map(|dir| dir.join("README.md"))
+      .unwrap_or_else(|| "README.md".into());
+    plan.add_operation(Operation::ArchiveSplitRepo {
+      crate_name: name.clone(),
+      target_repo_path: target_repo_path.display().to_string(),
+      branch: split_config.branch.clone(),
+      remote_url: split_config.remote.clone(),
+      readme_path: readme_path.display().to_string(),
+      readme: retire::archive_readme(name, &crate_paths, mono_url),
+      message: format!("Archive {}: development continues in the monorepo", name),
+      mirrors: split_config.mirrors(),
+      auth: GitAuth::for_split(split_config, &config.security, workspace_root),
+    });
+
+    // Whatever wasn't synced never reaches the split repo once it's archived
+    let closure = closure::resolve(ctx.graph.as_ref(), workspace_root, split_config, &config.splits)?;
+    let mut mappings = MappingStore::new(name.clone());
+    mappings.load(workspace_root)?;
+    let unsynced = unsynced_mono_commits(&SystemGit::open(workspace_root)?, &mappings, &closure.crate_paths)?;
+    if !unsynced.is_empty() {
+      summary.push(format!(
+        "⚠️  {} monorepo commit(s) were never synced; to publish them first: cargo rail sync {} --to-remote --apply",
+        unsynced.len(),
+        name
+      ));
+    }
+  }
+
+  if retire::has_notes(workspace_root, name) {
+    plan.add_operation(Operation::ArchiveNotes {
+      repo: workspace_root.display().to_string(),
+      crate_name: name.clone(),
+      remotes: Vec::new(),
+      auth: GitAuth::default(),
+    });
+  }
+
+  // The split repo has its own copy of the notes, also pushed to every remote
+  let remotes: Vec<RemoteConfig> = split_config
+    .all_remotes()
+    .into_iter()
+    .filter(|r| !utils::is_local_path(&r.url))
+    .collect();
+  if target_repo_path.exists() || (archive && !utils::is_local_path(&split_config.remote)) {
+    plan.add_operation(Operation::ArchiveNotes {
+      repo: target_repo_path.display().to_string(),
+      crate_name: name.clone(),
+      remotes,
+      auth: GitAuth::for_split(split_config, &config.security, workspace_root),
+    });
+  } else if !remotes.is_empty() {
+    summary.push(format!(
+      "⚠️  The split repo isn't checked out, so refs/notes/rail/{0} stays on its remotes; to remove it: git push <remote> --delete refs/notes/rail/{0}",
+      name
+    ));
+  }
+
+  let config_path = RailConfig::find_config_path(workspace_root).ok_or_else(|| {
+    RailError::Config(ConfigError::NotFound {
+      workspace_root: workspace_root.to_path_buf(),
+    })
+  })?;
+  let content =
+    std::fs::read_to_string(&config_path).with_context(|| format!("Failed to read {}", config_path.display()))?;
+  let (content, changes) = retire::retired_config(&content, name)?;
+  plan.add_operation(Operation::UpdateConfig {
+    path: config_path.display().to_string(),
+    content,
+    changes,
+  });
+
+  Ok(plan.with_summary(summary.join("\n")))
+}
+
+/// Refuse to retire a split whose sync is running, stopped on conflicts or interrupted
+fn ensure_not_syncing(workspace_root: &Path, crate_name: &str) -> RailResult<()> {
+  if SyncLock::is_held(workspace_root, crate_name)? {
+    return Err(RailError::with_help(
+      format!("A sync of '{}' is running", crate_name),
+      "Wait for it to finish (or stop `cargo rail watch`), then retire the split",
+    ));
+  }
+  if ConflictState::load(workspace_root, crate_name)?.is_some() {
+    return Err(RailError::with_help(
+      format!("A sync of '{}' is stopped on conflicts", crate_name),
+      format!(
+        "Finish it with `cargo rail resolve {0} --continue` or roll it back with `cargo rail sync {0} --abort`",
+        crate_name
+      ),
+    ));
+  }
+  if SyncJournal::load(workspace_root, crate_name)?.is_some() {
+    return Err(RailError::with_help(
+      format!("A sync of '{}' was interrupted", crate_name),
+      format!(
+        "Finish it with `cargo rail sync {0} --resume` or roll it back with `cargo rail sync {0} --abort`",
+        crate_name
+      ),
+    ));
+  }
+  Ok(())
+}
+
+/// The monorepo's `origin` URL, if it has one
+fn origin_url(workspace_root: &Path) -> Option<String> {
+  SystemGit::open(workspace_root)
+    .ok()?
+    .list_remotes()
+    .ok()?
+    .into_iter()
+    .find(|(name, _)| name == "origin")
+    .map(|(_, url)| url)
+}
@@ -1,13 +1,15 @@
 use std::io::{self, Write};
 
 use crate::commands::doctor;
+use crate::core::auth::GitAuth;
+use crate::core::cache;
+use crate::core::closure;
 use crate::core::context::WorkspaceContext;
 use crate::core::error::{ConfigError, RailError, RailResult};
 use crate::core::executor::PlanExecutor;
 use crate::core::plan::{Operation, OperationType, Plan};
-use crate::ui::progress::{FileProgress, MultiProgress};
+use crate::ui::progress::FileProgress;
 use crate::utils;
-use rayon::prelude::*;
 
 /// Prompt user for confirmation
 fn prompt_for_confirmation(message: &str) -> RailResult<bool> {
@@ -22,11 +24,15 @@ fn prompt_for_confirmation(message: &str) -> RailResult<bool> {
 }
 
 /// Run the split command
+#[allow(clippy::too_many_arguments)]
 pub fn run_split(
   ctx: &WorkspaceContext,
   crate_name: Option<String>,
   all: bool,
   remote: Option<String>,
+  verify_build: bool,
+  jobs: usize,
+  fail_fast: bool,
   apply: bool,
   json: bool,
 ) -> RailResult<()> {
@@ -114,20 +120,11 @@ pub fn run_split(
   let mut plans = Vec::new();
 
   for split_config in &crates_to_split {
-    let crate_paths = split_config.get_paths().into_iter().cloned().collect::<Vec<_>>();
+    // Resolve unpublished workspace deps (bundled paths extend the split)
+    let closure = closure::resolve(ctx.graph.as_ref(), ctx.workspace_root(), split_config, &config.splits)?;
+    let crate_paths = closure.crate_paths;
 
-    // Determine target repo path
-    let target_repo_path = if utils::is_local_path(&split_config.remote) {
-      std::path::PathBuf::from(&split_config.remote)
-    } else {
-      let remote_name = split_config
-        .remote
-        .rsplit('/')
-        .next()
-        .unwrap_or(&split_config.name)
-        .trim_end_matches(".git");
-      ctx.workspace_root().join("..").join(remote_name)
-    };
+    let target_repo_path = config.target_repo_path(ctx.workspace_root(), split_config)?;
 
     // Build unified Plan with ExecuteSplit operation
     let mut plan = Plan::new(OperationType::Split, Some(split_config.name.clone()));
@@ -137,9 +134,18 @@ pub fn run_split(
       crate_name: split_config.name.clone(),
       crate_paths: crate_paths.iter().map(|p| p.display().to_string()).collect(),
       mode: format!("{:?}", split_config.mode),
+      workspace_mode: format!("{:?}", split_config.workspace_mode),
       target_repo_path: target_repo_path.display().to_string(),
       branch: split_config.branch.clone(),
       remote_url: Some(split_config.remote.clone()),
+      target_dir: split_config.target_dir.as_ref().map(|d| d.display().to_string()),
+      lockfile: split_config
+        .lockfile
+        .unwrap_or_else(|| ctx.metadata.has_bin_target(&crate_paths)),
+      verify_build,
+      dependency_sources: closure.sources.clone(),
+      mirrors: split_config.mirrors(),
+      auth: GitAuth::for_split(split_config, &config.security, ctx.workspace_root()),
     });
 
     // Add metadata
@@ -177,16 +183,23 @@ pub fn run_split(
           println!("     • {}", path.display());
         }
         println!("   Target: {}", target_repo_path.display());
+        if let Some(ref target_dir) = split_config.target_dir {
+          println!("   Target dir: {}", target_dir.display());
+        }
         println!("   Remote: {}", split_config.remote);
         println!("   Branch: {}", split_config.branch);
+        if verify_build {
+          println!("   Verify build: cargo metadata / check / package --list (offline)");
+        }
         println!();
       }
 
       println!("✋ To execute this plan, run:");
+      let verify_flag = if verify_build { " --verify-build" } else { "" };
       if all {
-        println!("   cargo rail split --all --apply");
+        println!("   cargo rail split --all{} --apply", verify_flag);
       } else if let Some(ref name) = crate_name {
-        println!("   cargo rail split {} --apply", name);
+        println!("   cargo rail split {}{} --apply", name, verify_flag);
       }
       println!();
 
@@ -205,62 +218,44 @@ pub fn run_split(
     println!("\n🚀 APPLY MODE - Executing split operations\n");
   }
 
-  // Use existing workspace context for execution
-  let executor = PlanExecutor::new(ctx);
-
-  let plan_count = plans.len();
-
-  // Use parallel processing for multiple crates
-  if plan_count > 1 && all {
-    println!("🚀 Processing {} crates in parallel...\n", plan_count);
-
-    let multi_progress = MultiProgress::new();
-    let bars: Vec<_> = plans
+  // New split repos of remote splits are created in the cache
+  if plans
+    .iter()
+    .any(|(split_config, ..)| !utils::is_local_path(&split_config.remote))
+  {
+    let cache_dir = cache::workspace_cache_dir(ctx.workspace_root(), &config.cache)?;
+    if plans
       .iter()
-      .map(|(split_config, _, _, _)| multi_progress.add_bar(1, format!("Splitting {}", split_config.name)))
-      .collect();
-
-    // For parallel execution, we need to build contexts per-thread
-    let workspace_root = ctx.workspace_root().to_path_buf();
-    let results: Vec<RailResult<()>> = plans
-      .into_par_iter()
-      .enumerate()
-      .map(|(idx, (_, _, _, plan))| {
-        // Build workspace context for this thread
-        let thread_context = WorkspaceContext::build(&workspace_root)?;
-        let thread_executor = PlanExecutor::new(&thread_context);
-        let result = thread_executor.execute(&plan);
+      .any(|(_, _, target_repo_path, _)| target_repo_path.starts_with(&cache_dir))
+    {
+      cache::record_workspace(ctx.workspace_root(), &config.cache)?;
+    }
+  }
 
-        multi_progress.inc(&bars[idx]);
-        result
-      })
-      .collect();
+  // Use existing workspace context for execution
+  let executor = PlanExecutor::new(ctx);
 
-    // Check for errors
-    for result in results {
-      result?;
+  // --all: splits run concurrently, summarized together
+  if all {
+    println!("🚀 Processing {} crates in parallel...\n", plans.len());
+    let batch = executor.execute_batch(
+      plans
+        .into_iter()
+        .map(|(split_config, _, _, plan)| (split_config.name, plan))
+        .collect(),
+      jobs,
+      fail_fast,
+    )?;
+
+    println!("\n📊 Split summary:\n{}", batch.to_table());
+    if json {
+      println!("{}", serde_json::to_string_pretty(&batch)?);
     }
+    batch.into_result()?;
   } else {
-    // Sequential processing for single crate or when not using --all
-    let mut crate_progress = if plan_count > 1 {
-      Some(FileProgress::new(
-        plan_count,
-        format!("Splitting {} crates", plan_count),
-      ))
-    } else {
-      None
-    };
-
     for (split_config, _, _, plan) in plans {
-      if crate_progress.is_none() {
-        println!("🔨 Splitting crate '{}'...", split_config.name);
-      }
-
+      println!("🔨 Splitting crate '{}'...", split_config.name);
       executor.execute(&plan)?;
-
-      if let Some(ref mut p) = crate_progress {
-        p.inc();
-      }
       println!();
     }
   }
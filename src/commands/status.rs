@@ -1,9 +1,20 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::core::auth::GitAuth;
+use crate::core::closure;
+use crate::core::config::{RailConfig, SplitConfig};
 use crate::core::context::WorkspaceContext;
 use crate::core::error::{RailError, RailResult};
+use crate::core::history::AppliedPlan;
+use crate::core::journal::SyncJournal;
+use crate::core::mapping::MappingStore;
+use crate::core::plan::OperationType;
+use crate::core::resolve::ConflictState;
+use crate::core::security::SecurityValidator;
+use crate::core::sync::{unsynced_mono_commits, unsynced_remote_commits};
+use crate::core::vcs::{CommitInfo, SystemGit};
 use crate::utils;
 
 /// Status of a crate
@@ -32,6 +43,80 @@ pub enum SyncStatus {
   Diverged { ahead: u64, behind: u64 },
 }
 
+/// Where a mirror's branch stands relative to the split repo's
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MirrorDrift {
+  /// Same commit as the split repo
+  InSync,
+  /// Missing the split repo's latest N commits
+  Behind { commits: u64 },
+  /// Has commits the split repo doesn't
+  Diverged,
+  /// Branch doesn't exist on the mirror yet
+  Missing,
+  /// `git ls-remote` failed
+  Unreachable,
+}
+
+/// Drift of one mirror remote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorStatus {
+  pub name: String,
+  pub url: String,
+  pub drift: MirrorDrift,
+}
+
+/// A commit one side has that the other hasn't received
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingCommit {
+  pub sha: String,
+  /// First line of the message
+  pub summary: String,
+}
+
+/// The most recent applied sync of a split
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastSync {
+  pub plan_id: String,
+  /// RFC 3339
+  pub applied_at: String,
+}
+
+/// A sync that is waiting on the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum StoppedSync {
+  /// Stopped on conflicts; settle them with `cargo rail resolve`
+  Conflicts {
+    /// Remote commit being replayed
+    commit: String,
+    /// Files still holding conflict markers
+    unresolved: Vec<PathBuf>,
+  },
+  /// Interrupted; finish with `cargo rail sync --resume` or roll back with `--abort`
+  Interrupted {
+    /// Commits applied before the interruption
+    applied: usize,
+  },
+}
+
+/// Whether the local clone of the split repo has seen the remote's latest branch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetClone {
+  /// Tracking branch matches the remote
+  Current,
+  /// The remote's branch moved since the last fetch
+  Stale,
+  /// The split repo is a local path, used directly
+  Local,
+  /// `git ls-remote` failed
+  Unreachable,
+  /// Not cloned yet
+  Missing,
+}
+
 /// Status information for a single crate
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrateStatus {
@@ -52,6 +137,28 @@ pub struct CrateStatus {
 
   /// Target repository path (if it exists)
   pub target_path: Option<PathBuf>,
+
+  /// Mirror remotes, compared with the split repo's branch
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub mirrors: Vec<MirrorStatus>,
+
+  /// Monorepo commits touching the split's paths that haven't been synced to the remote
+  pub unmapped_mono_commits: Vec<PendingCommit>,
+
+  /// Split repo commits that haven't been imported (as of the clone's last fetch)
+  pub unmapped_remote_commits: Vec<PendingCommit>,
+
+  /// Most recent sync that changed something
+  pub last_sync: Option<LastSync>,
+
+  /// Remote → mono PR branches not merged yet
+  pub pr_branches: Vec<String>,
+
+  /// Sync stopped on conflicts or interrupted
+  pub stopped: Option<StoppedSync>,
+
+  /// Freshness of the split repo clone
+  pub target_clone: TargetClone,
 }
 
 /// Run the status command
@@ -64,20 +171,14 @@ pub fn run_status(ctx: &WorkspaceContext, json: bool) -> RailResult<()> {
   // Gather status for all crates
   let mut statuses = Vec::new();
 
+  let mono_git = SystemGit::open(workspace_root)?;
+  let pr_base = pr_base_branch(&mono_git, config);
+
   for split_config in &config.splits {
-    let target_repo_path = if utils::is_local_path(&split_config.remote) {
-      std::path::PathBuf::from(&split_config.remote)
-    } else {
-      let remote_name = split_config
-        .remote
-        .rsplit('/')
-        .next()
-        .unwrap_or(&split_config.name)
-        .trim_end_matches(".git");
-      workspace_root.join("..").join(remote_name)
-    };
+    let target_repo_path = config.target_repo_path(workspace_root, split_config)?;
 
     let target_exists = target_repo_path.exists();
+    let auth = GitAuth::for_split(split_config, &config.security, workspace_root);
 
     // Check if split exists by checking git-notes (more reliable than directory check)
     let has_git_notes = check_git_notes_exist(workspace_root, &split_config.name)?;
@@ -102,9 +203,39 @@ pub fn run_status(ctx: &WorkspaceContext, json: bool) -> RailResult<()> {
       None
     };
 
+    let mirrors = if target_exists {
+      split_config
+        .mirrors()
+        .into_iter()
+        .map(|mirror| MirrorStatus {
+          drift: check_mirror_drift(
+            &target_repo_path,
+            &split_config.branch,
+            &mirror.url,
+            &auth.for_remote(&mirror, workspace_root),
+          ),
+          name: mirror.name,
+          url: mirror.url,
+        })
+        .collect()
+    } else {
+      Vec::new()
+    };
+
     // Check for dirty state in monorepo paths
     let dirty = check_dirty_state(workspace_root, split_config.get_paths())?;
 
+    let (unmapped_mono_commits, unmapped_remote_commits) =
+      pending_commits(ctx, config, split_config, &mono_git, &target_repo_path)?;
+
+    let target_clone = if !target_exists {
+      TargetClone::Missing
+    } else if utils::is_local_path(&split_config.remote) {
+      TargetClone::Local
+    } else {
+      check_target_clone(&target_repo_path, &split_config.branch, &auth)
+    };
+
     statuses.push(CrateStatus {
       name: split_config.name.clone(),
       split_status,
@@ -112,6 +243,13 @@ pub fn run_status(ctx: &WorkspaceContext, json: bool) -> RailResult<()> {
       dirty,
       remote: split_config.remote.clone(),
       target_path: if target_exists { Some(target_repo_path) } else { None },
+      mirrors,
+      unmapped_mono_commits,
+      unmapped_remote_commits,
+      last_sync: last_sync(workspace_root, &split_config.name)?,
+      pr_branches: open_pr_branches(&mono_git, config, &split_config.name, &pr_base),
+      stopped: stopped_sync(workspace_root, &split_config.name)?,
+      target_clone,
     });
   }
 
@@ -128,6 +266,152 @@ pub fn run_status(ctx: &WorkspaceContext, json: bool) -> RailResult<()> {
   Ok(())
 }
 
+/// Commits each side has that the other hasn't received, in the order they'd be synced
+fn pending_commits(
+  ctx: &WorkspaceContext,
+  config: &RailConfig,
+  split_config: &SplitConfig,
+  mono_git: &SystemGit,
+  target_repo_path: &Path,
+) -> RailResult<(Vec<PendingCommit>, Vec<PendingCommit>)> {
+  let workspace_root = ctx.workspace_root();
+  let mut mappings = MappingStore::new(split_config.name.clone());
+  mappings.load(workspace_root)?;
+
+  let closure = closure::resolve(ctx.graph.as_ref(), workspace_root, split_config, &config.splits)?;
+  let mono = unsynced_mono_commits(mono_git, &mappings, &closure.crate_paths)?;
+
+  if !target_repo_path.exists() {
+    return Ok((pending(mono), Vec::new()));
+  }
+  mappings.load(target_repo_path)?;
+  let remote_git = SystemGit::open(target_repo_path)?;
+
+  // A clone is compared as of its last fetch; a local split repo is read directly
+  let branch_ref = if utils::is_local_path(&split_config.remote) {
+    split_config.branch.clone()
+  } else {
+    format!("origin/{}", split_config.branch)
+  };
+  if remote_git.resolve_reference(&branch_ref).is_err() {
+    return Ok((pending(mono), Vec::new()));
+  }
+  let subtree = match split_config.target_dir {
+    Some(ref dir) => dir.as_path(),
+    None => Path::new("."),
+  };
+  let remote = unsynced_remote_commits(&remote_git, &mappings, subtree, &branch_ref)?;
+
+  Ok((pending(mono), pending(remote)))
+}
+
+/// Summaries of commits (oldest first, as a sync replays them)
+fn pending(commits: Vec<CommitInfo>) -> Vec<PendingCommit> {
+  commits
+    .into_iter()
+    .map(|c| PendingCommit {
+      summary: c.message.lines().next().unwrap_or_default().to_string(),
+      sha: c.sha,
+    })
+    .collect()
+}
+
+/// The split's most recently applied sync plan
+fn last_sync(workspace_root: &Path, crate_name: &str) -> RailResult<Option<LastSync>> {
+  let plans = AppliedPlan::list(workspace_root)?;
+  Ok(
+    plans
+      .iter()
+      .filter(|plan| plan.operation_type == OperationType::Sync && plan.crate_name.as_deref() == Some(crate_name))
+      .find_map(|plan| {
+        plan.latest().map(|application| LastSync {
+          plan_id: plan.id.full().to_string(),
+          applied_at: application.applied_at.clone(),
+        })
+      }),
+  )
+}
+
+/// Branch remote → mono PR branches are opened against: the first protected branch that exists
+fn pr_base_branch(mono_git: &SystemGit, config: &RailConfig) -> String {
+  config
+    .security
+    .protected_branches
+    .iter()
+    .find(|branch| mono_git.resolve_reference(&format!("refs/heads/{}", branch)).is_ok())
+    .cloned()
+    .unwrap_or_else(|| "HEAD".to_string())
+}
+
+/// Local PR branches of the split that aren't merged into `base`
+fn open_pr_branches(mono_git: &SystemGit, config: &RailConfig, crate_name: &str, base: &str) -> Vec<String> {
+  let glob = SecurityValidator::new(config.security.clone()).pr_branch_glob(crate_name);
+  mono_git
+    .git_cmd()
+    .args([
+      "for-each-ref",
+      "--format=%(refname:short)",
+      &format!("--no-merged={}", base),
+      &format!("refs/heads/{}", glob),
+    ])
+    .output()
+    .ok()
+    .filter(|o| o.status.success())
+    .map(|o| String::from_utf8_lossy(&o.stdout).lines().map(str::to_string).collect())
+    .unwrap_or_default()
+}
+
+/// A sync of the split waiting on `resolve`, `--resume` or `--abort`
+fn stopped_sync(workspace_root: &Path, crate_name: &str) -> RailResult<Option<StoppedSync>> {
+  if let Some(state) = ConflictState::load(workspace_root, crate_name)? {
+    return Ok(Some(StoppedSync::Conflicts {
+      commit: state.commit.sha.clone(),
+      unresolved: state
+        .unresolved(workspace_root)?
+        .into_iter()
+        .map(|file| file.path.clone())
+        .collect(),
+    }));
+  }
+  Ok(
+    SyncJournal::load(workspace_root, crate_name)?.map(|journal| StoppedSync::Interrupted {
+      applied: journal.applied.len(),
+    }),
+  )
+}
+
+/// Compare the clone's tracking branch with the remote's, asking the remote with `git ls-remote`
+fn check_target_clone(target_path: &Path, branch: &str, auth: &GitAuth) -> TargetClone {
+  let git = |args: &[&str]| {
+    let mut cmd = Command::new("git");
+    auth.apply(&mut cmd);
+    cmd
+      .current_dir(target_path)
+      .args(args)
+      .output()
+      .ok()
+      .filter(|o| o.status.success())
+      .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+  };
+
+  let Some(listing) = git(&["ls-remote", "origin", &format!("refs/heads/{}", branch)]) else {
+    return TargetClone::Unreachable;
+  };
+  let remote_tip = listing.split_whitespace().next().map(str::to_string);
+  let fetched_tip = git(&[
+    "rev-parse",
+    "--verify",
+    "--quiet",
+    &format!("refs/remotes/origin/{}", branch),
+  ]);
+
+  if remote_tip == fetched_tip {
+    TargetClone::Current
+  } else {
+    TargetClone::Stale
+  }
+}
+
 /// Check sync status between monorepo and target repo
 fn check_sync_status(
   _monorepo_path: &std::path::Path,
@@ -171,6 +455,44 @@ fn check_sync_status(
   Ok(SyncStatus::UpToDate)
 }
 
+/// Compare a mirror's branch with the split repo's, asking the mirror with `git ls-remote`
+fn check_mirror_drift(target_path: &std::path::Path, branch: &str, url: &str, auth: &GitAuth) -> MirrorDrift {
+  let git = |args: &[&str]| {
+    let mut cmd = Command::new("git");
+    auth.apply(&mut cmd);
+    cmd
+      .current_dir(target_path)
+      .args(args)
+      .output()
+      .ok()
+      .filter(|o| o.status.success())
+      .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+  };
+
+  let branch_ref = format!("refs/heads/{}", branch);
+  let Some(listing) = git(&["ls-remote", url, &branch_ref]) else {
+    return MirrorDrift::Unreachable;
+  };
+  let Some(mirror_tip) = listing.split_whitespace().next().map(str::to_string) else {
+    return MirrorDrift::Missing;
+  };
+  let Some(local_tip) = git(&["rev-parse", &branch_ref]) else {
+    return MirrorDrift::Diverged;
+  };
+
+  if mirror_tip == local_tip {
+    return MirrorDrift::InSync;
+  }
+  // An unknown commit, or one not behind ours, means the mirror moved on its own
+  if git(&["merge-base", "--is-ancestor", &mirror_tip, &local_tip]).is_none() {
+    return MirrorDrift::Diverged;
+  }
+  let commits = git(&["rev-list", "--count", &format!("{}..{}", mirror_tip, local_tip)])
+    .and_then(|count| count.parse().ok())
+    .unwrap_or(0);
+  MirrorDrift::Behind { commits }
+}
+
 /// Extract number after a given prefix in a string
 fn extract_number_after(text: &str, prefix: &str) -> u64 {
   text
@@ -259,7 +581,61 @@ fn print_status_table(statuses: &[CrateStatus]) {
       "{:<20} {:<12} {:<20} {:<10} {}",
       status.name, split_str, sync_str, dirty_str, remote_display
     );
+
+    for mirror in &status.mirrors {
+      let drift_str = match mirror.drift {
+        MirrorDrift::InSync => "in sync".to_string(),
+        MirrorDrift::Behind { commits } => format!("behind {}", commits),
+        MirrorDrift::Diverged => "diverged".to_string(),
+        MirrorDrift::Missing => "no branch".to_string(),
+        MirrorDrift::Unreachable => "unreachable".to_string(),
+      };
+      println!(
+        "  ↳ {:<16} {:<12} {:<20} {:<10} {}",
+        mirror.name, "mirror", drift_str, "", mirror.url
+      );
+    }
+
+    print_sync_details(status);
   }
 
   println!();
 }
+
+/// Lines under a crate's row: pending commits, last sync, PR branches and anything needing attention
+fn print_sync_details(status: &CrateStatus) {
+  if !status.unmapped_mono_commits.is_empty() || !status.unmapped_remote_commits.is_empty() {
+    println!(
+      "    pending: {} to remote, {} to monorepo",
+      status.unmapped_mono_commits.len(),
+      status.unmapped_remote_commits.len()
+    );
+  }
+  if let Some(ref last) = status.last_sync {
+    println!(
+      "    last sync: {} (plan {})",
+      last.applied_at,
+      &last.plan_id[..last.plan_id.len().min(12)]
+    );
+  }
+  if !status.pr_branches.is_empty() {
+    println!("    PR branches: {}", status.pr_branches.join(", "));
+  }
+  match status.stopped {
+    Some(StoppedSync::Conflicts { ref unresolved, .. }) => println!(
+      "    ⚠️  stopped on conflicts ({} unresolved): cargo rail resolve {}",
+      unresolved.len(),
+      status.name
+    ),
+    Some(StoppedSync::Interrupted { applied }) => println!(
+      "    ⚠️  interrupted after {} commit(s): cargo rail sync {} --resume (or --abort)",
+      applied, status.name
+    ),
+    None => {}
+  }
+  match status.target_clone {
+    TargetClone::Stale => println!("    ⚠️  split repo clone is stale; the next sync fetches it"),
+    TargetClone::Unreachable => println!("    ⚠️  split repo remote is unreachable"),
+    _ => {}
+  }
+}
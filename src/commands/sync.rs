@@ -1,13 +1,19 @@
 use crate::commands::doctor;
+use crate::core::auth::GitAuth;
+use crate::core::cache;
+use crate::core::closure;
+use crate::core::config::{RailConfig, SplitConfig};
 use crate::core::conflict::ConflictStrategy;
 use crate::core::context::WorkspaceContext;
 use crate::core::error::{ConfigError, RailError, RailResult};
 use crate::core::executor::PlanExecutor;
+use crate::core::journal::{SyncJournal, SyncLock};
 use crate::core::plan::{Operation, OperationType, Plan};
-use crate::core::sync::SyncDirection;
-use crate::ui::progress::{FileProgress, MultiProgress};
+use crate::core::resolve::ConflictState;
+use crate::core::sync::{DivergencePolicy, SyncDirection};
+use crate::ui::progress::FileProgress;
 use crate::utils;
-use rayon::prelude::*;
+use std::path::{Path, PathBuf};
 
 /// Sync command parameters
 pub struct SyncParams {
@@ -17,7 +23,15 @@ pub struct SyncParams {
   pub from_remote: bool,
   pub to_remote: bool,
   pub strategy_str: String,
+  pub on_diverged: Option<String>,
+  /// Bundle written (--to-remote) or read (--from-remote) instead of the remote
+  pub bundle: Option<PathBuf>,
   pub no_protected_branches: bool,
+  pub verify_build: bool,
+  pub resume: bool,
+  pub abort: bool,
+  pub jobs: usize,
+  pub fail_fast: bool,
   pub apply: bool,
   pub json: bool,
 }
@@ -32,7 +46,14 @@ pub fn run_sync(
   from_remote: bool,
   to_remote: bool,
   strategy_str: String,
+  on_diverged: Option<String>,
+  bundle: Option<PathBuf>,
   no_protected_branches: bool,
+  verify_build: bool,
+  resume: bool,
+  abort: bool,
+  jobs: usize,
+  fail_fast: bool,
   apply: bool,
   json: bool,
 ) -> RailResult<()> {
@@ -44,7 +65,14 @@ pub fn run_sync(
     from_remote,
     to_remote,
     strategy_str,
+    on_diverged,
+    bundle,
     no_protected_branches,
+    verify_build,
+    resume,
+    abort,
+    jobs,
+    fail_fast,
     apply,
     json,
   };
@@ -60,10 +88,19 @@ fn run_sync_impl(ctx: &WorkspaceContext, params: SyncParams) -> RailResult<()> {
     from_remote,
     to_remote,
     strategy_str,
+    on_diverged,
+    bundle,
     no_protected_branches,
+    verify_build,
+    resume,
+    abort,
+    jobs,
+    fail_fast,
     apply,
     json,
   } = params;
+  // Resuming continues a run that was already applied
+  let apply = apply || resume;
   // Parse conflict strategy (validate it, then use as string in ExecuteSync operation)
   let _strategy = ConflictStrategy::from_str(&strategy_str)?;
 
@@ -101,8 +138,25 @@ fn run_sync_impl(ctx: &WorkspaceContext, params: SyncParams) -> RailResult<()> {
     }
   }
 
-  // Check if all remotes are local paths (skip SSH checks for local testing)
-  let all_local = crates_to_sync_check.iter().all(|s| utils::is_local_path(&s.remote));
+  // Roll back interrupted syncs instead of syncing
+  if abort {
+    return abort_interrupted_syncs(ctx, &crates_to_sync_check);
+  }
+
+  // A bundle carries one split's branch, relative to where the command was run
+  let bundle = match bundle {
+    Some(_) if all => {
+      return Err(RailError::with_help(
+        "A bundle holds a single split",
+        "Name the crate: cargo rail sync <crate> --to-bundle <file>",
+      ));
+    }
+    Some(path) => Some(std::path::absolute(&path)?),
+    None => None,
+  };
+
+  // Check if all remotes are local paths (skip SSH checks for local testing and bundles)
+  let all_local = bundle.is_some() || crates_to_sync_check.iter().all(|s| utils::is_local_path(&s.remote));
 
   // Run preflight health checks before proceeding (skip for local-only operations)
   if !json && apply && !all_local {
@@ -140,6 +194,13 @@ fn run_sync_impl(ctx: &WorkspaceContext, params: SyncParams) -> RailResult<()> {
     }
   };
 
+  // Pushing past unimported remote commits needs an explicit choice; bidirectional syncs import them
+  let on_diverged = match on_diverged {
+    Some(ref policy) => DivergencePolicy::from_str(policy)?,
+    None if matches!(direction, SyncDirection::Both) => DivergencePolicy::Import,
+    None => DivergencePolicy::Abort,
+  };
+
   // Use the crates we already determined
   let crates_to_sync = crates_to_sync_check;
   if all {
@@ -177,20 +238,14 @@ fn run_sync_impl(ctx: &WorkspaceContext, params: SyncParams) -> RailResult<()> {
   let mut plans = Vec::new();
 
   for split_config in &crates_to_sync {
-    let crate_paths = split_config.get_paths().into_iter().cloned().collect::<Vec<_>>();
-
-    // Determine target repo path
-    let target_repo_path = if utils::is_local_path(&split_config.remote) {
-      std::path::PathBuf::from(&split_config.remote)
-    } else {
-      let remote_name = split_config
-        .remote
-        .rsplit('/')
-        .next()
-        .unwrap_or(&split_config.name)
-        .trim_end_matches(".git");
-      ctx.workspace_root().join("..").join(remote_name)
-    };
+    let target_repo_path = config.target_repo_path(ctx.workspace_root(), split_config)?;
+    // Remote splits are cloned into the cache on first use
+    if apply && !utils::is_local_path(&split_config.remote) && bundle.is_none() && !target_repo_path.exists() {
+      if !json {
+        println!("   Cloning {} into {}", split_config.remote, target_repo_path.display());
+      }
+      cache::clone_split(&config, ctx.workspace_root(), split_config, &target_repo_path)?;
+    }
 
     // Check if target repo exists
     let target_exists = target_repo_path.exists();
@@ -200,34 +255,20 @@ fn run_sync_impl(ctx: &WorkspaceContext, params: SyncParams) -> RailResult<()> {
       continue;
     }
 
-    // Build unified Plan with ExecuteSync operation
-    let mut plan = Plan::new(OperationType::Sync, Some(split_config.name.clone()));
-
-    // Determine direction string for the plan
-    let dir_str = match direction {
-      SyncDirection::MonoToRemote => "to_remote",
-      SyncDirection::RemoteToMono => "from_remote",
-      SyncDirection::Both => "bidirectional",
-      SyncDirection::None => continue,
-    };
-
-    // Add high-level ExecuteSync operation
-    plan.add_operation(Operation::ExecuteSync {
-      crate_name: split_config.name.clone(),
-      crate_paths: crate_paths.iter().map(|p| p.display().to_string()).collect(),
-      mode: format!("{:?}", split_config.mode),
-      target_repo_path: target_repo_path.display().to_string(),
-      branch: split_config.branch.clone(),
-      remote_url: split_config.remote.clone(),
-      direction: dir_str.to_string(),
-      conflict_strategy: strategy_str.clone(),
-    });
-
-    let dir_display = match direction {
-      SyncDirection::MonoToRemote => "monorepo → remote",
-      SyncDirection::RemoteToMono => "remote → monorepo",
-      SyncDirection::Both => "bidirectional",
-      SyncDirection::None => "none",
+    let Some((plan, crate_paths)) = sync_plan(
+      ctx,
+      &config,
+      split_config,
+      &target_repo_path,
+      &direction,
+      &strategy_str,
+      resume,
+      verify_build,
+      on_diverged,
+      bundle.as_deref(),
+    )?
+    else {
+      continue;
     };
 
     // Add metadata
@@ -240,16 +281,6 @@ fn run_sync_impl(ctx: &WorkspaceContext, params: SyncParams) -> RailResult<()> {
       None
     };
 
-    plan = plan
-      .with_summary(format!(
-        "Sync crate '{}' ({}) with conflict strategy: {}",
-        split_config.name, dir_display, strategy_str
-      ))
-      .add_trailer("Rail-Operation", "sync")
-      .add_trailer("Rail-Crate", &split_config.name)
-      .add_trailer("Rail-Direction", dir_display)
-      .add_trailer("Rail-Strategy", &strategy_str);
-
     plans.push((
       split_config.clone(),
       crate_paths,
@@ -262,6 +293,14 @@ fn run_sync_impl(ctx: &WorkspaceContext, params: SyncParams) -> RailResult<()> {
 
   // Output plans
   if !apply {
+    // Preview the exact commits and predicted conflicts for targets that exist
+    let executor = PlanExecutor::new(ctx);
+    for (_, _, _, plan, target_exists, _) in &mut plans {
+      if *target_exists {
+        plan.sync_preview = executor.preview_sync(plan)?;
+      }
+    }
+
     if json {
       // JSON output for CI/automation
       let json_plans: Vec<&Plan> = plans.iter().map(|(_, _, _, plan, _, _)| plan).collect();
@@ -276,10 +315,18 @@ fn run_sync_impl(ctx: &WorkspaceContext, params: SyncParams) -> RailResult<()> {
       for (split_config, _, target_repo_path, plan, target_exists, protected_handling) in &plans {
         println!("{}", plan.to_human_readable());
         println!("   Target: {}", target_repo_path.display());
+        if let Some(ref target_dir) = split_config.target_dir {
+          println!("   Target dir: {}", target_dir.display());
+        }
         println!("   Remote: {}", split_config.remote);
         println!("   Branch: {}", split_config.branch);
         println!("   Conflict strategy: {}", strategy_str);
-        if !target_exists {
+        if verify_build {
+          println!("   Verify build: cargo metadata / check / package --list (offline)");
+        }
+        if !target_exists && !utils::is_local_path(&split_config.remote) && bundle.is_none() {
+          println!("   Split repo will be cloned into the cache first");
+        } else if !target_exists {
           println!(
             "   ⚠️  Target repo does not exist yet - run `cargo rail split {}` first",
             split_config.name
@@ -327,60 +374,135 @@ fn run_sync_impl(ctx: &WorkspaceContext, params: SyncParams) -> RailResult<()> {
   // Use existing workspace context for execution
   let executor = PlanExecutor::new(ctx);
 
-  let plan_count = plans.len();
+  // --all: splits run concurrently, summarized together
+  if all {
+    println!("🚀 Processing {} crates in parallel...\n", plans.len());
+    let batch = executor.execute_batch(
+      plans
+        .into_iter()
+        .map(|(split_config, _, _, plan, _, _)| (split_config.name, plan))
+        .collect(),
+      jobs,
+      fail_fast,
+    )?;
+
+    println!("\n📊 Sync summary:\n{}", batch.to_table());
+    if json {
+      println!("{}", serde_json::to_string_pretty(&batch)?);
+    }
+    return batch.into_result();
+  }
 
-  // Use parallel processing for multiple crates
-  if plan_count > 1 && all {
-    println!("🚀 Processing {} crates in parallel...\n", plan_count);
+  for (split_config, _, _, plan, _, _) in plans {
+    println!("\n🔄 Syncing crate: {}", split_config.name);
+    executor.execute(&plan)?;
+  }
 
-    let multi_progress = MultiProgress::new();
-    let bars: Vec<_> = plans
-      .iter()
-      .map(|(split_config, _, _, _, _, _)| multi_progress.add_bar(1, format!("Syncing {}", split_config.name)))
-      .collect();
-
-    // For parallel execution, we need to build contexts per-thread
-    let workspace_root = ctx.workspace_root().to_path_buf();
-    let results: Vec<RailResult<()>> = plans
-      .into_par_iter()
-      .enumerate()
-      .map(|(idx, (_, _, _, plan, _, _))| {
-        // Build workspace context for this thread
-        let thread_context = WorkspaceContext::build(&workspace_root)?;
-        let thread_executor = PlanExecutor::new(&thread_context);
-        let result = thread_executor.execute(&plan);
-
-        multi_progress.inc(&bars[idx]);
-        result
-      })
-      .collect();
-
-    // Check for errors
-    for result in results {
-      result?;
-    }
-  } else {
-    // Sequential processing for single crate or when not using --all
-    let mut crate_progress = if plan_count > 1 {
-      Some(FileProgress::new(plan_count, format!("Syncing {} crates", plan_count)))
-    } else {
-      None
-    };
+  println!("\n🎉 Sync operation complete!");
 
-    for (split_config, _, _, plan, _, _) in plans {
-      if crate_progress.is_none() {
-        println!("\n🔄 Syncing crate: {}", split_config.name);
-      }
+  Ok(())
+}
 
-      executor.execute(&plan)?;
+/// Plan syncing one split (a single `ExecuteSync` operation), with the crate paths it covers
+///
+/// `None` when there is no direction to sync in.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sync_plan(
+  ctx: &WorkspaceContext,
+  config: &RailConfig,
+  split_config: &SplitConfig,
+  target_repo_path: &Path,
+  direction: &SyncDirection,
+  strategy_str: &str,
+  resume: bool,
+  verify_build: bool,
+  on_diverged: DivergencePolicy,
+  bundle: Option<&Path>,
+) -> RailResult<Option<(Plan, Vec<PathBuf>)>> {
+  // Resolve unpublished workspace deps (bundled paths extend the split)
+  let closure = closure::resolve(ctx.graph.as_ref(), ctx.workspace_root(), split_config, &config.splits)?;
+  let crate_paths = closure.crate_paths;
+
+  // Build unified Plan with ExecuteSync operation
+  let mut plan = Plan::new(OperationType::Sync, Some(split_config.name.clone()));
+
+  // Determine direction string for the plan
+  let (dir_str, dir_display) = match direction {
+    SyncDirection::MonoToRemote => ("to_remote", "monorepo → remote"),
+    SyncDirection::RemoteToMono => ("from_remote", "remote → monorepo"),
+    SyncDirection::Both => ("bidirectional", "bidirectional"),
+    SyncDirection::None => return Ok(None),
+  };
 
-      if let Some(ref mut p) = crate_progress {
-        p.inc();
+  // Add high-level ExecuteSync operation
+  plan.add_operation(Operation::ExecuteSync {
+    crate_name: split_config.name.clone(),
+    crate_paths: crate_paths.iter().map(|p| p.display().to_string()).collect(),
+    mode: format!("{:?}", split_config.mode),
+    workspace_mode: format!("{:?}", split_config.workspace_mode),
+    target_repo_path: target_repo_path.display().to_string(),
+    branch: split_config.branch.clone(),
+    remote_url: split_config.remote.clone(),
+    direction: dir_str.to_string(),
+    conflict_strategy: strategy_str.to_string(),
+    resume,
+    target_dir: split_config.target_dir.as_ref().map(|d| d.display().to_string()),
+    lockfile: split_config
+      .lockfile
+      .unwrap_or_else(|| ctx.metadata.has_bin_target(&crate_paths)),
+    verify_build,
+    dependency_sources: closure.sources,
+    on_diverged: on_diverged.as_str().to_string(),
+    bundle: bundle.map(|b| b.display().to_string()),
+    mirrors: split_config.mirrors(),
+    auth: GitAuth::for_split(split_config, &config.security, ctx.workspace_root()),
+  });
+
+  let plan = plan
+    .with_summary(format!(
+      "Sync crate '{}' ({}) with conflict strategy: {}",
+      split_config.name, dir_display, strategy_str
+    ))
+    .add_trailer("Rail-Operation", "sync")
+    .add_trailer("Rail-Crate", &split_config.name)
+    .add_trailer("Rail-Direction", dir_display)
+    .add_trailer("Rail-Strategy", strategy_str);
+
+  Ok(Some((plan, crate_paths)))
+}
+
+/// Roll back the interrupted syncs of the given splits (`--abort`)
+fn abort_interrupted_syncs(ctx: &WorkspaceContext, splits: &[SplitConfig]) -> RailResult<()> {
+  let workspace_root = ctx.workspace_root();
+
+  for split_config in splits {
+    // Make sure the interrupted sync isn't actually still running
+    let _lock = SyncLock::acquire(workspace_root, &split_config.name)?;
+
+    // A sync stopped on conflicts left the stopped commit's files behind for the rollback
+    let conflict_state = ConflictState::load(workspace_root, &split_config.name)?;
+
+    match SyncJournal::load(workspace_root, &split_config.name)? {
+      Some(journal) => {
+        let written_repo = journal.written_repo(workspace_root).display().to_string();
+        let start_head = journal.start_head.clone();
+        let discarded = journal.abort(workspace_root, conflict_state.is_some())?;
+        println!(
+          "↩️  Rolled back interrupted sync of '{}': discarded {} commit(s), {} reset to {}",
+          split_config.name,
+          discarded,
+          written_repo,
+          &start_head[..start_head.len().min(7)]
+        );
       }
+      None => println!("   No interrupted sync of '{}' to abort", split_config.name),
     }
-  }
 
-  println!("\n🎉 Sync operation complete!");
+    // The rollback discarded the commit a stopped sync was waiting on
+    if let Some(state) = conflict_state {
+      state.finish()?;
+    }
+  }
 
   Ok(())
 }
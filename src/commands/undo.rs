@@ -0,0 +1,86 @@
+//! Undo command implementation
+//!
+//! Reverses an applied split, sync or release using the record kept in `.git/rail/applied/`.
+//! The reversal is itself a `Plan`: shown as a dry-run by default, applied with `--apply`.
+
+use crate::core::auth::GitAuth;
+use crate::core::context::WorkspaceContext;
+use crate::core::error::RailResult;
+use crate::core::executor::PlanExecutor;
+use crate::core::history::AppliedPlan;
+
+/// Run the undo command
+pub fn run_undo(ctx: &WorkspaceContext, plan_id: Option<String>, apply: bool, json: bool) -> RailResult<()> {
+  let Some(plan_id) = plan_id else {
+    return list_applied(ctx, json);
+  };
+
+  let record = AppliedPlan::find(ctx.workspace_root(), &plan_id)?;
+  let plan = record.undo_plan(ctx.workspace_root(), &split_auth(ctx, &record))?;
+
+  if !apply {
+    if json {
+      println!("{}", plan.to_json()?);
+    } else {
+      println!("\n🔍 DRY-RUN MODE - No changes will be made");
+      println!("   Add --apply to actually perform the undo\n");
+      println!("{}", plan.to_human_readable());
+      println!("\n💡 To execute this plan, run:");
+      println!("   cargo rail undo {} --apply", plan_id);
+    }
+    return Ok(());
+  }
+
+  println!("↩️  Undoing {} plan {}", record.operation_type, record.id);
+  PlanExecutor::new(ctx).execute(&plan)?;
+  record.pop()?;
+  println!("\n✅ Undo complete");
+
+  Ok(())
+}
+
+/// Credentials for the remote of the split an applied plan worked on
+///
+/// Plain SSH if the split is no longer configured.
+fn split_auth(ctx: &WorkspaceContext, record: &AppliedPlan) -> GitAuth {
+  let (Some(config), Some(crate_name)) = (ctx.config.as_ref(), record.crate_name.as_ref()) else {
+    return GitAuth::default();
+  };
+  config
+    .splits
+    .iter()
+    .find(|split| split.name == *crate_name)
+    .map(|split| GitAuth::for_split(split, &config.security, ctx.workspace_root()))
+    .unwrap_or_default()
+}
+
+/// List the applied plans that can be undone
+fn list_applied(ctx: &WorkspaceContext, json: bool) -> RailResult<()> {
+  let records = AppliedPlan::list(ctx.workspace_root())?;
+
+  if json {
+    println!("{}", serde_json::to_string_pretty(&records)?);
+    return Ok(());
+  }
+
+  if records.is_empty() {
+    println!("No applied plans recorded");
+    return Ok(());
+  }
+
+  println!("Applied plans (most recent first):\n");
+  for record in &records {
+    let applied_at = record.latest().map(|a| a.applied_at.as_str()).unwrap_or("-");
+    println!(
+      "  {}  {:<8} {:<20} {} (x{})",
+      record.id,
+      record.operation_type.to_string(),
+      record.crate_name.as_deref().unwrap_or("-"),
+      applied_at,
+      record.applications.len()
+    );
+  }
+  println!("\n💡 Review an undo plan with: cargo rail undo <plan-id>");
+
+  Ok(())
+}
@@ -0,0 +1,415 @@
+//! `cargo rail watch` - keep splits in sync from a long-running process
+//!
+//! Every `--interval` seconds each watched split gets a bidirectional sync, which only does work
+//! when the monorepo or the split repo has commits the other side lacks. A split whose sync fails
+//! is polled less often (doubling up to `--max-interval`) until a sync succeeds again.
+//!
+//! Progress is written to `.git/rail/watch.json` after every poll so other tools can see what the
+//! watcher is doing. SIGINT/SIGTERM stop it once the sync in flight has finished; a second signal
+//! exits immediately.
+
+use crate::commands::sync::sync_plan;
+use crate::core::cache;
+use crate::core::conflict::ConflictStrategy;
+use crate::core::context::WorkspaceContext;
+use crate::core::error::{ConfigError, RailError, RailResult, ResultExt};
+use crate::core::executor::{ExecutionReport, PlanExecutor};
+use crate::core::journal::{SyncJournal, SyncLock, rail_dir};
+use crate::core::resolve::ConflictState;
+use crate::core::sync::{DivergencePolicy, SyncDirection};
+use crate::core::vcs::SystemGit;
+use crate::utils;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often the sleep between polls checks for a stop signal
+const STOP_CHECK: Duration = Duration::from_millis(200);
+
+/// What the watcher last saw for a split
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchState {
+  /// Not polled yet
+  Pending,
+  /// Neither side had anything to sync
+  Idle,
+  /// The last poll synced commits
+  Synced,
+  /// Another sync of the split was running; polled again next interval
+  Busy,
+  /// A sync stopped on conflicts; waiting for `cargo rail resolve`
+  Conflicts,
+  /// A sync was interrupted; waiting for `cargo rail sync --resume` or `--abort`
+  Interrupted,
+  /// The split repo hasn't been created yet (`cargo rail split`)
+  NotSplit,
+  /// The last sync failed; polling backs off
+  Failed,
+}
+
+/// Watch progress of one split
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitWatch {
+  pub name: String,
+  pub state: WatchState,
+  pub last_poll: Option<String>,
+  /// When commits were last synced
+  pub last_sync: Option<String>,
+  /// Commits synced since the watcher started
+  pub commits_synced: usize,
+  /// Failed syncs in a row
+  pub failures: u32,
+  pub next_poll: Option<String>,
+  pub last_error: Option<String>,
+}
+
+/// Contents of `.git/rail/watch.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchStatus {
+  pub pid: u32,
+  pub started_at: String,
+  pub updated_at: String,
+  /// False once the watcher has stopped
+  pub running: bool,
+  pub splits: Vec<SplitWatch>,
+}
+
+/// Options of `cargo rail watch`
+pub struct WatchParams {
+  pub crates: Vec<String>,
+  pub interval: u64,
+  pub max_interval: u64,
+  pub strategy_str: String,
+  pub once: bool,
+}
+
+/// A watched split and when to poll it next
+struct Watched {
+  status: SplitWatch,
+  due: Instant,
+}
+
+/// Run the watch command
+pub fn run_watch(ctx: &WorkspaceContext, params: WatchParams) -> RailResult<()> {
+  let WatchParams {
+    crates,
+    interval,
+    max_interval,
+    strategy_str,
+    once,
+  } = params;
+  ConflictStrategy::from_str(&strategy_str)?;
+  if interval == 0 {
+    return Err(RailError::message("--interval must be at least 1 second"));
+  }
+  let interval = Duration::from_secs(interval);
+  let max_interval = Duration::from_secs(max_interval).max(interval);
+
+  let workspace_root = ctx.workspace_root().to_path_buf();
+  let config = ctx.require_config()?;
+  for name in &crates {
+    if !config.splits.iter().any(|s| s.name == *name) {
+      return Err(RailError::Config(ConfigError::CrateNotFound { name: name.clone() }));
+    }
+  }
+  let names: Vec<String> = if crates.is_empty() {
+    config.splits.iter().map(|s| s.name.clone()).collect()
+  } else {
+    crates
+  };
+  if names.is_empty() {
+    return Err(RailError::with_help(
+      "No splits to watch",
+      "Add [[splits]] to rail.toml, or run: cargo rail init",
+    ));
+  }
+
+  let _lock = WatchLock::acquire(&workspace_root)?;
+  let stop = Arc::new(AtomicBool::new(false));
+  for signal in [signal_hook::consts::SIGINT, signal_hook::consts::SIGTERM] {
+    // A second signal exits right away; the first one is only noted
+    signal_hook::flag::register_conditional_shutdown(signal, 1, Arc::clone(&stop))?;
+    signal_hook::flag::register(signal, Arc::clone(&stop))?;
+  }
+
+  let now = Instant::now();
+  let mut watched: Vec<Watched> = names
+    .into_iter()
+    .map(|name| Watched {
+      status: SplitWatch {
+        name,
+        state: WatchState::Pending,
+        last_poll: None,
+        last_sync: None,
+        commits_synced: 0,
+        failures: 0,
+        next_poll: None,
+        last_error: None,
+      },
+      due: now,
+    })
+    .collect();
+  let mut status = WatchStatus {
+    pid: std::process::id(),
+    started_at: Utc::now().to_rfc3339(),
+    updated_at: Utc::now().to_rfc3339(),
+    running: true,
+    splits: Vec::new(),
+  };
+
+  println!(
+    "👀 Watching {} split(s) every {}s (Ctrl-C to stop)",
+    watched.len(),
+    interval.as_secs()
+  );
+
+  while !stop.load(Ordering::SeqCst) {
+    // Pick up edits to rail.toml and the workspace between rounds
+    let fresh = WorkspaceContext::build(&workspace_root);
+    let round_ctx = match &fresh {
+      Ok(fresh) => fresh,
+      Err(e) => {
+        eprintln!("⚠️  Could not reload the workspace, using the previous one: {}", e);
+        ctx
+      }
+    };
+
+    for idx in 0..watched.len() {
+      if stop.load(Ordering::SeqCst) {
+        break;
+      }
+      let split = &mut watched[idx];
+      if split.due > Instant::now() {
+        continue;
+      }
+
+      poll_split(round_ctx, &mut split.status, &strategy_str);
+
+      let wait = match split.status.state {
+        WatchState::Failed => backoff(interval, max_interval, split.status.failures),
+        _ => interval,
+      };
+      split.due = Instant::now() + wait;
+      split.status.next_poll = Some((Utc::now() + wait).to_rfc3339());
+      write_status(&workspace_root, &mut status, &watched_statuses(&watched))?;
+    }
+
+    if once {
+      break;
+    }
+
+    // Sleep until the next split is due, waking up for signals
+    let next_due = watched.iter().map(|w| w.due).min().unwrap_or_else(Instant::now);
+    while !stop.load(Ordering::SeqCst) && Instant::now() < next_due {
+      std::thread::sleep(STOP_CHECK.min(next_due.saturating_duration_since(Instant::now())));
+    }
+  }
+
+  status.running = false;
+  for split in &mut watched {
+    split.status.next_poll = None;
+  }
+  write_status(&workspace_root, &mut status, &watched_statuses(&watched))?;
+  if stop.load(Ordering::SeqCst) {
+    println!("\n👋 Stopped watching");
+  }
+
+  Ok(())
+}
+
+/// Sync one split in both directions, recording the outcome
+fn poll_split(ctx: &WorkspaceContext, split: &mut SplitWatch, strategy_str: &str) {
+  split.last_poll = Some(Utc::now().to_rfc3339());
+
+  match sync_split(ctx, &split.name, strategy_str) {
+    Ok((state, commits)) => {
+      split.failures = 0;
+      split.last_error = None;
+      if commits > 0 {
+        split.commits_synced += commits;
+        split.last_sync = split.last_poll.clone();
+      }
+      split.state = state;
+    }
+    Err(e) => {
+      eprintln!("❌ Sync of '{}' failed: {}", split.name, e);
+      split.failures += 1;
+      split.last_error = Some(e.to_string());
+      split.state = WatchState::Failed;
+    }
+  }
+}
+
+/// Run a bidirectional sync of one split unless something is waiting on the user
+///
+/// Returns the split's new state and the number of commits synced.
+fn sync_split(ctx: &WorkspaceContext, name: &str, strategy_str: &str) -> RailResult<(WatchState, usize)> {
+  let workspace_root = ctx.workspace_root();
+  let config = ctx.require_config()?;
+  let split_config = config
+    .splits
+    .iter()
+    .find(|s| s.name == name)
+    .ok_or_else(|| RailError::Config(ConfigError::CrateNotFound { name: name.to_string() }))?;
+
+  let target_repo_path = config.target_repo_path(workspace_root, split_config)?;
+  if !target_repo_path.exists() {
+    if utils::is_local_path(&split_config.remote) {
+      return Ok((WatchState::NotSplit, 0));
+    }
+    println!("   Cloning {} into {}", split_config.remote, target_repo_path.display());
+    cache::clone_split(config, workspace_root, split_config, &target_repo_path)?;
+  }
+  if ConflictState::load(workspace_root, name)?.is_some() {
+    return Ok((WatchState::Conflicts, 0));
+  }
+  if SyncJournal::load(workspace_root, name)?.is_some() {
+    return Ok((WatchState::Interrupted, 0));
+  }
+  if SyncLock::is_held(workspace_root, name)? {
+    return Ok((WatchState::Busy, 0));
+  }
+
+  let Some((plan, _)) = sync_plan(
+    ctx,
+    config,
+    split_config,
+    &target_repo_path,
+    &SyncDirection::Both,
+    strategy_str,
+    false,
+    false,
+    DivergencePolicy::Import,
+    None,
+  )?
+  else {
+    return Ok((WatchState::Idle, 0));
+  };
+
+  // Imports onto a protected branch leave the monorepo on the PR branch; go back afterwards
+  let mono_git = SystemGit::open(workspace_root)?;
+  let start_branch = mono_git.current_branch()?;
+
+  println!("\n🔄 [{}] Syncing crate: {}", Utc::now().format("%H:%M:%S"), name);
+  let result = PlanExecutor::new(ctx).execute(&plan);
+
+  if ConflictState::load(workspace_root, name)?.is_none() && mono_git.current_branch()? != start_branch {
+    mono_git.checkout_branch(&start_branch)?;
+  }
+
+  let report = result?;
+  Ok((outcome(&report), report.commits))
+}
+
+/// State after a sync that ran to completion
+fn outcome(report: &ExecutionReport) -> WatchState {
+  if report.conflicts > 0 {
+    WatchState::Conflicts
+  } else if report.commits > 0 {
+    WatchState::Synced
+  } else {
+    WatchState::Idle
+  }
+}
+
+/// Delay before polling a split again after `failures` failed syncs in a row
+fn backoff(interval: Duration, max_interval: Duration, failures: u32) -> Duration {
+  interval
+    .checked_mul(2u32.saturating_pow(failures.min(16)))
+    .unwrap_or(max_interval)
+    .min(max_interval)
+}
+
+fn watched_statuses(watched: &[Watched]) -> Vec<SplitWatch> {
+  watched.iter().map(|w| w.status.clone()).collect()
+}
+
+/// Path of the watcher's status file
+pub fn status_path(workspace_root: &Path) -> RailResult<PathBuf> {
+  Ok(rail_dir(workspace_root)?.join("watch.json"))
+}
+
+/// Replace the status file, so readers never see it half-written
+fn write_status(workspace_root: &Path, status: &mut WatchStatus, splits: &[SplitWatch]) -> RailResult<()> {
+  status.updated_at = Utc::now().to_rfc3339();
+  status.splits = splits.to_vec();
+
+  let path = status_path(workspace_root)?;
+  let tmp = path.with_extension("json.tmp");
+  std::fs::write(&tmp, serde_json::to_string_pretty(status)?)
+    .with_context(|| format!("Failed to write {}", tmp.display()))?;
+  std::fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))?;
+  Ok(())
+}
+
+/// One watcher per workspace (`.git/rail/watch.lock`)
+struct WatchLock {
+  path: PathBuf,
+}
+
+impl WatchLock {
+  fn acquire(workspace_root: &Path) -> RailResult<Self> {
+    let path = rail_dir(workspace_root)?.join("watch.lock");
+
+    match OpenOptions::new().write(true).create_new(true).open(&path) {
+      Ok(mut file) => {
+        writeln!(file, "{}", std::process::id())?;
+        Ok(Self { path })
+      }
+      Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+        let holder = std::fs::read_to_string(&path).unwrap_or_default();
+        Err(RailError::with_help(
+          format!("cargo rail watch is already running here (pid {})", holder.trim()),
+          format!(
+            "Stop it first. If no watcher is running, remove the stale lock:\n  rm {}",
+            path.display()
+          ),
+        ))
+      }
+      Err(e) => Err(RailError::message(format!(
+        "Failed to create {}: {}",
+        path.display(),
+        e
+      ))),
+    }
+  }
+}
+
+impl Drop for WatchLock {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_file(&self.path);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_backoff_doubles_up_to_max() {
+    let interval = Duration::from_secs(30);
+    let max = Duration::from_secs(300);
+
+    assert_eq!(backoff(interval, max, 0), interval);
+    assert_eq!(backoff(interval, max, 1), Duration::from_secs(60));
+    assert_eq!(backoff(interval, max, 3), Duration::from_secs(240));
+    assert_eq!(backoff(interval, max, 4), max);
+    assert_eq!(backoff(interval, max, u32::MAX), max);
+  }
+
+  #[test]
+  fn test_outcome() {
+    let mut report = ExecutionReport::default();
+    assert_eq!(outcome(&report), WatchState::Idle);
+    report.commits = 2;
+    assert_eq!(outcome(&report), WatchState::Synced);
+    report.conflicts = 1;
+    assert_eq!(outcome(&report), WatchState::Conflicts);
+  }
+}
@@ -0,0 +1,373 @@
+//! Credentials for git subprocesses that talk to a split's remotes
+//!
+//! - `ssh` binds `GIT_SSH_COMMAND` to `[security] ssh_key_path`, if set, with
+//!   `IdentitiesOnly=yes`, so ssh-agent can't offer a different one; without it ssh chooses
+//!   the key as usual (agent, hardware keys, `~/.ssh/config`). A remote with a pinned
+//!   `known_hosts` file only accepts the host keys listed there (`StrictHostKeyChecking=yes`)
+//! - `https` hands git a token through an inline credential helper that reads it from the
+//!   environment, so the token never appears on a command line, in a config file, in a
+//!   remote URL, or in anything cargo-rail prints
+//! - `helper` defers to the git credential helper already configured on the machine, which
+//!   sees a fixed set of session variables plus the split's `helper_env`, never git's own
+//!
+//! Either HTTPS method disables git's terminal prompt, so missing credentials fail fast in CI.
+
+use crate::core::config::{AuthMethod, RemoteConfig, SecurityConfig, SplitConfig};
+use crate::core::error::{RailError, RailResult};
+use crate::core::security::SecurityValidator;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variable read for the token when a split doesn't set `token_env`
+pub const DEFAULT_TOKEN_ENV: &str = "CARGO_RAIL_TOKEN";
+
+/// Username sent with the token; GitHub requires this one, GitLab and Gitea accept any
+const TOKEN_USERNAME: &str = "x-access-token";
+
+/// Variables credential helpers commonly need (besides `XDG_*`) to find their store or agent
+const HELPER_ENV: &[&str] = &[
+  "HOME",
+  "PATH",
+  "USER",
+  "LOGNAME",
+  "TMPDIR",
+  "LANG",
+  "SSH_AUTH_SOCK",
+  "DBUS_SESSION_BUS_ADDRESS",
+  "DISPLAY",
+  "WAYLAND_DISPLAY",
+  "GNUPGHOME",
+  "GPG_TTY",
+  "USERPROFILE",
+  "APPDATA",
+  "LOCALAPPDATA",
+  "SYSTEMROOT",
+];
+
+/// Authentication for one split's remotes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitAuth {
+  pub method: AuthMethod,
+  /// Name (never the value) of the variable holding the token
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub token_env: Option<String>,
+  /// SSH key git is restricted to (ssh picks one itself if unset)
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub ssh_key: Option<PathBuf>,
+  /// known_hosts file the remote's host key must be in
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub known_hosts: Option<PathBuf>,
+  /// Extra variables passed to the credential helper (names only)
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub helper_env: Vec<String>,
+}
+
+impl GitAuth {
+  /// Authentication for a split's primary remote
+  pub fn for_split(split: &SplitConfig, security: &SecurityConfig, workspace_root: &Path) -> Self {
+    let ssh_key = match split.auth {
+      AuthMethod::Ssh => SecurityValidator::new(security.clone()).ssh_key_path(),
+      AuthMethod::Https | AuthMethod::Helper => None,
+    };
+    Self {
+      method: split.auth,
+      token_env: split.token_env.clone(),
+      ssh_key,
+      known_hosts: split.known_hosts.as_ref().map(|path| workspace_root.join(path)),
+      helper_env: match split.auth {
+        AuthMethod::Helper => split.helper_env.clone(),
+        AuthMethod::Ssh | AuthMethod::Https => Vec::new(),
+      },
+    }
+  }
+
+  /// The same credentials for another of the split's remotes, with that remote's pinning
+  pub fn for_remote(&self, remote: &RemoteConfig, workspace_root: &Path) -> Self {
+    Self {
+      known_hosts: remote.known_hosts_in(workspace_root),
+      ..self.clone()
+    }
+  }
+
+  /// Plain SSH with whatever key ssh picks (plans leave it out)
+  pub fn is_default(&self) -> bool {
+    *self == Self::default()
+  }
+
+  /// Variable the token is read from
+  pub fn token_env(&self) -> &str {
+    self.token_env.as_deref().unwrap_or(DEFAULT_TOKEN_ENV)
+  }
+
+  /// Check the credentials are usable before any remote operation
+  pub fn validate(&self, security: &SecurityValidator) -> RailResult<()> {
+    match self.method {
+      AuthMethod::Ssh => {
+        if self.ssh_key.is_some() {
+          security.validate_ssh_key()?;
+        }
+        if let Some(ref known_hosts) = self.known_hosts
+          && !known_hosts.is_file()
+        {
+          return Err(RailError::with_help(
+            format!("Pinned known_hosts file not found: {}", known_hosts.display()),
+            "Create it with: ssh-keyscan <host> > <file>, and verify the fingerprints out of band",
+          ));
+        }
+        Ok(())
+      }
+      AuthMethod::Https => {
+        if !has_token(self.token_env()) {
+          return Err(RailError::with_help(
+            format!(
+              "auth = \"https\" needs a token in ${}, which is not set",
+              self.token_env()
+            ),
+            "Export the token (e.g. from a CI secret), or point token_env in rail.toml at the variable that holds it",
+          ));
+        }
+        println!("✅ HTTPS token found in ${}", self.token_env());
+        Ok(())
+      }
+      AuthMethod::Helper => {
+        if !has_credential_helper() {
+          return Err(RailError::with_help(
+            "auth = \"helper\" but git has no credential.helper configured",
+            "Configure one (e.g. git config --global credential.helper store), or use auth = \"https\" with a token",
+          ));
+        }
+        println!("✅ Using the configured git credential helper");
+        Ok(())
+      }
+    }
+  }
+
+  /// Set up a git subprocess to authenticate with this method
+  ///
+  /// Works on commands from `SystemGit::git_cmd`, whose environment is cleared, as well as on
+  /// plain `Command::new("git")`.
+  pub fn apply(&self, cmd: &mut Command) {
+    match self.method {
+      AuthMethod::Ssh => {
+        if let Some(ssh_command) = self.ssh_command() {
+          cmd.env("GIT_SSH_COMMAND", ssh_command);
+        }
+        // The key may still come from the agent, as long as it is the bound one
+        if let Some(sock) = std::env::var_os("SSH_AUTH_SOCK") {
+          cmd.env("SSH_AUTH_SOCK", sock);
+        }
+      }
+      AuthMethod::Https => {
+        if let Ok(token) = std::env::var(self.token_env()) {
+          cmd.env(self.token_env(), token);
+        }
+        // An empty helper resets any configured ones, so only ours answers
+        cmd
+          .env("GIT_TERMINAL_PROMPT", "0")
+          .env("GIT_CONFIG_COUNT", "2")
+          .env("GIT_CONFIG_KEY_0", "credential.helper")
+          .env("GIT_CONFIG_VALUE_0", "")
+          .env("GIT_CONFIG_KEY_1", "credential.helper")
+          .env("GIT_CONFIG_VALUE_1", token_helper(self.token_env()));
+      }
+      AuthMethod::Helper => {
+        // Helpers need more of the session than git itself (D-Bus, XDG dirs, tokens), but
+        // never GIT_DIR, GIT_CONFIG_* and the like
+        let passed =
+          std::env::vars_os().filter(|(name, _)| name.to_str().is_some_and(|name| self.passes_to_helper(name)));
+        cmd.envs(passed).env("GIT_TERMINAL_PROMPT", "0");
+      }
+    }
+  }
+
+  /// Whether the credential helper gets the variable `name`
+  fn passes_to_helper(&self, name: &str) -> bool {
+    HELPER_ENV.contains(&name) || name.starts_with("XDG_") || self.helper_env.iter().any(|var| var == name)
+  }
+
+  /// ssh invocation bound to the key and pinned host keys (None: nothing to bind)
+  fn ssh_command(&self) -> Option<String> {
+    if self.ssh_key.is_none() && self.known_hosts.is_none() {
+      return None;
+    }
+
+    let mut command = "ssh".to_string();
+    if let Some(ref key) = self.ssh_key {
+      command.push_str(&format!(" -i {} -o IdentitiesOnly=yes", shell_quote(key)));
+    }
+    if let Some(ref known_hosts) = self.known_hosts {
+      command.push_str(&format!(
+        " -o UserKnownHostsFile={} -o GlobalKnownHostsFile=/dev/null -o StrictHostKeyChecking=yes",
+        shell_quote(known_hosts)
+      ));
+    }
+    Some(command)
+  }
+}
+
+/// Quote a path for the shell git runs `GIT_SSH_COMMAND` with
+fn shell_quote(path: &Path) -> String {
+  format!("'{}'", path.display().to_string().replace('\'', "'\\''"))
+}
+
+/// Inline credential helper answering `get` with the token read from `var` when git runs it
+fn token_helper(var: &str) -> String {
+  format!(
+    "!f() {{ test \"$1\" = get || return 0; echo username={}; echo \"password=${}\"; }}; f",
+    TOKEN_USERNAME, var
+  )
+}
+
+/// Whether `var` holds a non-blank token
+pub fn has_token(var: &str) -> bool {
+  std::env::var(var).is_ok_and(|token| !token.trim().is_empty())
+}
+
+/// Whether git has a `credential.helper` configured
+pub fn has_credential_helper() -> bool {
+  Command::new("git")
+    .args(["config", "--get-all", "credential.helper"])
+    .output()
+    .is_ok_and(|o| o.status.success() && !o.stdout.trim_ascii().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_https_auth_passes_token_through_env_only() {
+    let auth = GitAuth {
+      method: AuthMethod::Https,
+      token_env: Some("RAIL_TEST_TOKEN_ENV_ONLY".to_string()),
+      ..Default::default()
+    };
+    let mut cmd = Command::new("git");
+    auth.apply(&mut cmd);
+
+    let helper = cmd
+      .get_envs()
+      .find(|(k, _)| *k == "GIT_CONFIG_VALUE_1")
+      .and_then(|(_, v)| v)
+      .unwrap()
+      .to_string_lossy()
+      .into_owned();
+    assert!(helper.contains("password=$RAIL_TEST_TOKEN_ENV_ONLY"));
+    assert_eq!(cmd.get_args().count(), 0);
+  }
+
+  #[test]
+  fn test_https_auth_answers_git_credential_requests() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let auth = GitAuth {
+      method: AuthMethod::Https,
+      token_env: Some("RAIL_TEST_TOKEN_FILL".to_string()),
+      ..Default::default()
+    };
+    let mut cmd = Command::new("git");
+    auth.apply(&mut cmd);
+    let mut child = cmd
+      .env("RAIL_TEST_TOKEN_FILL", "t0ken")
+      .args(["credential", "fill"])
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .spawn()
+      .unwrap();
+    child
+      .stdin
+      .take()
+      .unwrap()
+      .write_all(b"protocol=https\nhost=git.example.com\n\n")
+      .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("username=x-access-token"), "{}", stdout);
+    assert!(stdout.contains("password=t0ken"), "{}", stdout);
+  }
+
+  #[test]
+  fn test_ssh_auth_binds_key_and_pins_host_keys() {
+    let auth = GitAuth {
+      ssh_key: Some(PathBuf::from("/keys/it's deploy")),
+      known_hosts: Some(PathBuf::from("/pins/known_hosts")),
+      ..Default::default()
+    };
+    let ssh_command = auth.ssh_command().unwrap();
+
+    // Run it through the shell as git does, printing the arguments instead of connecting
+    let printed = Command::new("sh")
+      .arg("-c")
+      .arg(ssh_command.replacen("ssh", "printf '%s\\n'", 1))
+      .output()
+      .unwrap();
+    let args: Vec<String> = String::from_utf8_lossy(&printed.stdout)
+      .lines()
+      .map(String::from)
+      .collect();
+    assert_eq!(
+      args,
+      [
+        "-i",
+        "/keys/it's deploy",
+        "-o",
+        "IdentitiesOnly=yes",
+        "-o",
+        "UserKnownHostsFile=/pins/known_hosts",
+        "-o",
+        "GlobalKnownHostsFile=/dev/null",
+        "-o",
+        "StrictHostKeyChecking=yes",
+      ]
+    );
+
+    assert!(GitAuth::default().ssh_command().is_none());
+  }
+
+  #[test]
+  fn test_ssh_auth_leaves_key_choice_to_ssh_unless_configured() {
+    let validator = SecurityValidator::new(Default::default());
+    assert!(validator.ssh_key_path().is_none());
+    // No key file is needed when ssh-agent or ~/.ssh/config provides one
+    GitAuth::default().validate(&validator).unwrap();
+
+    let pinned = GitAuth {
+      known_hosts: Some(PathBuf::from("/pins/known_hosts")),
+      ..Default::default()
+    };
+    let ssh_command = pinned.ssh_command().unwrap();
+    assert!(!ssh_command.contains("IdentitiesOnly"), "{}", ssh_command);
+    assert!(!ssh_command.contains(" -i "), "{}", ssh_command);
+  }
+
+  #[test]
+  fn test_helper_auth_passes_only_session_and_listed_vars() {
+    let auth = GitAuth {
+      method: AuthMethod::Helper,
+      helper_env: vec!["GH_TOKEN".to_string()],
+      ..Default::default()
+    };
+    assert!(auth.passes_to_helper("HOME"));
+    assert!(auth.passes_to_helper("XDG_RUNTIME_DIR"));
+    assert!(auth.passes_to_helper("GH_TOKEN"));
+    assert!(!auth.passes_to_helper("GIT_DIR"));
+    assert!(!auth.passes_to_helper("GIT_CONFIG_COUNT"));
+    assert!(!auth.passes_to_helper("AWS_SECRET_ACCESS_KEY"));
+    assert!(!GitAuth::default().passes_to_helper("GH_TOKEN"));
+  }
+
+  #[test]
+  fn test_https_auth_requires_token() {
+    let auth = GitAuth {
+      method: AuthMethod::Https,
+      token_env: Some("RAIL_TEST_TOKEN_UNSET".to_string()),
+      ..Default::default()
+    };
+    let validator = SecurityValidator::new(Default::default());
+    let err = auth.validate(&validator).unwrap_err();
+    assert!(err.to_string().contains("$RAIL_TEST_TOKEN_UNSET"));
+  }
+}
@@ -0,0 +1,233 @@
+//! Managed clones of split repositories
+//!
+//! Splits with a non-local remote are cloned under `<cache>/<workspace-hash>/<split>`, where
+//! `<cache>` is `[cache] dir` or `$XDG_CACHE_HOME/cargo-rail` (`~/.cache/cargo-rail`). Each
+//! workspace directory records the workspace it belongs to, so `cargo rail cache prune` can
+//! drop the clones of workspaces that no longer exist.
+
+use crate::core::auth::GitAuth;
+use crate::core::config::{CacheConfig, CloneFetch, RailConfig, SplitConfig};
+use crate::core::error::{GitError, RailError, RailResult, ResultExt};
+use crate::core::split::configure_git_identity;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// File in a workspace's cache directory holding the workspace path
+const WORKSPACE_MARKER: &str = "workspace";
+
+/// Commits a shallow clone fetches when `[cache] depth` isn't set
+const DEFAULT_DEPTH: u32 = 100;
+
+/// Directory all workspaces' clones live under
+pub fn cache_root(workspace_root: &Path, config: &CacheConfig) -> RailResult<PathBuf> {
+  if let Some(ref dir) = config.dir {
+    return Ok(workspace_root.join(dir));
+  }
+  let base = match std::env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+    Some(dir) => PathBuf::from(dir),
+    None => match std::env::var_os("HOME").filter(|dir| !dir.is_empty()) {
+      Some(home) => PathBuf::from(home).join(".cache"),
+      None => {
+        return Err(RailError::with_help(
+          "Can't locate a cache directory for split repo clones ($XDG_CACHE_HOME and $HOME are unset)",
+          "Set one in rail.toml:\n  [cache]\n  dir = \"/path/to/cache\"",
+        ));
+      }
+    },
+  };
+  Ok(base.join("cargo-rail"))
+}
+
+/// Directory holding this workspace's clones
+pub fn workspace_cache_dir(workspace_root: &Path, config: &CacheConfig) -> RailResult<PathBuf> {
+  Ok(cache_root(workspace_root, config)?.join(workspace_hash(workspace_root)))
+}
+
+/// Stable name for a workspace, from its canonical path
+fn workspace_hash(workspace_root: &Path) -> String {
+  let path = workspace_root
+    .canonicalize()
+    .unwrap_or_else(|_| workspace_root.to_path_buf());
+  let digest = Sha256::digest(path.to_string_lossy().as_bytes());
+  digest.iter().take(8).map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Create this workspace's cache directory, recording which workspace it belongs to
+pub fn record_workspace(workspace_root: &Path, config: &CacheConfig) -> RailResult<()> {
+  let workspace_dir = workspace_cache_dir(workspace_root, config)?;
+  std::fs::create_dir_all(&workspace_dir).with_context(|| format!("Failed to create {}", workspace_dir.display()))?;
+  let marker = workspace_dir.join(WORKSPACE_MARKER);
+  if !marker.exists() {
+    let canonical = workspace_root
+      .canonicalize()
+      .unwrap_or_else(|_| workspace_root.to_path_buf());
+    std::fs::write(&marker, canonical.to_string_lossy().as_bytes())
+      .with_context(|| format!("Failed to write {}", marker.display()))?;
+  }
+  Ok(())
+}
+
+/// Clone a split repo into the cache, fetching as much history as `[cache] fetch` asks for
+pub fn clone_split(config: &RailConfig, workspace_root: &Path, split: &SplitConfig, path: &Path) -> RailResult<()> {
+  record_workspace(workspace_root, &config.cache)?;
+
+  let mut cmd = Command::new("git");
+  GitAuth::for_split(split, &config.security, workspace_root).apply(&mut cmd);
+  cmd.args(["clone", "--quiet", "--branch", &split.branch]);
+  match config.cache.fetch {
+    CloneFetch::Full => {}
+    CloneFetch::Partial => {
+      cmd.arg("--filter=blob:none");
+    }
+    CloneFetch::Shallow => {
+      cmd.arg(format!("--depth={}", config.cache.depth.unwrap_or(DEFAULT_DEPTH)));
+    }
+  }
+  let output = cmd
+    .arg(&split.remote)
+    .arg(path)
+    .output()
+    .context("Failed to clone split repository")?;
+  if !output.status.success() {
+    return Err(RailError::Git(GitError::CommandFailed {
+      command: "git clone".to_string(),
+      stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    }));
+  }
+
+  configure_git_identity(workspace_root, path)
+}
+
+/// A clone in the cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+  /// Split the clone belongs to (its directory name)
+  pub split: String,
+  pub path: PathBuf,
+  /// Workspace that made the clone, if recorded
+  pub workspace: Option<PathBuf>,
+  /// Size on disk
+  pub bytes: u64,
+  /// Local commits not on any remote branch (None if git couldn't tell)
+  pub unpushed: Option<usize>,
+  /// Whether the worktree has uncommitted changes or untracked files (None if git couldn't tell)
+  pub dirty: Option<bool>,
+}
+
+/// All clones under the cache root, grouped by workspace directory
+pub fn entries(cache_root: &Path) -> RailResult<Vec<CacheEntry>> {
+  let mut entries = Vec::new();
+  if !cache_root.is_dir() {
+    return Ok(entries);
+  }
+
+  for workspace_dir in sorted_dirs(cache_root)? {
+    let workspace = std::fs::read_to_string(workspace_dir.join(WORKSPACE_MARKER))
+      .ok()
+      .map(|path| PathBuf::from(path.trim()));
+    for path in sorted_dirs(&workspace_dir)? {
+      entries.push(CacheEntry {
+        split: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        bytes: dir_size(&path),
+        unpushed: unpushed_commits(&path),
+        dirty: has_local_changes(&path),
+        workspace: workspace.clone(),
+        path,
+      });
+    }
+  }
+
+  Ok(entries)
+}
+
+fn sorted_dirs(dir: &Path) -> RailResult<Vec<PathBuf>> {
+  let mut dirs = Vec::new();
+  for entry in std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+    let path = entry?.path();
+    if path.is_dir() {
+      dirs.push(path);
+    }
+  }
+  dirs.sort();
+  Ok(dirs)
+}
+
+fn dir_size(path: &Path) -> u64 {
+  let Ok(read_dir) = std::fs::read_dir(path) else {
+    return 0;
+  };
+  read_dir
+    .flatten()
+    .map(|entry| match entry.file_type() {
+      Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+      Ok(kind) if kind.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+      _ => 0,
+    })
+    .sum()
+}
+
+/// Commits on local branches that no remote-tracking branch has
+fn unpushed_commits(path: &Path) -> Option<usize> {
+  git_stdout(path, &["rev-list", "--count", "--branches", "--not", "--remotes"])?
+    .trim()
+    .parse()
+    .ok()
+}
+
+/// Whether `git status` shows anything, tracked or not
+fn has_local_changes(path: &Path) -> Option<bool> {
+  git_stdout(path, &["status", "--porcelain"]).map(|status| !status.trim().is_empty())
+}
+
+/// stdout of a git command in `path`, or None if it didn't succeed
+fn git_stdout(path: &Path, args: &[&str]) -> Option<String> {
+  Command::new("git")
+    .current_dir(path)
+    .args(args)
+    .output()
+    .ok()
+    .filter(|o| o.status.success())
+    .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tempfile::TempDir;
+
+  #[test]
+  fn test_workspace_cache_dir_is_stable_and_per_workspace() {
+    let temp = TempDir::new().unwrap();
+    let one = temp.path().join("one");
+    let two = temp.path().join("two");
+    std::fs::create_dir_all(&one).unwrap();
+    std::fs::create_dir_all(&two).unwrap();
+    let config = CacheConfig {
+      dir: Some(temp.path().join("cache")),
+      ..CacheConfig::default()
+    };
+
+    let dir = workspace_cache_dir(&one, &config).unwrap();
+    assert!(dir.starts_with(temp.path().join("cache")));
+    assert_eq!(dir, workspace_cache_dir(&one.join("."), &config).unwrap());
+    assert_ne!(dir, workspace_cache_dir(&two, &config).unwrap());
+  }
+
+  #[test]
+  fn test_entries_reads_workspace_marker() {
+    let temp = TempDir::new().unwrap();
+    let workspace_dir = temp.path().join("0123456789abcdef");
+    std::fs::create_dir_all(workspace_dir.join("my-crate")).unwrap();
+    std::fs::write(workspace_dir.join("my-crate/file"), "12345").unwrap();
+    std::fs::write(workspace_dir.join(WORKSPACE_MARKER), "/src/mono\n").unwrap();
+
+    let found = entries(temp.path()).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].split, "my-crate");
+    assert_eq!(found[0].workspace, Some(PathBuf::from("/src/mono")));
+    assert_eq!(found[0].bytes, 5);
+    assert!(entries(&temp.path().join("missing")).unwrap().is_empty());
+  }
+}
@@ -0,0 +1,223 @@
+//! Dependency-closure planning for splits
+//!
+//! A split rewrites workspace path dependencies into registry version dependencies.
+//! That only works when the dependency is actually on a registry. This module walks the
+//! `WorkspaceGraph` from the split's crates, finds unpublished workspace crates in the
+//! closure, and resolves them according to the split's `unpublished_deps` policy:
+//!
+//! - `fail`: refuse to plan, listing every unpublished dependency and who needs it
+//! - `include`: bundle them into the (combined) split repo, keeping path dependencies
+//! - `git`: point at their own split remotes with `git = "..."` dependencies
+
+use crate::cargo::transform::DependencyOverrides;
+use crate::core::config::{SplitConfig, UnpublishedDepsPolicy};
+use crate::core::error::{RailError, RailResult};
+use crate::core::plan::DependencySource;
+use crate::graph::workspace_graph::{UnpublishedDependency, WorkspaceGraph};
+use crate::utils;
+use std::path::{Path, PathBuf};
+
+/// Crate paths and dependency sources for a split after resolving its closure
+#[derive(Debug, Clone)]
+pub struct DependencyClosure {
+  /// Paths to split: the configured paths plus any bundled dependencies
+  pub crate_paths: Vec<PathBuf>,
+  /// How each unpublished dependency is provided (empty when there are none)
+  pub sources: Vec<DependencySource>,
+}
+
+/// Resolve the unpublished workspace dependencies of `split`
+///
+/// `splits` is the full list of configured splits, used to find the remote of each
+/// dependency when the policy is `git`.
+pub fn resolve(
+  graph: &WorkspaceGraph,
+  workspace_root: &Path,
+  split: &SplitConfig,
+  splits: &[SplitConfig],
+) -> RailResult<DependencyClosure> {
+  let mut crate_paths: Vec<PathBuf> = split.get_paths().into_iter().cloned().collect();
+
+  let roots: Vec<String> = crate_paths
+    .iter()
+    .filter_map(|path| graph.file_to_crate(&workspace_root.join(path)))
+    .collect();
+  let unpublished = graph.unpublished_dependency_closure(&roots)?;
+
+  if unpublished.is_empty() {
+    return Ok(DependencyClosure {
+      crate_paths,
+      sources: Vec::new(),
+    });
+  }
+
+  let mut sources = Vec::new();
+
+  match split.unpublished_deps {
+    UnpublishedDepsPolicy::Fail => {
+      let listing: Vec<String> = unpublished
+        .iter()
+        .map(|dep| {
+          format!(
+            "  • {} ({}) ← {} {}",
+            dep.name,
+            crate_dir(workspace_root, dep).display(),
+            if dep.dev_only {
+              "dev-dependency of"
+            } else {
+              "required by"
+            },
+            dep.required_by
+          )
+        })
+        .collect();
+
+      return Err(RailError::with_help(
+        format!(
+          "Split '{}' depends on unpublished workspace crates:\n{}\n\
+           Converted to version dependencies, these would leave the split repo unable to resolve \
+           (dev-dependencies included: the split repo resolves them to build its tests).",
+          split.name,
+          listing.join("\n")
+        ),
+        format!(
+          "Choose how to provide them in the [[splits]] entry for '{}':\n  \
+           unpublished_deps = \"include\"  # bundle them into a combined split\n  \
+           unpublished_deps = \"git\"      # use git deps on their own split remotes\n\
+           Or publish them to crates.io.",
+          split.name
+        ),
+      ));
+    }
+    UnpublishedDepsPolicy::Include => {
+      for dep in &unpublished {
+        let path = crate_dir(workspace_root, dep);
+        if !crate_paths.contains(&path) {
+          crate_paths.push(path.clone());
+        }
+        sources.push(DependencySource::Bundled {
+          name: dep.name.clone(),
+          path: path.display().to_string(),
+        });
+      }
+    }
+    UnpublishedDepsPolicy::Git => {
+      // Only direct dependencies matter: the dependency's own split carries the rest
+      let mut missing = Vec::new();
+      for dep in unpublished.iter().filter(|dep| roots.contains(&dep.required_by)) {
+        let path = crate_dir(workspace_root, dep);
+        let Some(dep_split) = splits
+          .iter()
+          .find(|s| s.name != split.name && s.paths.iter().any(|cp| cp.path == path))
+        else {
+          missing.push(format!("  • {} ({})", dep.name, path.display()));
+          continue;
+        };
+
+        sources.push(DependencySource::Git {
+          name: dep.name.clone(),
+          url: git_url(workspace_root, &dep_split.remote),
+          branch: dep_split.branch.clone(),
+        });
+      }
+
+      if !missing.is_empty() {
+        return Err(RailError::with_help(
+          format!(
+            "Split '{}' uses unpublished_deps = \"git\", but these dependencies have no split of their own:\n{}",
+            split.name,
+            missing.join("\n")
+          ),
+          "Add a [[splits]] entry for each of them, or use unpublished_deps = \"include\"",
+        ));
+      }
+    }
+  }
+
+  Ok(DependencyClosure { crate_paths, sources })
+}
+
+/// Build transform overrides from the dependency sources recorded in a plan
+pub fn dependency_overrides(sources: &[DependencySource]) -> DependencyOverrides {
+  let mut overrides = DependencyOverrides::default();
+  for source in sources {
+    match source {
+      DependencySource::Bundled { name, .. } => {
+        overrides.bundled.insert(name.clone());
+      }
+      DependencySource::Git { name, url, branch } => {
+        overrides.git.insert(name.clone(), (url.clone(), branch.clone()));
+      }
+    }
+  }
+  overrides
+}
+
+/// Workspace-relative directory of an unpublished dependency
+fn crate_dir(workspace_root: &Path, dep: &UnpublishedDependency) -> PathBuf {
+  let dir = dep.manifest_path.parent().unwrap_or(&dep.manifest_path);
+  let root = workspace_root
+    .canonicalize()
+    .unwrap_or_else(|_| workspace_root.to_path_buf());
+  let dir_canonical = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+  dir_canonical
+    .strip_prefix(&root)
+    .map(Path::to_path_buf)
+    .unwrap_or(dir_canonical)
+}
+
+/// Cargo needs a URL for git dependencies: local remotes become `file://` URLs and
+/// scp-style remotes (`git@host:org/repo.git`) become `ssh://` URLs
+fn git_url(workspace_root: &Path, remote: &str) -> String {
+  if !utils::is_local_path(remote) {
+    if !remote.contains("://")
+      && let Some((host, path)) = remote.split_once(':')
+    {
+      return format!("ssh://{}/{}", host, path);
+    }
+    return remote.to_string();
+  }
+
+  let path = workspace_root.join(remote);
+  let absolute = path.canonicalize().unwrap_or(path);
+  format!("file://{}", absolute.display())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_dependency_overrides_from_sources() {
+    let sources = vec![
+      DependencySource::Bundled {
+        name: "internal-util".to_string(),
+        path: "crates/internal-util".to_string(),
+      },
+      DependencySource::Git {
+        name: "internal-proto".to_string(),
+        url: "https://example.com/internal-proto.git".to_string(),
+        branch: "main".to_string(),
+      },
+    ];
+
+    let overrides = dependency_overrides(&sources);
+    assert!(overrides.bundled.contains("internal-util"));
+    assert_eq!(
+      overrides.git.get("internal-proto"),
+      Some(&("https://example.com/internal-proto.git".to_string(), "main".to_string()))
+    );
+  }
+
+  #[test]
+  fn test_git_url_for_remotes() {
+    assert_eq!(
+      git_url(Path::new("/ws"), "https://github.com/org/repo.git"),
+      "https://github.com/org/repo.git"
+    );
+    assert_eq!(
+      git_url(Path::new("/ws"), "git@github.com:org/repo.git"),
+      "ssh://git@github.com/org/repo.git"
+    );
+  }
+}
@@ -1,10 +1,16 @@
 /// Conflict resolution for cargo-rail
 ///
 /// Handles file-level conflicts when syncing changes between monorepo and split repos.
-/// Uses Git's battle-tested 3-way merge algorithm via `git merge-file`.
+/// Uses Git's battle-tested 3-way merge algorithm via `git merge-file`, and predicts
+/// conflicts for dry-runs via `git merge-tree --write-tree` (git 2.38+; `git merge-file` on
+/// older versions).
 use crate::core::error::{RailError, RailResult, ResultExt};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::OnceLock;
+
+/// First git version with `merge-tree --write-tree`
+const MERGE_TREE_WRITE_TREE: (u32, u32) = (2, 38);
 
 /// Strategy for resolving conflicts
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -39,12 +45,20 @@ impl ConflictStrategy {
 #[derive(Debug, Clone)]
 pub struct ConflictInfo {
   pub file_path: PathBuf,
-  /// Conflict description (used by SyncEngine, may be logged in future)
-  #[allow(dead_code)]
+  /// Conflict description
   pub message: String,
-  /// Whether conflict was auto-resolved (used by SyncEngine, may be logged in future)
-  #[allow(dead_code)]
+  /// Whether conflict was auto-resolved
   pub resolved: bool,
+  /// The merge sides, kept so an unresolved conflict can be settled later
+  pub versions: Option<MergeVersions>,
+}
+
+/// Base, ours (monorepo) and theirs (remote) content of a merged file
+#[derive(Debug, Clone)]
+pub struct MergeVersions {
+  pub base: Vec<u8>,
+  pub ours: Vec<u8>,
+  pub theirs: Vec<u8>,
 }
 
 /// Result of a merge operation
@@ -169,6 +183,248 @@ impl ConflictResolver {
   }
 }
 
+/// One file of a predicted 3-way merge (`None` = absent on that side)
+#[derive(Debug, Clone)]
+pub struct MergeInput {
+  pub path: PathBuf,
+  pub base: Option<Vec<u8>>,
+  pub ours: Option<Vec<u8>>,
+  pub theirs: Option<Vec<u8>>,
+}
+
+/// Predict which files a 3-way merge would conflict on, without touching the worktree
+///
+/// Writes throwaway base/ours/theirs commits into `repo`'s object store (through a temporary
+/// index) and runs `git merge-tree --write-tree` on them. Returns the conflicting paths.
+/// Git older than 2.38 has no `--write-tree`, so each file is merged with `git merge-file`.
+pub fn predict_conflicts(repo: &Path, files: &[MergeInput]) -> RailResult<Vec<PathBuf>> {
+  if files.is_empty() {
+    return Ok(Vec::new());
+  }
+  if !git_has_merge_tree_write_tree() {
+    return predict_with_merge_file(files);
+  }
+
+  let index = std::env::temp_dir().join(format!(
+    "cargo-rail-predict-{}-{}.index",
+    std::process::id(),
+    std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .unwrap_or_default()
+      .as_nanos()
+  ));
+
+  let result = (|| {
+    let base = write_side_tree(repo, &index, files, |f| f.base.as_deref())?;
+    let ours = write_side_tree(repo, &index, files, |f| f.ours.as_deref())?;
+    let theirs = write_side_tree(repo, &index, files, |f| f.theirs.as_deref())?;
+
+    let base = commit_tree(repo, &base, None)?;
+    let ours = commit_tree(repo, &ours, Some(&base))?;
+    let theirs = commit_tree(repo, &theirs, Some(&base))?;
+
+    let output = Command::new("git")
+      .current_dir(repo)
+      .args([
+        "merge-tree",
+        "--write-tree",
+        "--name-only",
+        "--no-messages",
+        &ours,
+        &theirs,
+      ])
+      .output()
+      .context("Failed to run git merge-tree")?;
+
+    // Exit 0 = clean, 1 = conflicts: first line is the tree, then the conflicting paths
+    match output.status.code() {
+      Some(0) => Ok(Vec::new()),
+      Some(1) => {
+        let mut conflicts: Vec<PathBuf> = String::from_utf8_lossy(&output.stdout)
+          .lines()
+          .skip(1)
+          .take_while(|line| !line.is_empty())
+          .map(PathBuf::from)
+          .collect();
+        conflicts.dedup();
+        Ok(conflicts)
+      }
+      _ => Err(RailError::message(format!(
+        "git merge-tree failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+      ))),
+    }
+  })();
+
+  let _ = std::fs::remove_file(&index);
+  result
+}
+
+/// Write one side of the merge as a tree, via a scratch index file
+fn write_side_tree(
+  repo: &Path,
+  index: &Path,
+  files: &[MergeInput],
+  side: impl Fn(&MergeInput) -> Option<&[u8]>,
+) -> RailResult<String> {
+  let _ = std::fs::remove_file(index);
+
+  let mut args = vec!["update-index".to_string(), "--add".to_string()];
+  for file in files {
+    if let Some(content) = side(file) {
+      let blob = run_git_with_input(repo, index, &["hash-object", "-w", "--stdin"], content)?;
+      args.push("--cacheinfo".to_string());
+      args.push(format!("100644,{},{}", blob, file.path.display()));
+    }
+  }
+  if args.len() > 2 {
+    run_git_with_input(repo, index, &args.iter().map(String::as_str).collect::<Vec<_>>(), &[])?;
+  }
+
+  run_git_with_input(repo, index, &["write-tree"], &[])
+}
+
+/// Merge each file on its own with `git merge-file`, for git without `merge-tree --write-tree`
+fn predict_with_merge_file(files: &[MergeInput]) -> RailResult<Vec<PathBuf>> {
+  let dir = std::env::temp_dir().join(format!(
+    "cargo-rail-predict-{}-{}",
+    std::process::id(),
+    std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .unwrap_or_default()
+      .as_nanos()
+  ));
+  std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+  let result = (|| {
+    let mut conflicts = Vec::new();
+    for file in files {
+      let (base, ours, theirs) = (file.base.as_deref(), file.ours.as_deref(), file.theirs.as_deref());
+      // One side unchanged (or both changed alike): the merge takes the other side
+      if ours == theirs || ours == base || theirs == base {
+        continue;
+      }
+      // Changed on one side, deleted on the other
+      let (Some(ours), Some(theirs)) = (ours, theirs) else {
+        conflicts.push(file.path.clone());
+        continue;
+      };
+
+      let paths = [dir.join("ours"), dir.join("base"), dir.join("theirs")];
+      for (path, content) in paths.iter().zip([ours, base.unwrap_or_default(), theirs]) {
+        std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+      }
+      let output = Command::new("git")
+        .arg("merge-file")
+        .args(["-p", "--quiet"])
+        .args(&paths)
+        .output()
+        .context("Failed to run git merge-file")?;
+      // Exit status is the number of conflicts; negative (255) on error
+      match output.status.code() {
+        Some(0) => {}
+        Some(1..=127) => conflicts.push(file.path.clone()),
+        _ => {
+          return Err(RailError::message(format!(
+            "git merge-file failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+          )));
+        }
+      }
+    }
+    Ok(conflicts)
+  })();
+
+  let _ = std::fs::remove_dir_all(&dir);
+  result
+}
+
+/// Whether the installed git supports `merge-tree --write-tree` (assumed if unknown)
+fn git_has_merge_tree_write_tree() -> bool {
+  static SUPPORTED: OnceLock<bool> = OnceLock::new();
+  *SUPPORTED.get_or_init(|| {
+    Command::new("git")
+      .arg("--version")
+      .output()
+      .ok()
+      .and_then(|o| parse_git_version(&String::from_utf8_lossy(&o.stdout)))
+      .is_none_or(|version| version >= MERGE_TREE_WRITE_TREE)
+  })
+}
+
+/// Major and minor version from `git --version` output ("git version 2.39.2 (Apple Git-143)")
+fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+  let version = output.trim().strip_prefix("git version ")?;
+  let mut parts = version.split(|c: char| !c.is_ascii_digit());
+  let major = parts.next()?.parse().ok()?;
+  let minor = parts.next()?.parse().ok()?;
+  Some((major, minor))
+}
+
+/// Wrap a tree in a commit so merge-tree can find the merge base
+///
+/// Never signed: the commits are throwaway, and signing could prompt for or fail on a key.
+fn commit_tree(repo: &Path, tree: &str, parent: Option<&str>) -> RailResult<String> {
+  let mut args = vec![
+    "commit-tree",
+    "--no-gpg-sign",
+    tree,
+    "-m",
+    "cargo-rail conflict prediction",
+  ];
+  if let Some(parent) = parent {
+    args.extend(["-p", parent]);
+  }
+
+  let output = Command::new("git")
+    .current_dir(repo)
+    .args(&args)
+    .env("GIT_AUTHOR_NAME", "cargo-rail")
+    .env("GIT_AUTHOR_EMAIL", "cargo-rail@localhost")
+    .env("GIT_COMMITTER_NAME", "cargo-rail")
+    .env("GIT_COMMITTER_EMAIL", "cargo-rail@localhost")
+    .output()
+    .context("Failed to run git commit-tree")?;
+
+  if !output.status.success() {
+    return Err(RailError::message(format!(
+      "git commit-tree failed: {}",
+      String::from_utf8_lossy(&output.stderr)
+    )));
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Run git against a scratch index, feeding `input` on stdin; returns trimmed stdout
+fn run_git_with_input(repo: &Path, index: &Path, args: &[&str], input: &[u8]) -> RailResult<String> {
+  use std::io::Write;
+  use std::process::Stdio;
+
+  let mut child = Command::new("git")
+    .current_dir(repo)
+    .args(args)
+    .env("GIT_INDEX_FILE", index)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .with_context(|| format!("Failed to run git {}", args[0]))?;
+
+  if let Some(mut stdin) = child.stdin.take() {
+    stdin.write_all(input)?;
+  }
+
+  let output = child.wait_with_output()?;
+  if !output.status.success() {
+    return Err(RailError::message(format!(
+      "git {} failed: {}",
+      args[0],
+      String::from_utf8_lossy(&output.stderr)
+    )));
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -299,4 +555,84 @@ mod tests {
       _ => panic!("Expected clean merge with --union"),
     }
   }
+
+  #[test]
+  fn test_predict_conflicts_with_merge_tree() {
+    let temp = TempDir::new().unwrap();
+    Command::new("git")
+      .current_dir(temp.path())
+      .args(["init", "--quiet"])
+      .status()
+      .unwrap();
+
+    let file = |path: &str, ours: &[u8], theirs: &[u8]| MergeInput {
+      path: PathBuf::from(path),
+      base: Some(b"line 1\nline 2\nline 3\n".to_vec()),
+      ours: Some(ours.to_vec()),
+      theirs: Some(theirs.to_vec()),
+    };
+
+    let conflicts = predict_conflicts(
+      temp.path(),
+      &[
+        file(
+          "src/clean.rs",
+          b"line 1 ours\nline 2\nline 3\n",
+          b"line 1\nline 2\nline 3 theirs\n",
+        ),
+        file(
+          "src/conflict.rs",
+          b"line 1\nline 2 ours\nline 3\n",
+          b"line 1\nline 2 theirs\nline 3\n",
+        ),
+      ],
+    )
+    .unwrap();
+
+    assert_eq!(conflicts, vec![PathBuf::from("src/conflict.rs")]);
+    // Nothing was written to the worktree
+    assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 1);
+  }
+
+  #[test]
+  fn test_predict_conflicts_with_merge_file() {
+    let file = |path: &str, base: Option<&[u8]>, ours: Option<&[u8]>, theirs: Option<&[u8]>| MergeInput {
+      path: PathBuf::from(path),
+      base: base.map(<[u8]>::to_vec),
+      ours: ours.map(<[u8]>::to_vec),
+      theirs: theirs.map(<[u8]>::to_vec),
+    };
+    let base: &[u8] = b"line 1\nline 2\nline 3\n";
+
+    let conflicts = predict_with_merge_file(&[
+      file(
+        "src/clean.rs",
+        Some(base),
+        Some(b"line 1 ours\nline 2\nline 3\n"),
+        Some(b"line 1\nline 2\nline 3 theirs\n"),
+      ),
+      file(
+        "src/conflict.rs",
+        Some(base),
+        Some(b"line 1\nline 2 ours\nline 3\n"),
+        Some(b"line 1\nline 2 theirs\nline 3\n"),
+      ),
+      file("src/deleted.rs", Some(base), None, Some(b"line 1\n")),
+      file("src/added.rs", None, Some(b"same\n"), Some(b"same\n")),
+    ])
+    .unwrap();
+
+    assert_eq!(
+      conflicts,
+      vec![PathBuf::from("src/conflict.rs"), PathBuf::from("src/deleted.rs")]
+    );
+  }
+
+  #[test]
+  fn test_parse_git_version() {
+    assert_eq!(parse_git_version("git version 2.39.2\n"), Some((2, 39)));
+    assert_eq!(parse_git_version("git version 2.37.1 (Apple Git-137.1)"), Some((2, 37)));
+    assert_eq!(parse_git_version("git version 2.45.windows.1"), Some((2, 45)));
+    assert_eq!(parse_git_version("not git"), None);
+  }
 }
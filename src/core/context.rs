@@ -23,6 +23,7 @@
 //! ```
 
 use crate::cargo::metadata::WorkspaceMetadata;
+use crate::cargo::transform::CargoTransform;
 use crate::core::config::RailConfig;
 use crate::core::error::RailResult;
 use crate::graph::workspace_graph::WorkspaceGraph;
@@ -46,6 +47,9 @@ pub struct WorkspaceContext {
   #[allow(dead_code)]
   pub metadata: WorkspaceMetadata,
 
+  /// Manifest transform built from the metadata, shared by every split and sync
+  pub transform: CargoTransform,
+
   /// Dependency graph (built from metadata)
   /// Wrapped in Arc for efficient sharing across threads/commands
   pub graph: Arc<WorkspaceGraph>,
@@ -73,12 +77,14 @@ impl WorkspaceContext {
   pub fn build(workspace_root: &Path) -> RailResult<Self> {
     let root = workspace_root.to_path_buf();
     let metadata = WorkspaceMetadata::load(&root)?;
+    let transform = CargoTransform::new(metadata.clone());
     let graph = Arc::new(WorkspaceGraph::load(&root)?);
     let config = RailConfig::load(&root).ok().map(Arc::new); // Optional - not all commands need it
 
     Ok(Self {
       root,
       metadata,
+      transform,
       graph,
       config,
     })
@@ -10,20 +10,195 @@
 //! - Dry-run mode (show plan without executing)
 //! - Idempotent operations (same plan = same result)
 //! - Auditability (plans are JSON-serializable)
+//! - Rollback (applied splits/syncs are recorded for `cargo rail undo`)
 
-use crate::cargo::metadata::WorkspaceMetadata;
-use crate::cargo::transform::{CargoTransform, TransformContext};
-use crate::core::config::{SecurityConfig, SplitMode};
+use crate::cargo::transform::TransformContext;
+use crate::core::auth::GitAuth;
+use crate::core::closure;
+use crate::core::config::{RemoteConfig, SecurityConfig, SplitMode, WorkspaceMode};
 use crate::core::conflict::ConflictStrategy;
 use crate::core::context::WorkspaceContext;
-use crate::core::error::{RailError, RailResult, ResultExt};
-use crate::core::plan::{Operation, Plan};
+use crate::core::error::{GitError, RailError, RailResult, ResultExt};
+use crate::core::history::{AppliedPlan, Effect, OperationWatch, UNDO_TRAILER, is_ancestor};
+use crate::core::mapping::MappingStore;
+use crate::core::mirror;
+use crate::core::plan::{DependencySource, Operation, Plan, SyncPreview};
+use crate::core::retire;
+use crate::core::security::{SecurityValidator, ssh_signing_args};
 use crate::core::split::{SplitConfig, Splitter};
-use crate::core::sync::{SyncConfig, SyncDirection, SyncEngine};
+use crate::core::sync::{BundleSync, DivergencePolicy, SyncConfig, SyncDirection, SyncEngine};
 use crate::core::vcs::SystemGit;
+use crate::ui::progress::MultiProgress;
+use crate::utils;
+use chrono::Utc;
+use rayon::prelude::*;
+use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// What executing a plan did
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExecutionReport {
+  /// Commits split or synced (new mapping notes in the monorepo)
+  pub commits: usize,
+  /// Files merged on both sides during a sync
+  pub conflicts: usize,
+  /// Remote commits held back by the inbound trust policy
+  pub quarantined: usize,
+  /// What was pushed, as `<remote> <ref>`
+  pub pushes: Vec<String>,
+}
+
+impl ExecutionReport {
+  /// Count mapped commits and pushes from the recorded effects
+  fn add_effects(&mut self, workspace_root: &Path, effects: &[Effect]) {
+    for effect in effects {
+      let pushed = match effect {
+        Effect::Commits { branch, pushed_to, .. } => pushed_to.as_ref().map(|remote| format!("{} {}", remote, branch)),
+        Effect::Branch { name, pushed_to, .. } => pushed_to.as_ref().map(|remote| format!("{} {}", remote, name)),
+        Effect::MappingNotes {
+          repo,
+          crate_name,
+          commits,
+          pushed_to,
+        } => {
+          if repo == workspace_root {
+            self.commits += commits.len();
+          }
+          pushed_to
+            .as_ref()
+            .map(|remote| format!("{} refs/notes/rail/{}", remote, crate_name))
+        }
+        Effect::Repo { pushed_to, .. } => pushed_to.clone(),
+        Effect::File { .. } | Effect::Tag { .. } => None,
+      };
+      if let Some(pushed) = pushed
+        && !self.pushes.contains(&pushed)
+      {
+        self.pushes.push(pushed);
+      }
+    }
+  }
+}
+
+/// How one plan of a batch ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+  Ok,
+  Failed,
+  /// Not started because an earlier plan failed (`--fail-fast`)
+  Skipped,
+}
+
+/// Outcome of one plan of a batch
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchEntry {
+  pub name: String,
+  pub status: BatchStatus,
+  #[serde(flatten)]
+  pub report: ExecutionReport,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+}
+
+impl BatchEntry {
+  fn ok(name: String, report: ExecutionReport) -> Self {
+    Self {
+      name,
+      status: BatchStatus::Ok,
+      report,
+      error: None,
+    }
+  }
+
+  fn failed(name: String, error: &RailError) -> Self {
+    Self {
+      name,
+      status: BatchStatus::Failed,
+      report: ExecutionReport::default(),
+      error: Some(error.to_string()),
+    }
+  }
+
+  fn skipped(name: String) -> Self {
+    Self {
+      name,
+      status: BatchStatus::Skipped,
+      report: ExecutionReport::default(),
+      error: None,
+    }
+  }
+}
+
+/// Outcome of a batch of plans (`split --all` / `sync --all`), in plan order
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchReport {
+  pub entries: Vec<BatchEntry>,
+}
+
+impl BatchReport {
+  /// Number of plans that failed
+  pub fn failures(&self) -> usize {
+    self.entries.iter().filter(|e| e.status == BatchStatus::Failed).count()
+  }
+
+  /// Error for a batch with failures, `Ok` otherwise
+  pub fn into_result(self) -> RailResult<()> {
+    match self.failures() {
+      0 => Ok(()),
+      failures => Err(RailError::with_help(
+        format!("{} of {} plans failed", failures, self.entries.len()),
+        "See the summary above; re-run the failed splits individually for full output",
+      )),
+    }
+  }
+
+  /// Format as a summary table
+  pub fn to_table(&self) -> String {
+    let width = self
+      .entries
+      .iter()
+      .map(|e| e.name.len())
+      .max()
+      .unwrap_or(0)
+      .max("SPLIT".len());
+    let mut out = format!(
+      "{:<width$}  {:<8} {:>7} {:>9}  PUSHED\n",
+      "SPLIT",
+      "STATUS",
+      "COMMITS",
+      "CONFLICTS",
+      width = width
+    );
+
+    for entry in &self.entries {
+      let status = match entry.status {
+        BatchStatus::Ok => "ok",
+        BatchStatus::Failed => "failed",
+        BatchStatus::Skipped => "skipped",
+      };
+      let pushed = match entry.error {
+        Some(ref error) => error.lines().next().unwrap_or_default().to_string(),
+        None if entry.report.pushes.is_empty() => "-".to_string(),
+        None => entry.report.pushes.join(", "),
+      };
+      out.push_str(&format!(
+        "{:<width$}  {:<8} {:>7} {:>9}  {}\n",
+        entry.name,
+        status,
+        entry.report.commits,
+        entry.report.conflicts,
+        pushed,
+        width = width
+      ));
+    }
+
+    out
+  }
+}
 
 /// Executes plans in a workspace context
 ///
@@ -43,25 +218,133 @@ impl<'a> PlanExecutor<'a> {
   /// Execute a plan
   ///
   /// Executes all operations in order. If any operation fails, execution stops
-  /// and returns the error.
-  pub fn execute(&self, plan: &Plan) -> RailResult<()> {
+  /// and returns the error. What splits and syncs changed is recorded under the plan's ID
+  /// (even if a later operation fails) so it can be reversed with `cargo rail undo`.
+  pub fn execute(&self, plan: &Plan) -> RailResult<ExecutionReport> {
+    let mut effects = Vec::new();
+    let mut report = ExecutionReport::default();
+    let mut result = Ok(());
+
     for (idx, operation) in plan.operations.iter().enumerate() {
-      self
-        .execute_operation(operation)
-        .with_context(|| format!("Failed to execute operation {} of {}", idx + 1, plan.operations.len()))?;
+      let watch = self.watch(operation);
+      result = self
+        .execute_operation(operation, &mut report)
+        .with_context(|| format!("Failed to execute operation {} of {}", idx + 1, plan.operations.len()));
+
+      if let Some(watch) = watch {
+        effects.extend(watch.finish());
+      }
+      if result.is_err() {
+        break;
+      }
     }
 
-    Ok(())
+    report.add_effects(&self.context.root, &effects);
+    if !effects.is_empty() {
+      AppliedPlan::record(
+        &self.context.root,
+        &plan.metadata.id,
+        plan.metadata.operation_type.clone(),
+        plan.metadata.crate_name.clone(),
+        effects,
+      )?;
+      println!(
+        "\n   ↩️  Applied plan {} (undo with: cargo rail undo {})",
+        plan.metadata.id, plan.metadata.id
+      );
+    }
+
+    result.map(|_| report)
+  }
+
+  /// Execute independent plans concurrently, at most `jobs` at a time (0 = one per CPU)
+  ///
+  /// Plans share this executor's workspace context. A failed plan doesn't stop the others
+  /// unless `fail_fast` is set, in which case plans not yet started are skipped.
+  pub fn execute_batch(&self, plans: Vec<(String, Plan)>, jobs: usize, fail_fast: bool) -> RailResult<BatchReport> {
+    let pool = rayon::ThreadPoolBuilder::new()
+      .num_threads(jobs)
+      .build()
+      .map_err(|e| RailError::message(format!("Failed to start worker threads: {}", e)))?;
+
+    let multi_progress = MultiProgress::new();
+    let bars: Vec<_> = plans
+      .iter()
+      .map(|(name, plan)| multi_progress.add_bar(1, format!("{} {}", plan.metadata.operation_type, name)))
+      .collect();
+    let failed = AtomicBool::new(false);
+
+    let entries = pool.install(|| {
+      plans
+        .into_par_iter()
+        .zip(bars.into_par_iter())
+        .map(|((name, plan), bar)| {
+          if fail_fast && failed.load(Ordering::SeqCst) {
+            return BatchEntry::skipped(name);
+          }
+
+          let entry = match self.execute(&plan) {
+            Ok(report) => BatchEntry::ok(name, report),
+            Err(e) => {
+              failed.store(true, Ordering::SeqCst);
+              BatchEntry::failed(name, &e)
+            }
+          };
+          multi_progress.inc(&bar);
+          entry
+        })
+        .collect()
+    });
+
+    Ok(BatchReport { entries })
+  }
+
+  /// Snapshot the repositories a split or sync operation touches
+  fn watch(&self, operation: &Operation) -> Option<OperationWatch> {
+    match operation {
+      Operation::ExecuteSplit {
+        crate_name,
+        target_repo_path,
+        branch,
+        remote_url,
+        ..
+      } => Some(OperationWatch::begin(
+        &self.context.root,
+        crate_name,
+        Path::new(target_repo_path),
+        branch,
+        remote_url.as_deref(),
+      )),
+      Operation::ExecuteSync {
+        crate_name,
+        target_repo_path,
+        branch,
+        remote_url,
+        ..
+      } => Some(OperationWatch::begin(
+        &self.context.root,
+        crate_name,
+        Path::new(target_repo_path),
+        branch,
+        Some(remote_url),
+      )),
+      _ => None,
+    }
   }
 
   /// Execute a single operation
-  fn execute_operation(&self, operation: &Operation) -> RailResult<()> {
+  fn execute_operation(&self, operation: &Operation, report: &mut ExecutionReport) -> RailResult<()> {
     match operation {
       Operation::InitRepo { path } => self.execute_init_repo(path),
-      Operation::Clone { url, path } => self.execute_clone(url, path),
+      Operation::Clone { url, path, auth } => self.execute_clone(url, path, auth),
       Operation::CreateCommit { message, files } => self.execute_create_commit(message, files),
-      Operation::Push { remote, branch, force } => self.execute_push(remote, branch, *force),
-      Operation::Pull { remote, branch } => self.execute_pull(remote, branch),
+      Operation::Push {
+        remote,
+        branch,
+        force,
+        auth,
+      } => self.execute_push(remote, branch, *force, auth),
+      Operation::Pull { remote, branch, auth } => self.execute_pull(remote, branch, auth),
       Operation::Transform { path, transform_type } => self.execute_transform(path, transform_type),
       Operation::Copy { from, to } => self.execute_copy(from, to),
       Operation::CreateBranch { name, from } => self.execute_create_branch(name, from),
@@ -77,29 +360,71 @@ impl<'a> PlanExecutor<'a> {
         crate_name,
         crate_paths,
         mode,
+        workspace_mode,
         target_repo_path,
         branch,
         remote_url,
-      } => self.execute_split(crate_name, crate_paths, mode, target_repo_path, branch, remote_url),
-      Operation::ExecuteSync {
-        crate_name,
-        crate_paths,
-        mode,
-        target_repo_path,
-        branch,
-        remote_url,
-        direction,
-        conflict_strategy,
-      } => self.execute_sync(
+        target_dir,
+        lockfile,
+        verify_build,
+        dependency_sources,
+        mirrors,
+        auth,
+      } => self.execute_split(
         crate_name,
         crate_paths,
         mode,
+        workspace_mode,
         target_repo_path,
         branch,
         remote_url,
-        direction,
-        conflict_strategy,
+        target_dir,
+        *lockfile,
+        *verify_build,
+        dependency_sources,
+        mirrors,
+        auth,
       ),
+      Operation::ExecuteSync { .. } => self.execute_sync(operation, report),
+      Operation::ResetBranch { repo, branch, from, to } => self.execute_reset_branch(repo, branch, from, to),
+      Operation::RevertCommits {
+        repo,
+        branch,
+        from,
+        to,
+        push_to,
+        undo_of,
+        auth,
+      } => self.execute_revert_commits(repo, branch, from, to, push_to, undo_of, auth),
+      Operation::DeleteBranch {
+        repo,
+        name,
+        checkout,
+        remote,
+        auth,
+      } => self.execute_delete_branch(repo, name, checkout, remote, auth),
+      Operation::RemoveNotes {
+        repo,
+        crate_name,
+        commits,
+        push_to,
+        auth,
+      } => self.execute_remove_notes(repo, crate_name, commits, push_to, auth),
+      Operation::RestoreFile { path, content } => self.execute_restore_file(path, content),
+      Operation::DeleteTag { repo, name } => run_git(Path::new(repo), &["tag", "-d", name]).map(|_| ()),
+      Operation::RemoveRepo { path } => {
+        fs::remove_dir_all(path).with_context(|| format!("Failed to remove repository {}", path))
+      }
+      Operation::ArchiveSplitRepo { .. } => self.execute_archive_split_repo(operation),
+      Operation::ArchiveNotes {
+        repo,
+        crate_name,
+        remotes,
+        auth,
+      } => self.execute_archive_notes(Path::new(repo), crate_name, remotes, auth),
+      Operation::UpdateConfig { path, content, .. } => {
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path))
+      }
     }
   }
 
@@ -128,7 +453,7 @@ impl<'a> PlanExecutor<'a> {
   }
 
   /// Clone a repository
-  fn execute_clone(&self, url: &str, path: &str) -> RailResult<()> {
+  fn execute_clone(&self, url: &str, path: &str, auth: &GitAuth) -> RailResult<()> {
     let dest_path = Path::new(path);
 
     // Create parent directory if needed
@@ -137,10 +462,10 @@ impl<'a> PlanExecutor<'a> {
     }
 
     // Use git command to clone
-    let output = std::process::Command::new("git")
-      .arg("clone")
-      .arg(url)
-      .arg(path)
+    let mut cmd = std::process::Command::new("git");
+    cmd.arg("clone").arg(url).arg(path);
+    auth.apply(&mut cmd);
+    let output = cmd
       .output()
       .with_context(|| format!("Failed to clone repository from {}", url))?;
 
@@ -191,15 +516,16 @@ impl<'a> PlanExecutor<'a> {
   }
 
   /// Push to remote
-  fn execute_push(&self, remote: &str, branch: &str, force: bool) -> RailResult<()> {
+  fn execute_push(&self, remote: &str, branch: &str, force: bool, auth: &GitAuth) -> RailResult<()> {
     let mut args = vec!["push", remote, branch];
     if force {
       args.push("--force");
     }
 
-    let output = std::process::Command::new("git")
-      .current_dir(&self.context.root)
-      .args(&args)
+    let mut cmd = std::process::Command::new("git");
+    cmd.current_dir(&self.context.root).args(&args);
+    auth.apply(&mut cmd);
+    let output = cmd
       .output()
       .with_context(|| format!("Failed to push to {}/{}", remote, branch))?;
 
@@ -214,10 +540,11 @@ impl<'a> PlanExecutor<'a> {
   }
 
   /// Pull from remote
-  fn execute_pull(&self, remote: &str, branch: &str) -> RailResult<()> {
-    let output = std::process::Command::new("git")
-      .current_dir(&self.context.root)
-      .args(["pull", remote, branch])
+  fn execute_pull(&self, remote: &str, branch: &str, auth: &GitAuth) -> RailResult<()> {
+    let mut cmd = std::process::Command::new("git");
+    cmd.current_dir(&self.context.root).args(["pull", remote, branch]);
+    auth.apply(&mut cmd);
+    let output = cmd
       .output()
       .with_context(|| format!("Failed to pull from {}/{}", remote, branch))?;
 
@@ -247,9 +574,8 @@ impl<'a> PlanExecutor<'a> {
     let content = fs::read_to_string(file_path)
       .with_context(|| format!("Failed to read file for transform: {}", file_path.display()))?;
 
-    // Load workspace metadata and create transformer
-    let metadata = WorkspaceMetadata::load(&self.context.root)?;
-    let transformer = CargoTransform::new(metadata);
+    // Shared transformer from the workspace context
+    let transformer = &self.context.transform;
 
     // Determine crate name from context or path
     let crate_name = self
@@ -416,14 +742,22 @@ impl<'a> PlanExecutor<'a> {
 
   /// Execute a split workflow
   /// This delegates to the battle-tested Splitter implementation
+  #[allow(clippy::too_many_arguments)]
   fn execute_split(
     &self,
     crate_name: &str,
     crate_paths: &[String],
     mode: &str,
+    workspace_mode: &str,
     target_repo_path: &str,
     branch: &str,
     remote_url: &Option<String>,
+    target_dir: &Option<String>,
+    lockfile: bool,
+    verify_build: bool,
+    dependency_sources: &[DependencySource],
+    mirrors: &[RemoteConfig],
+    auth: &GitAuth,
   ) -> RailResult<()> {
     // Parse mode
     let split_mode = match mode {
@@ -431,6 +765,7 @@ impl<'a> PlanExecutor<'a> {
       "combined" | "Combined" => SplitMode::Combined,
       _ => return Err(RailError::message(format!("Invalid split mode: {}", mode))),
     };
+    let workspace_mode = parse_workspace_mode(workspace_mode)?;
 
     // Convert string paths to PathBuf
     let crate_paths: Vec<PathBuf> = crate_paths.iter().map(PathBuf::from).collect();
@@ -444,16 +779,27 @@ impl<'a> PlanExecutor<'a> {
       .unwrap_or_default();
 
     // Create splitter
-    let splitter = Splitter::new(self.context.root.clone(), security_config)?;
+    let splitter = Splitter::new(
+      self.context.root.clone(),
+      self.context.transform.clone(),
+      security_config,
+    )?
+    .with_dependency_overrides(closure::dependency_overrides(dependency_sources));
 
     // Build split config
     let split_config = SplitConfig {
       crate_name: crate_name.to_string(),
       crate_paths,
       mode: split_mode,
+      workspace_mode,
       target_repo_path: PathBuf::from(target_repo_path),
       branch: branch.to_string(),
       remote_url: remote_url.clone(),
+      target_dir: target_dir.as_ref().map(PathBuf::from).unwrap_or_default(),
+      lockfile,
+      verify_build,
+      mirrors: mirrors.to_vec(),
+      auth: auth.clone(),
     };
 
     // Execute the split
@@ -462,29 +808,42 @@ impl<'a> PlanExecutor<'a> {
     Ok(())
   }
 
-  /// Execute a sync workflow
-  /// This delegates to the battle-tested SyncEngine implementation
-  #[allow(clippy::too_many_arguments)]
-  fn execute_sync(
-    &self,
-    crate_name: &str,
-    crate_paths: &[String],
-    mode: &str,
-    target_repo_path: &str,
-    branch: &str,
-    remote_url: &str,
-    direction: &str,
-    conflict_strategy: &str,
-  ) -> RailResult<()> {
+  /// Build the sync engine and direction for an `ExecuteSync` operation
+  fn sync_engine(&self, operation: &Operation) -> RailResult<(SyncEngine, SyncDirection)> {
+    let Operation::ExecuteSync {
+      crate_name,
+      crate_paths,
+      mode,
+      workspace_mode,
+      target_repo_path,
+      branch,
+      remote_url,
+      direction,
+      conflict_strategy,
+      resume,
+      target_dir,
+      lockfile,
+      verify_build,
+      dependency_sources,
+      on_diverged,
+      bundle,
+      mirrors,
+      auth,
+    } = operation
+    else {
+      return Err(RailError::message("Expected an ExecuteSync operation"));
+    };
+
     // Parse mode
-    let split_mode = match mode {
+    let split_mode = match mode.as_str() {
       "single" | "Single" => SplitMode::Single,
       "combined" | "Combined" => SplitMode::Combined,
       _ => return Err(RailError::message(format!("Invalid split mode: {}", mode))),
     };
+    let workspace_mode = parse_workspace_mode(workspace_mode)?;
 
     // Parse direction
-    let sync_direction = match direction {
+    let sync_direction = match direction.as_str() {
       "to_remote" | "MonoToRemote" => SyncDirection::MonoToRemote,
       "from_remote" | "RemoteToMono" => SyncDirection::RemoteToMono,
       "bidirectional" | "Both" => SyncDirection::Both,
@@ -494,6 +853,20 @@ impl<'a> PlanExecutor<'a> {
     // Parse conflict strategy
     let strategy = ConflictStrategy::from_str(conflict_strategy)?;
 
+    // Plans written before divergence handling existed carry no policy
+    let on_diverged = if on_diverged.is_empty() {
+      DivergencePolicy::default()
+    } else {
+      DivergencePolicy::from_str(on_diverged)?
+    };
+
+    let bundle = match (bundle, &sync_direction) {
+      (None, _) => None,
+      (Some(path), SyncDirection::MonoToRemote) => Some(BundleSync::To(PathBuf::from(path))),
+      (Some(path), SyncDirection::RemoteToMono) => Some(BundleSync::From(PathBuf::from(path))),
+      (Some(_), _) => return Err(RailError::message("Bundle syncs run in one direction")),
+    };
+
     // Convert string paths to PathBuf
     let crate_paths: Vec<PathBuf> = crate_paths.iter().map(PathBuf::from).collect();
 
@@ -510,32 +883,413 @@ impl<'a> PlanExecutor<'a> {
       crate_name: crate_name.to_string(),
       crate_paths,
       mode: split_mode,
+      workspace_mode,
       target_repo_path: PathBuf::from(target_repo_path),
       branch: branch.to_string(),
       remote_url: remote_url.to_string(),
+      dependency_overrides: closure::dependency_overrides(dependency_sources),
+      target_dir: target_dir.as_ref().map(PathBuf::from).unwrap_or_default(),
+      lockfile: *lockfile,
+      verify_build: *verify_build,
+      resume: *resume,
+      on_diverged,
+      bundle,
+      mirrors: mirrors.clone(),
+      auth: auth.clone(),
+      forge: self.context.config.as_ref().and_then(|c| c.forge.clone()),
     };
 
-    // Create sync engine
-    let mut sync_engine = SyncEngine::new(self.context.root.clone(), sync_config, security_config, strategy)?;
+    let sync_engine = SyncEngine::new(
+      self.context.root.clone(),
+      self.context.transform.clone(),
+      sync_config,
+      security_config,
+      strategy,
+    )?;
+
+    Ok((sync_engine, sync_direction))
+  }
+
+  /// Preview what the plan's sync would replay, without applying it
+  ///
+  /// Returns `None` for plans without an `ExecuteSync` operation.
+  pub fn preview_sync(&self, plan: &Plan) -> RailResult<Option<SyncPreview>> {
+    let Some(operation) = plan
+      .operations
+      .iter()
+      .find(|op| matches!(op, Operation::ExecuteSync { .. }))
+    else {
+      return Ok(None);
+    };
+
+    let (mut sync_engine, sync_direction) = self.sync_engine(operation)?;
+    sync_engine.preview(&sync_direction).map(Some)
+  }
+
+  /// Execute a sync workflow
+  /// This delegates to the battle-tested SyncEngine implementation
+  fn execute_sync(&self, operation: &Operation, report: &mut ExecutionReport) -> RailResult<()> {
+    let (mut sync_engine, sync_direction) = self.sync_engine(operation)?;
 
     // Execute the sync based on direction
-    match sync_direction {
-      SyncDirection::MonoToRemote => {
-        sync_engine.sync_to_remote()?;
+    let result = match sync_direction {
+      SyncDirection::MonoToRemote => sync_engine.sync_to_remote()?,
+      SyncDirection::RemoteToMono => sync_engine.sync_from_remote()?,
+      SyncDirection::Both => sync_engine.sync_bidirectional()?,
+      SyncDirection::None => {
+        // Nothing to do
+        return Ok(());
       }
-      SyncDirection::RemoteToMono => {
-        sync_engine.sync_from_remote()?;
+    };
+    report.conflicts += result.conflicts.len();
+    report.quarantined += result.quarantined.len();
+
+    Ok(())
+  }
+
+  /// Move a branch back to an earlier commit, provided nothing else moved it meanwhile
+  fn execute_reset_branch(&self, repo: &str, branch: &str, from: &str, to: &str) -> RailResult<()> {
+    let repo = Path::new(repo);
+    let git = SystemGit::open(repo)?;
+
+    let current = git.resolve_reference(&format!("refs/heads/{}", branch))?;
+    if current != from {
+      return Err(RailError::with_help(
+        format!(
+          "Branch '{}' in {} moved since the plan was applied",
+          branch,
+          repo.display()
+        ),
+        "Re-run 'cargo rail undo' to build a fresh undo plan",
+      ));
+    }
+
+    if git.current_branch()? == branch {
+      ensure_clean(repo)?;
+      git.reset_hard(to)?;
+    } else {
+      run_git(repo, &["update-ref", &format!("refs/heads/{}", branch), to, from])?;
+    }
+
+    Ok(())
+  }
+
+  /// Revert commits newest-first on a branch, marking each with the undo trailer
+  ///
+  /// Merge commits are reverted against their first parent (the branch they were merged into),
+  /// which undoes the commits they brought in as well.
+  /// The reverts are signed like split and sync commits when `require_signed_commits` is set.
+  #[allow(clippy::too_many_arguments)]
+  fn execute_revert_commits(
+    &self,
+    repo: &str,
+    branch: &str,
+    from: &str,
+    to: &str,
+    push_to: &Option<String>,
+    undo_of: &str,
+    auth: &GitAuth,
+  ) -> RailResult<()> {
+    let repo = Path::new(repo);
+    let git = SystemGit::open(repo)?.with_auth(auth.clone());
+    let signing_key = self
+      .context
+      .config
+      .as_ref()
+      .and_then(|c| SecurityValidator::new(c.security.clone()).signing_key_path());
+    let signing_args: Vec<String> = signing_key
+      .as_deref()
+      .map(|key| ssh_signing_args(key).to_vec())
+      .unwrap_or_default();
+
+    // Checked before the checkout, which would carry local changes over to the branch
+    ensure_clean(repo)?;
+    if git.current_branch()? != branch {
+      git.checkout_branch(branch)?;
+    }
+
+    let range = format!("{}..{}", from, to);
+    // Along the branch only: reverting a merge against its first parent also undoes the
+    // commits it brought in
+    let commits = run_git(repo, &["rev-list", "--first-parent", "--parents", &range])?;
+    for line in commits.lines() {
+      let mut shas = line.split_whitespace();
+      let Some(sha) = shas.next() else {
+        continue;
+      };
+      let mut revert = vec!["revert", "--no-commit"];
+      if shas.count() > 1 {
+        revert.extend(["-m", "1"]);
       }
-      SyncDirection::Both => {
-        sync_engine.sync_bidirectional()?;
+      revert.push(sha);
+      if let Err(e) = run_git(repo, &revert) {
+        let _ = run_git(repo, &["revert", "--abort"]);
+        return Err(e);
       }
-      SyncDirection::None => {
-        // Nothing to do
+
+      let subject = git
+        .get_commit_message(sha)?
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+      let message = format!(
+        "Revert \"{}\"\n\nThis reverts commit {}.\n\n{} {}",
+        subject, sha, UNDO_TRAILER, undo_of
+      );
+      let mut commit: Vec<&str> = signing_args.iter().map(String::as_str).collect();
+      commit.extend(["commit", "--quiet", "--allow-empty", "-m", &message]);
+      if signing_key.is_some() {
+        commit.push("-S");
       }
+      run_git(repo, &commit)?;
+    }
+
+    if let Some(remote) = push_to {
+      git.push_to_remote(remote, branch)?;
+    }
+
+    Ok(())
+  }
+
+  /// Delete a branch, checking out another one first if needed
+  fn execute_delete_branch(
+    &self,
+    repo: &str,
+    name: &str,
+    checkout: &str,
+    remote: &Option<String>,
+    auth: &GitAuth,
+  ) -> RailResult<()> {
+    let repo = Path::new(repo);
+    let git = SystemGit::open(repo)?;
+
+    if git.current_branch()? == name {
+      ensure_clean(repo)?;
+      git.checkout_branch(checkout)?;
+    }
+    if git.resolve_reference(&format!("refs/heads/{}", name)).is_ok() {
+      run_git(repo, &["branch", "-D", name])?;
+    }
+
+    if let Some(remote) = remote
+      && let Err(e) = run_remote_git(repo, &["push", remote, "--delete", name], auth)
+      && !e.to_string().contains("remote ref does not exist")
+    {
+      return Err(e);
+    }
+
+    Ok(())
+  }
+
+  /// Remove mapping notes, pushing the updated notes ref if they were published
+  fn execute_remove_notes(
+    &self,
+    repo: &str,
+    crate_name: &str,
+    commits: &[String],
+    push_to: &Option<String>,
+    auth: &GitAuth,
+  ) -> RailResult<()> {
+    let repo = Path::new(repo);
+    MappingStore::new(crate_name.to_string()).remove_notes(repo, commits)?;
+
+    if let Some(remote) = push_to {
+      run_remote_git(
+        repo,
+        &["push", remote, &format!("refs/notes/rail/{}", crate_name)],
+        auth,
+      )?;
     }
 
     Ok(())
   }
+
+  /// Commit the archival README of a retired split and push it to the split's remotes
+  ///
+  /// The split repo is brought up to date with its remote first; local commits the remote
+  /// doesn't have stop the archive, since they would be published along with it.
+  fn execute_archive_split_repo(&self, operation: &Operation) -> RailResult<()> {
+    let Operation::ArchiveSplitRepo {
+      crate_name,
+      target_repo_path,
+      branch,
+      remote_url,
+      readme_path,
+      readme,
+      message,
+      mirrors,
+      auth,
+    } = operation
+    else {
+      return Err(RailError::message("Expected an ArchiveSplitRepo operation"));
+    };
+
+    let repo = Path::new(target_repo_path);
+    let signing_key = self
+      .context
+      .config
+      .as_ref()
+      .and_then(|c| SecurityValidator::new(c.security.clone()).signing_key_path());
+    let git = SystemGit::open(repo)?
+      .with_auth(auth.clone())
+      .with_signing_key(signing_key);
+    let push = !utils::is_local_path(remote_url);
+
+    ensure_clean(repo)?;
+    if git.current_branch()? != *branch {
+      git.checkout_branch(branch)?;
+    }
+
+    let mut remote_tip = None;
+    if push {
+      git.fetch_from_remote("origin")?;
+      remote_tip = git.resolve_reference(&format!("refs/remotes/origin/{}", branch)).ok();
+      if let Some(ref tip) = remote_tip {
+        let head = git.head_commit()?;
+        if head != *tip {
+          if !is_ancestor(repo, &head, tip)? {
+            return Err(RailError::with_help(
+              format!(
+                "{} has commits on '{}' that aren't on the remote",
+                target_repo_path, branch
+              ),
+              format!(
+                "Push or drop them first, or retire without --archive: cargo rail retire {}",
+                crate_name
+              ),
+            ));
+          }
+          git.reset_hard(tip)?;
+        }
+      }
+    }
+
+    let readme_file = repo.join(readme_path);
+    if let Some(parent) = readme_file.parent() {
+      fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&readme_file, readme).with_context(|| format!("Failed to write {}", readme_file.display()))?;
+
+    let author_name = run_git(repo, &["config", "user.name"]).unwrap_or_else(|_| "Cargo Rail".to_string());
+    let author_email = run_git(repo, &["config", "user.email"]).unwrap_or_else(|_| "cargo-rail@localhost".to_string());
+    let parents: Vec<String> = git.head_commit().ok().into_iter().collect();
+    git.create_commit_with_metadata(message, &author_name, &author_email, Utc::now().timestamp(), &parents)?;
+    println!("   Committed {} in {}", readme_path, target_repo_path);
+
+    if !push {
+      println!("   Skipping push (local testing mode)");
+      return Ok(());
+    }
+    git.push_with_lease("origin", branch, remote_tip.as_deref())?;
+    if !mirrors.is_empty() {
+      mirror::report(&mirror::push_to_mirrors(
+        repo,
+        mirrors,
+        branch,
+        crate_name,
+        auth,
+        &self.context.root,
+      ))?;
+    }
+
+    Ok(())
+  }
+
+  /// Move a crate's mapping notes to the archive ref in a repo and on its remotes
+  fn execute_archive_notes(
+    &self,
+    repo: &Path,
+    crate_name: &str,
+    remotes: &[RemoteConfig],
+    auth: &GitAuth,
+  ) -> RailResult<()> {
+    if !retire::has_notes(repo, crate_name)
+      && let Some(primary) = remotes.first()
+    {
+      retire::fetch_notes(
+        repo,
+        crate_name,
+        &primary.url,
+        &auth.for_remote(primary, &self.context.root),
+      )?;
+    }
+    if retire::has_notes(repo, crate_name) {
+      retire::archive_notes(repo, crate_name)?;
+    }
+    for remote in remotes {
+      println!("   Archiving notes on '{}'...", remote.name);
+      retire::archive_remote_notes(
+        repo,
+        crate_name,
+        &remote.url,
+        &auth.for_remote(remote, &self.context.root),
+      )?;
+    }
+    Ok(())
+  }
+
+  /// Write a file back to its recorded contents
+  fn execute_restore_file(&self, path: &str, content: &Option<String>) -> RailResult<()> {
+    match content {
+      Some(content) => fs::write(path, content).with_context(|| format!("Failed to restore {}", path)),
+      None if Path::new(path).exists() => fs::remove_file(path).with_context(|| format!("Failed to remove {}", path)),
+      None => Ok(()),
+    }
+  }
+}
+
+/// Run git in a repository, returning stdout
+fn run_git(repo: &Path, args: &[&str]) -> RailResult<String> {
+  let mut cmd = std::process::Command::new("git");
+  cmd.current_dir(repo).args(args);
+  git_output(cmd, args)
+}
+
+/// Run git in a repository with credentials for the remote it talks to, returning stdout
+fn run_remote_git(repo: &Path, args: &[&str], auth: &GitAuth) -> RailResult<String> {
+  let mut cmd = std::process::Command::new("git");
+  cmd.current_dir(repo).args(args);
+  auth.apply(&mut cmd);
+  git_output(cmd, args)
+}
+
+fn git_output(mut cmd: std::process::Command, args: &[&str]) -> RailResult<String> {
+  let output = cmd
+    .output()
+    .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+
+  if !output.status.success() {
+    return Err(RailError::Git(GitError::CommandFailed {
+      command: format!("git {}", args.join(" ")),
+      stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    }));
+  }
+
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Refuse to rewrite a working tree with uncommitted changes to tracked files
+fn ensure_clean(repo: &Path) -> RailResult<()> {
+  if !run_git(repo, &["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+    return Err(RailError::with_help(
+      format!("{} has uncommitted changes", repo.display()),
+      "Commit or stash them first",
+    ));
+  }
+  Ok(())
+}
+
+/// Parse a combined-mode workspace layout (empty = standalone, for older plans)
+fn parse_workspace_mode(workspace_mode: &str) -> RailResult<WorkspaceMode> {
+  match workspace_mode {
+    "" | "standalone" | "Standalone" => Ok(WorkspaceMode::Standalone),
+    "workspace" | "Workspace" => Ok(WorkspaceMode::Workspace),
+    _ => Err(RailError::message(format!(
+      "Invalid workspace mode: {}",
+      workspace_mode
+    ))),
+  }
 }
 
 /// Recursively copy a directory
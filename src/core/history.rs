@@ -0,0 +1,806 @@
+//! Record of applied plans, used by `cargo rail undo`
+//!
+//! Applying a split, sync or release records what it changed: branch heads that moved,
+//! branches and repositories it created, mapping notes it added, files it rewrote and tags it
+//! created. `cargo rail undo <plan-id>` turns the latest record of a plan into its own `Plan`
+//! of reversing operations, which can be reviewed before it is applied.
+//!
+//! Records live in the monorepo's git directory, one file per plan:
+//!
+//! ```text
+//! .git/rail/applied/{plan-id}.json
+//! ```
+//!
+//! Plan IDs hash the plan's operations, so applying the same plan again (e.g. the next sync
+//! of a crate) stacks another application on the same record; undo reverses the latest one.
+
+use crate::core::auth::GitAuth;
+use crate::core::error::{RailError, RailResult, ResultExt};
+use crate::core::journal;
+use crate::core::mapping::MappingStore;
+use crate::core::plan::{Operation, OperationType, Plan, PlanId};
+use crate::core::vcs::SystemGit;
+use crate::utils;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Trailer marking commits created by `cargo rail undo` (never synced in either direction)
+pub const UNDO_TRAILER: &str = "Rail-Undo:";
+
+/// A change made by an applied plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Effect {
+  /// Commits added on top of a branch
+  Commits {
+    repo: PathBuf,
+    branch: String,
+    before: String,
+    after: String,
+    /// Remote the branch was pushed to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pushed_to: Option<String>,
+  },
+
+  /// Branch created and checked out (e.g. a PR branch)
+  Branch {
+    repo: PathBuf,
+    name: String,
+    /// Branch that was checked out before
+    previous: String,
+    /// Remote the branch was pushed to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pushed_to: Option<String>,
+  },
+
+  /// Mapping notes added under `refs/notes/rail/{crate}`
+  MappingNotes {
+    repo: PathBuf,
+    crate_name: String,
+    commits: Vec<String>,
+    /// Remote the notes were pushed to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pushed_to: Option<String>,
+  },
+
+  /// File rewritten in place (`before` is `None` if it didn't exist)
+  File { path: PathBuf, before: Option<String> },
+
+  /// Tag created
+  Tag { repo: PathBuf, name: String },
+
+  /// Repository created from scratch (e.g. by a split)
+  Repo {
+    path: PathBuf,
+    /// Remote URL the repository was pushed to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pushed_to: Option<String>,
+  },
+}
+
+/// One application of a plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Application {
+  /// When the plan was applied (RFC 3339)
+  pub applied_at: String,
+  /// What it changed, in order
+  pub effects: Vec<Effect>,
+}
+
+/// Record of an applied plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedPlan {
+  pub id: PlanId,
+  pub operation_type: OperationType,
+  pub crate_name: Option<String>,
+  /// Applications of this plan, oldest first
+  pub applications: Vec<Application>,
+  /// Where this record is stored
+  #[serde(skip)]
+  path: PathBuf,
+}
+
+impl AppliedPlan {
+  /// Append an application of a plan to its record
+  pub fn record(
+    workspace_root: &Path,
+    id: &PlanId,
+    operation_type: OperationType,
+    crate_name: Option<String>,
+    effects: Vec<Effect>,
+  ) -> RailResult<()> {
+    let path = applied_dir(workspace_root)?.join(format!("{}.json", id.full()));
+    let mut record = if path.exists() {
+      Self::read(&path)?
+    } else {
+      Self {
+        id: id.clone(),
+        operation_type,
+        crate_name,
+        applications: Vec::new(),
+        path,
+      }
+    };
+
+    record.applications.push(Application {
+      applied_at: Utc::now().to_rfc3339(),
+      effects,
+    });
+    record.persist()
+  }
+
+  /// All recorded plans, most recently applied first
+  pub fn list(workspace_root: &Path) -> RailResult<Vec<Self>> {
+    let dir = applied_dir(workspace_root)?;
+    let mut records = Vec::new();
+    for entry in std::fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+      let path = entry?.path();
+      if path.extension().is_some_and(|ext| ext == "json") {
+        records.push(Self::read(&path)?);
+      }
+    }
+    records.sort_by(|a, b| {
+      b.latest()
+        .map(|l| &l.applied_at)
+        .cmp(&a.latest().map(|l| &l.applied_at))
+    });
+    Ok(records)
+  }
+
+  /// Find the record of a plan by (a prefix of) its ID
+  pub fn find(workspace_root: &Path, id_prefix: &str) -> RailResult<Self> {
+    let mut matches: Vec<Self> = Self::list(workspace_root)?
+      .into_iter()
+      .filter(|record| record.id.full().starts_with(id_prefix))
+      .collect();
+
+    match matches.len() {
+      0 => Err(RailError::with_help(
+        format!("No applied plan matches '{}'", id_prefix),
+        "Run 'cargo rail undo' to list the plans that can be undone",
+      )),
+      1 => Ok(matches.remove(0)),
+      n => Err(RailError::with_help(
+        format!("Plan ID '{}' is ambiguous ({} applied plans match)", id_prefix, n),
+        "Use more characters of the plan ID",
+      )),
+    }
+  }
+
+  /// The most recent application
+  pub fn latest(&self) -> Option<&Application> {
+    self.applications.last()
+  }
+
+  /// Build the plan that reverses the latest application
+  ///
+  /// Unpushed commits are reset away; commits that were pushed (or that have new commits on
+  /// top) are reverted instead. Fails if a branch was rewritten since the plan was applied.
+  /// Pushes to the split repo's remote authenticate with `split_auth`; the monorepo's use git's
+  /// own setup, as the sync that made them did.
+  pub fn undo_plan(&self, workspace_root: &Path, split_auth: &GitAuth) -> RailResult<Plan> {
+    let application = self
+      .latest()
+      .ok_or_else(|| RailError::message(format!("Plan {} has nothing left to undo", self.id)))?;
+
+    let mut plan = Plan::new(OperationType::Undo, self.crate_name.clone());
+    let mut notes = vec![format!(
+      "   Undo {} plan {} (applied {})",
+      self.operation_type, self.id, application.applied_at
+    )];
+
+    for effect in application.effects.iter().rev() {
+      if let Some(note) = self.undo_effect(effect, workspace_root, split_auth, &mut plan)? {
+        notes.push(note);
+      }
+    }
+
+    let pushes = plan.operations.iter().any(|op| {
+      matches!(
+        op,
+        Operation::RevertCommits { push_to: Some(_), .. }
+          | Operation::DeleteBranch { remote: Some(_), .. }
+          | Operation::RemoveNotes { push_to: Some(_), .. }
+      )
+    });
+
+    let plan = plan.with_summary(notes.join("\n"));
+    Ok(if pushes { plan.mark_destructive() } else { plan })
+  }
+
+  /// Drop the latest application (once undone), deleting the record when none are left
+  pub fn pop(mut self) -> RailResult<()> {
+    self.applications.pop();
+    if self.applications.is_empty() {
+      std::fs::remove_file(&self.path).with_context(|| format!("Failed to remove {}", self.path.display()))?;
+      Ok(())
+    } else {
+      self.persist()
+    }
+  }
+
+  /// Add the operations reversing one effect; returns a note for the summary if needed
+  fn undo_effect(
+    &self,
+    effect: &Effect,
+    workspace_root: &Path,
+    split_auth: &GitAuth,
+    plan: &mut Plan,
+  ) -> RailResult<Option<String>> {
+    let auth_for = |repo: &Path| {
+      if repo == workspace_root {
+        GitAuth::default()
+      } else {
+        split_auth.clone()
+      }
+    };
+
+    match effect {
+      Effect::Commits {
+        repo,
+        branch,
+        before,
+        after,
+        pushed_to,
+      } => {
+        if !repo.exists() {
+          return Ok(Some(format!(
+            "   Skipped {}: repository no longer exists",
+            repo.display()
+          )));
+        }
+        let current = SystemGit::open(repo)?.resolve_reference(&format!("refs/heads/{}", branch))?;
+        if current == *before {
+          return Ok(Some(format!(
+            "   Skipped {} ({}): already undone",
+            repo.display(),
+            branch
+          )));
+        }
+        if !is_ancestor(repo, before, after)? {
+          // The branch was rewritten (a rebased PR branch): only moving it back undoes that
+          if pushed_to.is_some() || current != *after {
+            return Err(RailError::with_help(
+              format!(
+                "Branch '{}' in {} was rebased when the plan was applied",
+                branch,
+                repo.display()
+              ),
+              format!(
+                "Move it back by hand: git branch -f {} {} (and force-push it if it was pushed)",
+                branch,
+                short(before)
+              ),
+            ));
+          }
+          plan.add_operation(Operation::ResetBranch {
+            repo: repo.display().to_string(),
+            branch: branch.clone(),
+            from: after.clone(),
+            to: before.clone(),
+          });
+          return Ok(None);
+        }
+        if !is_ancestor(repo, after, &current)? {
+          return Err(RailError::with_help(
+            format!(
+              "Branch '{}' in {} was rewritten since the plan was applied",
+              branch,
+              repo.display()
+            ),
+            format!(
+              "The applied commits ({}..{}) are no longer on the branch",
+              short(before),
+              short(after)
+            ),
+          ));
+        }
+
+        if pushed_to.is_none() && current == *after {
+          plan.add_operation(Operation::ResetBranch {
+            repo: repo.display().to_string(),
+            branch: branch.clone(),
+            from: after.clone(),
+            to: before.clone(),
+          });
+        } else {
+          plan.add_operation(Operation::RevertCommits {
+            repo: repo.display().to_string(),
+            branch: branch.clone(),
+            from: before.clone(),
+            to: after.clone(),
+            push_to: pushed_to.clone(),
+            undo_of: self.id.full().to_string(),
+            auth: auth_for(repo),
+          });
+        }
+        Ok(None)
+      }
+      Effect::Branch {
+        repo,
+        name,
+        previous,
+        pushed_to,
+      } => {
+        plan.add_operation(Operation::DeleteBranch {
+          repo: repo.display().to_string(),
+          name: name.clone(),
+          checkout: previous.clone(),
+          remote: pushed_to.clone(),
+          auth: auth_for(repo),
+        });
+        Ok(None)
+      }
+      Effect::MappingNotes {
+        repo,
+        crate_name,
+        commits,
+        pushed_to,
+      } => {
+        if repo.exists() {
+          plan.add_operation(Operation::RemoveNotes {
+            repo: repo.display().to_string(),
+            crate_name: crate_name.clone(),
+            commits: commits.clone(),
+            push_to: pushed_to.clone(),
+            auth: auth_for(repo),
+          });
+        }
+        Ok(None)
+      }
+      Effect::File { path, before } => {
+        plan.add_operation(Operation::RestoreFile {
+          path: path.display().to_string(),
+          content: before.clone(),
+        });
+        Ok(None)
+      }
+      Effect::Tag { repo, name } => {
+        plan.add_operation(Operation::DeleteTag {
+          repo: repo.display().to_string(),
+          name: name.clone(),
+        });
+        Ok(Some(format!(
+          "   Tag {} is deleted locally only; if it was pushed: git push origin :refs/tags/{}",
+          name, name
+        )))
+      }
+      Effect::Repo { path, pushed_to } => {
+        if path.exists() {
+          plan.add_operation(Operation::RemoveRepo {
+            path: path.display().to_string(),
+          });
+        }
+        Ok(pushed_to.as_ref().map(|url| {
+          format!(
+            "   ⚠️  The split was pushed to {}; delete that repository on its host",
+            url
+          )
+        }))
+      }
+    }
+  }
+
+  fn read(path: &Path) -> RailResult<Self> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut record: Self = serde_json::from_str(&content).map_err(|e| {
+      RailError::with_help(
+        format!("Corrupt applied-plan record at {}: {}", path.display(), e),
+        "Delete the file; the plan it describes can then no longer be undone",
+      )
+    })?;
+    record.path = path.to_path_buf();
+    Ok(record)
+  }
+
+  /// Write the record atomically (temp file + fsync + rename)
+  fn persist(&self) -> RailResult<()> {
+    let tmp = self.path.with_extension("json.tmp");
+    let content = serde_json::to_vec_pretty(self)
+      .map_err(|e| RailError::message(format!("Failed to serialize applied-plan record: {}", e)))?;
+
+    let mut file = std::fs::File::create(&tmp).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    file.write_all(&content)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, &self.path).with_context(|| format!("Failed to write {}", self.path.display()))?;
+    Ok(())
+  }
+}
+
+/// State of a repository captured before and after an operation
+#[derive(Debug, Clone)]
+pub struct RepoSnapshot {
+  path: PathBuf,
+  existed: bool,
+  branch: Option<String>,
+  head: Option<String>,
+  /// Tips of all local branches
+  heads: HashMap<String, String>,
+  noted: HashSet<String>,
+}
+
+impl RepoSnapshot {
+  /// Capture a repository; `branch` is the branch to watch (the checked-out one if `None`)
+  pub fn capture(path: &Path, branch: Option<&str>, crate_name: &str) -> Self {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let git = if path.join(".git").exists() {
+      SystemGit::open(&path).ok()
+    } else {
+      None
+    };
+
+    let branch = match (branch, &git) {
+      (Some(branch), _) => Some(branch.to_string()),
+      (None, Some(git)) => git.current_branch().ok(),
+      (None, None) => None,
+    };
+    let head = match (&branch, &git) {
+      (Some(branch), Some(git)) => git.resolve_reference(&format!("refs/heads/{}", branch)).ok(),
+      _ => None,
+    };
+    let heads = match &git {
+      Some(git) => git
+        .git_cmd()
+        .args(["for-each-ref", "--format=%(refname:short) %(objectname)", "refs/heads"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+          String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, sha)| (name.to_string(), sha.to_string()))
+            .collect()
+        })
+        .unwrap_or_default(),
+      None => HashMap::new(),
+    };
+    let noted = match git {
+      Some(_) => MappingStore::new(crate_name.to_string()).noted_commits(&path),
+      None => HashSet::new(),
+    };
+
+    Self {
+      existed: path.exists(),
+      path,
+      branch,
+      head,
+      heads,
+      noted,
+    }
+  }
+
+  /// Effects of the operation that happened between `self` and `after`
+  ///
+  /// `pushed_to` is the remote new commits, branches and notes were pushed to.
+  pub fn effects_to(&self, after: &RepoSnapshot, crate_name: &str, pushed_to: Option<&str>) -> Vec<Effect> {
+    let pushed_to = pushed_to.map(str::to_string);
+
+    if !self.existed && after.existed {
+      return vec![Effect::Repo {
+        path: after.path.clone(),
+        pushed_to,
+      }];
+    }
+
+    let mut effects = Vec::new();
+    match (&self.branch, &self.head, &after.branch, &after.head) {
+      (Some(previous), _, Some(branch), Some(head)) if previous != branch => match self.heads.get(branch) {
+        // A branch that already existed (e.g. a reused PR branch) only got new commits
+        Some(before) if before != head => effects.push(Effect::Commits {
+          repo: after.path.clone(),
+          branch: branch.clone(),
+          before: before.clone(),
+          after: head.clone(),
+          pushed_to: pushed_to.clone(),
+        }),
+        Some(_) => {}
+        None => effects.push(Effect::Branch {
+          repo: after.path.clone(),
+          name: branch.clone(),
+          previous: previous.clone(),
+          pushed_to: pushed_to.clone(),
+        }),
+      },
+      (Some(branch), Some(before), _, Some(head)) if before != head => effects.push(Effect::Commits {
+        repo: after.path.clone(),
+        branch: branch.clone(),
+        before: before.clone(),
+        after: head.clone(),
+        pushed_to: pushed_to.clone(),
+      }),
+      _ => {}
+    }
+
+    let mut added: Vec<String> = after.noted.difference(&self.noted).cloned().collect();
+    if !added.is_empty() {
+      added.sort();
+      effects.push(Effect::MappingNotes {
+        repo: after.path.clone(),
+        crate_name: crate_name.to_string(),
+        commits: added,
+        pushed_to,
+      });
+    }
+
+    effects
+  }
+}
+
+/// Snapshots taken around a split or sync, turned into effects once it finishes
+#[derive(Debug)]
+pub struct OperationWatch {
+  crate_name: String,
+  workspace_root: PathBuf,
+  target_repo_path: PathBuf,
+  branch: String,
+  remote_url: Option<String>,
+  mono: RepoSnapshot,
+  target: RepoSnapshot,
+}
+
+impl OperationWatch {
+  /// Snapshot the monorepo and the split's target repository
+  pub fn begin(
+    workspace_root: &Path,
+    crate_name: &str,
+    target_repo_path: &Path,
+    branch: &str,
+    remote_url: Option<&str>,
+  ) -> Self {
+    Self {
+      crate_name: crate_name.to_string(),
+      workspace_root: workspace_root.to_path_buf(),
+      target_repo_path: target_repo_path.to_path_buf(),
+      branch: branch.to_string(),
+      remote_url: remote_url.filter(|url| !url.is_empty()).map(str::to_string),
+      mono: RepoSnapshot::capture(workspace_root, None, crate_name),
+      target: RepoSnapshot::capture(target_repo_path, Some(branch), crate_name),
+    }
+  }
+
+  /// Compare against the state after the operation
+  ///
+  /// Only the split repo and PR branches are ever pushed; monorepo history and notes stay local.
+  pub fn finish(self) -> Vec<Effect> {
+    let pushed = self.remote_url.as_deref().filter(|url| !utils::is_local_path(url));
+
+    // A newly created split repo records the URL it was pushed to; existing ones push to origin
+    let target = RepoSnapshot::capture(&self.target_repo_path, Some(&self.branch), &self.crate_name);
+    let target_push = if self.target.existed {
+      pushed.map(|_| "origin")
+    } else {
+      pushed
+    };
+    let mut effects = self.target.effects_to(&target, &self.crate_name, target_push);
+
+    let mono = RepoSnapshot::capture(&self.workspace_root, None, &self.crate_name);
+    for mut effect in self.mono.effects_to(&mono, &self.crate_name, None) {
+      match &mut effect {
+        Effect::Branch { pushed_to, .. } => *pushed_to = pushed.map(|_| "origin".to_string()),
+        Effect::Commits { branch, pushed_to, .. } if self.mono.branch.as_ref() != Some(branch) => {
+          *pushed_to = pushed.map(|_| "origin".to_string())
+        }
+        _ => {}
+      }
+      effects.push(effect);
+    }
+
+    effects
+  }
+}
+
+/// Snapshot of a file before it is rewritten
+pub fn snapshot_file(path: &Path) -> RailResult<Effect> {
+  let before = if path.exists() {
+    Some(std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?)
+  } else {
+    None
+  };
+  Ok(Effect::File {
+    path: std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()),
+    before,
+  })
+}
+
+/// Directory holding applied-plan records
+fn applied_dir(workspace_root: &Path) -> RailResult<PathBuf> {
+  let dir = journal::rail_dir(workspace_root)?.join("applied");
+  std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+  Ok(dir)
+}
+
+/// Whether `ancestor` is reachable from `descendant`
+pub(crate) fn is_ancestor(repo: &Path, ancestor: &str, descendant: &str) -> RailResult<bool> {
+  let status = Command::new("git")
+    .current_dir(repo)
+    .args(["merge-base", "--is-ancestor", ancestor, descendant])
+    .status()
+    .context("Failed to run git merge-base")?;
+  Ok(status.success())
+}
+
+fn short(sha: &str) -> &str {
+  &sha[..7.min(sha.len())]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tempfile::TempDir;
+
+  fn git(repo: &Path, args: &[&str]) -> String {
+    let output = Command::new("git").current_dir(repo).args(args).output().unwrap();
+    assert!(
+      output.status.success(),
+      "git {:?}: {}",
+      args,
+      String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+  }
+
+  fn init_repo() -> TempDir {
+    let temp = TempDir::new().unwrap();
+    git(temp.path(), &["init", "--quiet", "--initial-branch=main"]);
+    git(temp.path(), &["config", "user.name", "Test"]);
+    git(temp.path(), &["config", "user.email", "test@example.com"]);
+    commit(temp.path(), "base");
+    temp
+  }
+
+  fn commit(repo: &Path, message: &str) -> String {
+    git(repo, &["commit", "--quiet", "--allow-empty", "-m", message]);
+    git(repo, &["rev-parse", "HEAD"])
+  }
+
+  #[test]
+  fn test_effects_for_new_commits_and_notes() {
+    let repo = init_repo();
+    let before = RepoSnapshot::capture(repo.path(), None, "my-crate");
+
+    let head = commit(repo.path(), "synced");
+    git(
+      repo.path(),
+      &["notes", "--ref", "refs/notes/rail/my-crate", "add", "-m", "x", &head],
+    );
+    let after = RepoSnapshot::capture(repo.path(), None, "my-crate");
+
+    let effects = before.effects_to(&after, "my-crate", None);
+    assert_eq!(effects.len(), 2);
+    assert!(matches!(&effects[0], Effect::Commits { branch, after, .. } if branch == "main" && *after == head));
+    assert!(matches!(&effects[1], Effect::MappingNotes { commits, .. } if *commits == vec![head.clone()]));
+  }
+
+  #[test]
+  fn test_effects_for_new_branch() {
+    let repo = init_repo();
+    let before = RepoSnapshot::capture(repo.path(), None, "my-crate");
+
+    git(repo.path(), &["checkout", "--quiet", "-b", "rail/sync/my-crate"]);
+    commit(repo.path(), "from remote");
+    let after = RepoSnapshot::capture(repo.path(), None, "my-crate");
+
+    let effects = before.effects_to(&after, "my-crate", None);
+    assert_eq!(
+      effects,
+      vec![Effect::Branch {
+        repo: after.path.clone(),
+        name: "rail/sync/my-crate".to_string(),
+        previous: "main".to_string(),
+        pushed_to: None,
+      }]
+    );
+  }
+
+  #[test]
+  fn test_effects_for_reused_branch() {
+    let repo = init_repo();
+    git(repo.path(), &["branch", "rail/sync/my-crate"]);
+    let before_tip = git(repo.path(), &["rev-parse", "HEAD"]);
+    let before = RepoSnapshot::capture(repo.path(), None, "my-crate");
+
+    git(repo.path(), &["checkout", "--quiet", "rail/sync/my-crate"]);
+    let head = commit(repo.path(), "from remote");
+    let after = RepoSnapshot::capture(repo.path(), None, "my-crate");
+
+    let effects = before.effects_to(&after, "my-crate", None);
+    assert_eq!(
+      effects,
+      vec![Effect::Commits {
+        repo: after.path.clone(),
+        branch: "rail/sync/my-crate".to_string(),
+        before: before_tip,
+        after: head,
+        pushed_to: None,
+      }]
+    );
+  }
+
+  #[test]
+  fn test_undo_resets_unpushed_and_reverts_pushed_commits() {
+    let repo = init_repo();
+    let base = git(repo.path(), &["rev-parse", "HEAD"]);
+    let head = commit(repo.path(), "synced");
+    let id = PlanId::from_contents(b"plan");
+
+    let commits = |pushed_to: Option<&str>| Effect::Commits {
+      repo: repo.path().to_path_buf(),
+      branch: "main".to_string(),
+      before: base.clone(),
+      after: head.clone(),
+      pushed_to: pushed_to.map(str::to_string),
+    };
+
+    AppliedPlan::record(repo.path(), &id, OperationType::Sync, None, vec![commits(None)]).unwrap();
+    let record = AppliedPlan::find(repo.path(), &id.full()[..8]).unwrap();
+    let plan = record.undo_plan(repo.path(), &GitAuth::default()).unwrap();
+    assert!(matches!(&plan.operations[..], [Operation::ResetBranch { to, .. }] if *to == base));
+    assert!(!plan.metadata.is_destructive);
+
+    AppliedPlan::record(
+      repo.path(),
+      &id,
+      OperationType::Sync,
+      None,
+      vec![commits(Some("origin"))],
+    )
+    .unwrap();
+    let record = AppliedPlan::find(repo.path(), &id.full()[..8]).unwrap();
+    assert_eq!(record.applications.len(), 2);
+    let plan = record.undo_plan(repo.path(), &GitAuth::default()).unwrap();
+    assert!(matches!(
+      &plan.operations[..],
+      [Operation::RevertCommits { push_to: Some(_), .. }]
+    ));
+    assert!(plan.metadata.is_destructive);
+
+    record.pop().unwrap();
+    let record = AppliedPlan::find(repo.path(), &id.full()[..8]).unwrap();
+    record.pop().unwrap();
+    assert!(AppliedPlan::find(repo.path(), &id.full()[..8]).is_err());
+  }
+
+  #[test]
+  fn test_undo_pushes_to_the_split_remote_with_split_auth() {
+    let repo = init_repo();
+    let split = TempDir::new().unwrap();
+    let id = PlanId::from_contents(b"split plan");
+    let branch = |repo: &Path| Effect::Branch {
+      repo: repo.to_path_buf(),
+      name: "rail/sync/my-crate".to_string(),
+      previous: "main".to_string(),
+      pushed_to: Some("origin".to_string()),
+    };
+    AppliedPlan::record(
+      repo.path(),
+      &id,
+      OperationType::Sync,
+      Some("my-crate".to_string()),
+      vec![branch(split.path()), branch(repo.path())],
+    )
+    .unwrap();
+
+    let split_auth = GitAuth {
+      method: crate::core::config::AuthMethod::Https,
+      token_env: Some("SPLIT_TOKEN".to_string()),
+      ..GitAuth::default()
+    };
+    let record = AppliedPlan::find(repo.path(), &id.full()[..8]).unwrap();
+    let plan = record.undo_plan(repo.path(), &split_auth).unwrap();
+    let auths: Vec<&GitAuth> = plan
+      .operations
+      .iter()
+      .map(|op| match op {
+        Operation::DeleteBranch { auth, .. } => auth,
+        other => panic!("unexpected {:?}", other),
+      })
+      .collect();
+    // Effects are undone newest first: the monorepo's branch, then the split's
+    assert_eq!(auths, [&GitAuth::default(), &split_auth]);
+  }
+}
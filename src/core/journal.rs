@@ -0,0 +1,395 @@
+//! Crash-safe sync journal and per-split lock
+//!
+//! A sync creates commits one at a time and only saves mappings at the end. If the process
+//! dies in between, the created commits have no mappings and the next run would replay them
+//! again. The journal closes that gap: every commit pair is appended (atomically) as soon as
+//! the commit exists, so an interrupted run can be finished with `--resume` or rolled back
+//! with `--abort`.
+//!
+//! State lives in the monorepo's git directory:
+//!
+//! ```text
+//! .git/rail/sync-{crate}.json   # journal of the in-flight sync
+//! .git/rail/sync-{crate}.lock   # locked (flock) while a sync of the split runs
+//! ```
+
+use crate::core::error::{RailError, RailResult, ResultExt};
+use crate::core::mapping::MappingStore;
+use crate::core::vcs::SystemGit;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Which repository a journaled sync writes commits to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalDirection {
+  /// Mono → remote: commits land in the split repo
+  ToRemote,
+  /// Remote → mono: commits land in the monorepo
+  FromRemote,
+}
+
+impl JournalDirection {
+  /// CLI flag selecting this direction
+  pub fn flag(&self) -> &'static str {
+    match self {
+      JournalDirection::ToRemote => "--to-remote",
+      JournalDirection::FromRemote => "--from-remote",
+    }
+  }
+}
+
+/// A commit pair applied by the journaled sync
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+  pub mono: String,
+  pub remote: String,
+}
+
+/// Journal of an in-flight sync
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncJournal {
+  pub crate_name: String,
+  pub direction: JournalDirection,
+  /// Split repository the sync runs against
+  pub target_repo_path: PathBuf,
+  /// HEAD of the repository being written to before the sync started (what `--abort` restores)
+  pub start_head: String,
+  /// Branch the sync commits to (None: detached HEAD, or a journal from an older version)
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub branch: Option<String>,
+  /// Commit pairs applied so far, in order
+  pub applied: Vec<JournalEntry>,
+  /// Where this journal is stored
+  #[serde(skip)]
+  path: PathBuf,
+}
+
+impl SyncJournal {
+  /// Start a new journal for the split
+  pub fn begin(
+    workspace_root: &Path,
+    crate_name: &str,
+    direction: JournalDirection,
+    target_repo_path: &Path,
+    start_head: &str,
+    branch: &str,
+  ) -> RailResult<Self> {
+    let journal = Self {
+      crate_name: crate_name.to_string(),
+      direction,
+      target_repo_path: target_repo_path.to_path_buf(),
+      start_head: start_head.to_string(),
+      branch: (branch != "HEAD").then(|| branch.to_string()),
+      applied: Vec::new(),
+      path: state_path(workspace_root, crate_name, "json")?,
+    };
+    journal.persist()?;
+    Ok(journal)
+  }
+
+  /// Load the journal of an interrupted sync, if there is one
+  pub fn load(workspace_root: &Path, crate_name: &str) -> RailResult<Option<Self>> {
+    let path = state_path(workspace_root, crate_name, "json")?;
+    if !path.exists() {
+      return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    let mut journal: Self = serde_json::from_str(&content).map_err(|e| {
+      RailError::with_help(
+        format!("Corrupt sync journal at {}: {}", path.display(), e),
+        "Inspect the repositories manually, then delete the journal file",
+      )
+    })?;
+    journal.path = path;
+    Ok(Some(journal))
+  }
+
+  /// Record a commit pair; persisted before returning
+  pub fn record(&mut self, mono_sha: &str, remote_sha: &str) -> RailResult<()> {
+    self.applied.push(JournalEntry {
+      mono: mono_sha.to_string(),
+      remote: remote_sha.to_string(),
+    });
+    self.persist()
+  }
+
+  /// The last commit known to be complete in the repository being written to
+  pub fn last_head(&self) -> &str {
+    self
+      .applied
+      .last()
+      .map(|entry| match self.direction {
+        JournalDirection::ToRemote => entry.remote.as_str(),
+        JournalDirection::FromRemote => entry.mono.as_str(),
+      })
+      .unwrap_or(&self.start_head)
+  }
+
+  /// Path of the repository this sync writes commits to
+  pub fn written_repo<'a>(&'a self, workspace_root: &'a Path) -> &'a Path {
+    match self.direction {
+      JournalDirection::ToRemote => &self.target_repo_path,
+      JournalDirection::FromRemote => workspace_root,
+    }
+  }
+
+  /// Bring the written repository back to the last journaled commit and re-record the mappings
+  ///
+  /// Discards a commit that was created but never journaled. Refuses to run off the sync's
+  /// branch or over uncommitted changes, which would be lost with it.
+  pub fn recover(&self, workspace_root: &Path, mapping_store: &mut MappingStore) -> RailResult<()> {
+    self.rewind(workspace_root, self.last_head(), false)?;
+    for entry in &self.applied {
+      mapping_store.record_mapping(&entry.mono, &entry.remote)?;
+    }
+    Ok(())
+  }
+
+  /// Roll the interrupted sync back and delete the journal
+  ///
+  /// Resets the sync's branch to where it was before the sync and drops any mapping notes the
+  /// sync already saved. Uncommitted changes are only discarded with `discard_changes` (the
+  /// files of a commit the sync stopped on); otherwise they stop the rollback. Returns the
+  /// number of commits discarded.
+  pub fn abort(self, workspace_root: &Path, discard_changes: bool) -> RailResult<usize> {
+    self.rewind(workspace_root, &self.start_head, discard_changes)?;
+
+    let mono_shas: Vec<String> = self.applied.iter().map(|entry| entry.mono.clone()).collect();
+    let mapping_store = MappingStore::new(self.crate_name.clone());
+    mapping_store.remove_notes(workspace_root, &mono_shas)?;
+    if self.target_repo_path.exists() {
+      mapping_store.remove_notes(&self.target_repo_path, &mono_shas)?;
+    }
+
+    let discarded = self.applied.len();
+    self.finish()?;
+    Ok(discarded)
+  }
+
+  /// Reset the sync's branch in the written repository to `commit`
+  fn rewind(&self, workspace_root: &Path, commit: &str, discard_changes: bool) -> RailResult<()> {
+    let repo = self.written_repo(workspace_root);
+    let git = SystemGit::open(repo)?;
+
+    if let Some(ref branch) = self.branch {
+      let current = git.current_branch()?;
+      if current != *branch {
+        return Err(RailError::with_help(
+          format!(
+            "The interrupted sync of '{}' wrote to '{}', but {} has '{}' checked out",
+            self.crate_name,
+            branch,
+            repo.display(),
+            current
+          ),
+          format!("Check out '{}' in {} and run the command again", branch, repo.display()),
+        ));
+      }
+    }
+
+    if !discard_changes {
+      let output = git
+        .git_cmd()
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .context("Failed to run git status")?;
+      let changes = String::from_utf8_lossy(&output.stdout);
+      if !output.status.success() || !changes.trim().is_empty() {
+        return Err(RailError::with_help(
+          format!("{} has uncommitted changes", repo.display()),
+          "Commit or stash them, or discard them if they were left by the interrupted sync, and run the command again",
+        ));
+      }
+    }
+
+    git.reset_hard(commit)
+  }
+
+  /// The sync completed; drop the journal
+  pub fn finish(self) -> RailResult<()> {
+    if self.path.exists() {
+      std::fs::remove_file(&self.path)
+        .with_context(|| format!("Failed to remove sync journal {}", self.path.display()))?;
+    }
+    Ok(())
+  }
+
+  /// Write the journal atomically (temp file + fsync + rename)
+  fn persist(&self) -> RailResult<()> {
+    let tmp = self.path.with_extension("json.tmp");
+    let content = serde_json::to_vec_pretty(self)
+      .map_err(|e| RailError::message(format!("Failed to serialize sync journal: {}", e)))?;
+
+    let mut file = std::fs::File::create(&tmp).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    file.write_all(&content)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, &self.path).with_context(|| format!("Failed to write {}", self.path.display()))?;
+    Ok(())
+  }
+}
+
+/// Exclusive lock against concurrent syncs of the same split, released on drop
+///
+/// An OS advisory lock on the lock file, so the kernel releases it when its owner exits, even
+/// if the sync was killed before `Drop` ran. The file itself stays behind and holds the pid of
+/// the last owner, for error messages only.
+#[derive(Debug)]
+pub struct SyncLock {
+  _file: File,
+}
+
+impl SyncLock {
+  /// Take the lock for a split, failing if a running sync holds it
+  pub fn acquire(workspace_root: &Path, crate_name: &str) -> RailResult<Self> {
+    let path = state_path(workspace_root, crate_name, "lock")?;
+    let mut file = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .create(true)
+      .truncate(false)
+      .open(&path)
+      .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    match file.try_lock() {
+      Ok(()) => {}
+      Err(TryLockError::WouldBlock) => {
+        let holder = std::fs::read_to_string(&path).unwrap_or_default();
+        return Err(RailError::with_help(
+          format!(
+            "Another sync of '{}' is in progress (pid {})",
+            crate_name,
+            holder.trim()
+          ),
+          "Wait for it to finish",
+        ));
+      }
+      Err(TryLockError::Error(e)) => {
+        return Err(RailError::message(format!("Failed to lock {}: {}", path.display(), e)));
+      }
+    }
+
+    file.set_len(0)?;
+    writeln!(file, "{}", std::process::id())?;
+    Ok(Self { _file: file })
+  }
+
+  /// Whether a running sync of the split holds the lock right now
+  pub fn is_held(workspace_root: &Path, crate_name: &str) -> RailResult<bool> {
+    let path = state_path(workspace_root, crate_name, "lock")?;
+    let file = match File::open(&path) {
+      Ok(file) => file,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+      Err(e) => return Err(RailError::message(format!("Failed to open {}: {}", path.display(), e))),
+    };
+    match file.try_lock_shared() {
+      Ok(()) => Ok(false),
+      Err(TryLockError::WouldBlock) => Ok(true),
+      Err(TryLockError::Error(e)) => Err(RailError::message(format!("Failed to lock {}: {}", path.display(), e))),
+    }
+  }
+}
+
+/// Directory holding cargo-rail state (`.git/rail/`), created if missing
+pub(crate) fn rail_dir(workspace_root: &Path) -> RailResult<PathBuf> {
+  let dir = SystemGit::open(workspace_root)?.git_dir()?.join("rail");
+  std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+  Ok(dir)
+}
+
+/// Path of a per-split state file under `.git/rail/`
+fn state_path(workspace_root: &Path, crate_name: &str, extension: &str) -> RailResult<PathBuf> {
+  Ok(rail_dir(workspace_root)?.join(format!("sync-{}.{}", crate_name, extension)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::process::Command;
+  use tempfile::TempDir;
+
+  fn init_repo() -> TempDir {
+    let temp = TempDir::new().unwrap();
+    Command::new("git")
+      .current_dir(temp.path())
+      .args(["init", "--quiet"])
+      .status()
+      .unwrap();
+    temp
+  }
+
+  #[test]
+  fn test_journal_roundtrip() {
+    let repo = init_repo();
+    let mut journal = SyncJournal::begin(
+      repo.path(),
+      "my-crate",
+      JournalDirection::ToRemote,
+      Path::new("/split"),
+      "base",
+      "main",
+    )
+    .unwrap();
+    assert_eq!(journal.last_head(), "base");
+
+    journal.record("m1", "r1").unwrap();
+    journal.record("m2", "r2").unwrap();
+
+    let loaded = SyncJournal::load(repo.path(), "my-crate").unwrap().unwrap();
+    assert_eq!(loaded.direction, JournalDirection::ToRemote);
+    assert_eq!(loaded.applied.len(), 2);
+    assert_eq!(loaded.last_head(), "r2");
+
+    loaded.finish().unwrap();
+    assert!(SyncJournal::load(repo.path(), "my-crate").unwrap().is_none());
+  }
+
+  #[test]
+  fn test_last_head_follows_direction() {
+    let repo = init_repo();
+    let mut journal = SyncJournal::begin(
+      repo.path(),
+      "my-crate",
+      JournalDirection::FromRemote,
+      Path::new("/split"),
+      "base",
+      "main",
+    )
+    .unwrap();
+    journal.record("m1", "r1").unwrap();
+    assert_eq!(journal.last_head(), "m1");
+  }
+
+  #[test]
+  fn test_lock_is_exclusive_and_released_on_drop() {
+    let repo = init_repo();
+    let lock = SyncLock::acquire(repo.path(), "my-crate").unwrap();
+    assert!(SyncLock::acquire(repo.path(), "my-crate").is_err());
+    // Other splits are independent
+    let _other = SyncLock::acquire(repo.path(), "other-crate").unwrap();
+
+    drop(lock);
+    assert!(SyncLock::acquire(repo.path(), "my-crate").is_ok());
+  }
+
+  #[test]
+  fn test_lock_left_by_a_dead_process_is_free() {
+    let repo = init_repo();
+    // A sync that was killed leaves the file behind, but not the OS lock
+    let path = state_path(repo.path(), "my-crate", "lock").unwrap();
+    std::fs::write(&path, "999999\n").unwrap();
+    assert!(!SyncLock::is_held(repo.path(), "my-crate").unwrap());
+
+    let lock = SyncLock::acquire(repo.path(), "my-crate").unwrap();
+    assert!(SyncLock::is_held(repo.path(), "my-crate").unwrap());
+    assert_eq!(
+      std::fs::read_to_string(&path).unwrap().trim(),
+      std::process::id().to_string()
+    );
+    drop(lock);
+    assert!(!SyncLock::is_held(repo.path(), "my-crate").unwrap());
+  }
+}
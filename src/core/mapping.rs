@@ -1,6 +1,7 @@
+use crate::core::auth::GitAuth;
 use crate::core::error::{GitError, RailError, RailResult, ResultExt};
 use crate::ui::progress::FileProgress;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 /// Commit mapping store using git-notes
@@ -143,11 +144,26 @@ impl MappingStore {
     Ok(())
   }
 
+  /// Move the mapping of a rewritten commit (e.g. a rebased PR branch commit) to its new SHA
+  ///
+  /// Only the in-memory mapping changes; drop the old note with `remove_notes`.
+  pub fn repoint_mapping(&mut self, old_sha: &str, new_sha: &str) -> RailResult<()> {
+    if let Some(to) = self.mappings.remove(old_sha) {
+      self.record_mapping(new_sha, &to)?;
+    }
+    Ok(())
+  }
+
   /// Get the mapped commit SHA if it exists
   pub fn get_mapping(&self, sha: &str) -> RailResult<Option<String>> {
     Ok(self.mappings.get(sha).cloned())
   }
 
+  /// The commit mapped to `sha`, if any (reverse direction)
+  pub fn get_reverse_mapping(&self, sha: &str) -> Option<String> {
+    self.reverse_mappings.get(sha).cloned()
+  }
+
   /// Check if a commit has been mapped (forward direction)
   pub fn has_mapping(&self, sha: &str) -> bool {
     self.mappings.contains_key(sha)
@@ -159,6 +175,54 @@ impl MappingStore {
     self.reverse_mappings.contains_key(sha)
   }
 
+  /// Delete the notes for the given source commits from a repository
+  ///
+  /// Used when rolling back a sync whose mappings may already have been saved.
+  pub fn remove_notes(&self, repo_path: &Path, from_shas: &[String]) -> RailResult<()> {
+    use std::process::Command;
+
+    if from_shas.is_empty() {
+      return Ok(());
+    }
+
+    let notes_ref = format!("refs/notes/rail/{}", self.crate_name);
+    let output = Command::new("git")
+      .current_dir(repo_path)
+      .args(["notes", "--ref", &notes_ref, "remove", "--ignore-missing"])
+      .args(from_shas)
+      .output()
+      .context("Failed to remove git notes")?;
+
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(RailError::Git(GitError::CommandFailed {
+        command: "git notes remove".to_string(),
+        stderr: stderr.to_string(),
+      }));
+    }
+
+    Ok(())
+  }
+
+  /// Commits that carry a mapping note in a repository (empty if the notes ref doesn't exist)
+  pub fn noted_commits(&self, repo_path: &Path) -> HashSet<String> {
+    use std::process::Command;
+
+    let notes_ref = format!("refs/notes/rail/{}", self.crate_name);
+    match Command::new("git")
+      .current_dir(repo_path)
+      .args(["notes", "--ref", &notes_ref, "list"])
+      .output()
+    {
+      Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(str::to_string)
+        .collect(),
+      _ => HashSet::new(),
+    }
+  }
+
   /// Get all mappings
   pub fn all_mappings(&self) -> &HashMap<String, String> {
     &self.mappings
@@ -178,7 +242,7 @@ impl MappingStore {
   }
 
   /// Push git-notes to a remote repository
-  pub fn push_notes(&self, repo_path: &Path, remote: &str) -> RailResult<()> {
+  pub fn push_notes(&self, repo_path: &Path, remote: &str, auth: &GitAuth) -> RailResult<()> {
     use std::process::Command;
 
     // Skip if no mappings exist
@@ -191,7 +255,9 @@ impl MappingStore {
 
     println!("   Pushing git-notes to remote '{}'...", remote);
 
-    let output = Command::new("git")
+    let mut cmd = Command::new("git");
+    auth.apply(&mut cmd);
+    let output = cmd
       .current_dir(repo_path)
       .args(["push", remote, &notes_ref])
       .output()
@@ -210,7 +276,7 @@ impl MappingStore {
   }
 
   /// Fetch git-notes from a remote repository
-  pub fn fetch_notes(&self, repo_path: &Path, remote: &str) -> RailResult<()> {
+  pub fn fetch_notes(&self, repo_path: &Path, remote: &str, auth: &GitAuth) -> RailResult<()> {
     use std::process::Command;
 
     let notes_ref = format!("refs/notes/rail/{}", self.crate_name);
@@ -218,7 +284,9 @@ impl MappingStore {
 
     println!("   Fetching git-notes from remote '{}'...", remote);
 
-    let output = Command::new("git")
+    let mut cmd = Command::new("git");
+    auth.apply(&mut cmd);
+    let output = cmd
       .current_dir(repo_path)
       .args(["fetch", remote, &refspec])
       .output()
@@ -239,7 +307,9 @@ impl MappingStore {
         println!("   🔄 Attempting automatic merge with union strategy...");
 
         // Fetch to FETCH_HEAD without updating the ref
-        let fetch_output = Command::new("git")
+        let mut cmd = Command::new("git");
+        auth.apply(&mut cmd);
+        let fetch_output = cmd
           .current_dir(repo_path)
           .args(["fetch", remote, &notes_ref])
           .output()
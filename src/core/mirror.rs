@@ -0,0 +1,152 @@
+//! Push fan-out from a split repo to its mirror remotes
+//!
+//! The primary remote is pushed (and imported from) as before; mirrors then get the same
+//! branch and rail notes. A mirror that fails doesn't stop the others, and since every push
+//! sends the full branch, the next split or sync retries any mirror left behind.
+
+use crate::core::auth::GitAuth;
+use crate::core::config::RemoteConfig;
+use crate::core::error::{RailError, RailResult};
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+
+/// Outcome of pushing to one mirror
+#[derive(Debug, Clone, Serialize)]
+pub struct MirrorPush {
+  pub name: String,
+  pub url: String,
+  /// Why the push failed (None: pushed or already up to date)
+  pub error: Option<String>,
+}
+
+/// Push `branch` and the split's rail notes from `repo` to every mirror
+pub fn push_to_mirrors(
+  repo: &Path,
+  mirrors: &[RemoteConfig],
+  branch: &str,
+  crate_name: &str,
+  auth: &GitAuth,
+  workspace_root: &Path,
+) -> Vec<MirrorPush> {
+  let notes_ref = format!("refs/notes/rail/{}", crate_name);
+  let has_notes = Command::new("git")
+    .current_dir(repo)
+    .args(["rev-parse", "--verify", "--quiet", &notes_ref])
+    .output()
+    .is_ok_and(|o| o.status.success());
+
+  let mut refspecs = vec![format!("refs/heads/{0}:refs/heads/{0}", branch)];
+  if has_notes {
+    refspecs.push(format!("{0}:{0}", notes_ref));
+  }
+
+  mirrors
+    .iter()
+    .map(|mirror| {
+      println!("   Pushing to mirror '{}'...", mirror.name);
+      let mut cmd = Command::new("git");
+      auth.for_remote(mirror, workspace_root).apply(&mut cmd);
+      let error = match cmd
+        .current_dir(repo)
+        .arg("push")
+        .arg(&mirror.url)
+        .args(&refspecs)
+        .output()
+      {
+        Ok(output) if output.status.success() => None,
+        Ok(output) => Some(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+        Err(err) => Some(err.to_string()),
+      };
+      MirrorPush {
+        name: mirror.name.clone(),
+        url: mirror.url.clone(),
+        error,
+      }
+    })
+    .collect()
+}
+
+/// Print each mirror's outcome; an error naming the mirrors that failed
+pub fn report(pushes: &[MirrorPush]) -> RailResult<()> {
+  for push in pushes {
+    match push.error {
+      None => println!("   ✅ Mirror {} ({})", push.name, push.url),
+      Some(ref error) => println!(
+        "   ❌ Mirror {} ({}): {}",
+        push.name,
+        push.url,
+        error.lines().last().unwrap_or_default()
+      ),
+    }
+  }
+
+  let failed: Vec<&str> = pushes
+    .iter()
+    .filter(|p| p.error.is_some())
+    .map(|p| p.name.as_str())
+    .collect();
+  if failed.is_empty() {
+    return Ok(());
+  }
+
+  Err(RailError::with_help(
+    format!(
+      "Push to {} of {} mirror(s) failed: {}",
+      failed.len(),
+      pushes.len(),
+      failed.join(", ")
+    ),
+    "The primary remote is up to date; sync again to retry the mirrors",
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tempfile::TempDir;
+
+  fn git(cwd: &Path, args: &[&str]) {
+    let output = Command::new("git").current_dir(cwd).args(args).output().unwrap();
+    assert!(
+      output.status.success(),
+      "git {:?}: {}",
+      args,
+      String::from_utf8_lossy(&output.stderr)
+    );
+  }
+
+  #[test]
+  fn test_push_to_mirrors_reports_each_mirror() {
+    let dir = TempDir::new().unwrap();
+    let repo = dir.path().join("split");
+    std::fs::create_dir(&repo).unwrap();
+    git(&repo, &["init", "-q", "--initial-branch=main"]);
+    git(&repo, &["config", "user.name", "Test User"]);
+    git(&repo, &["config", "user.email", "test@example.com"]);
+    git(&repo, &["commit", "-q", "--allow-empty", "-m", "init"]);
+    git(dir.path(), &["init", "-q", "--bare", "good.git"]);
+
+    let mirrors = vec![
+      RemoteConfig {
+        name: "good".to_string(),
+        url: dir.path().join("good.git").display().to_string(),
+        primary: false,
+        known_hosts: None,
+      },
+      RemoteConfig {
+        name: "gone".to_string(),
+        url: dir.path().join("missing.git").display().to_string(),
+        primary: false,
+        known_hosts: None,
+      },
+    ];
+
+    let pushes = push_to_mirrors(&repo, &mirrors, "main", "my-crate", &GitAuth::default(), dir.path());
+    assert!(pushes[0].error.is_none());
+    assert!(pushes[1].error.is_some());
+
+    let err = report(&pushes).unwrap_err();
+    assert!(err.to_string().contains("1 of 2 mirror(s) failed: gone"), "{}", err);
+  }
+}
@@ -2,26 +2,46 @@
 //!
 //! This module contains the fundamental building blocks for all cargo-rail functionality:
 //!
+//! - **auth**: SSH, HTTPS token or credential-helper authentication for split remotes
+//! - **cache**: Managed clones of split repos (`$XDG_CACHE_HOME/cargo-rail/`)
+//! - **closure**: Unpublished workspace dependency resolution for splits
 //! - **config**: Rail configuration (rail.toml) parsing and validation
 //! - **context**: Unified workspace context for efficient data sharing across operations
 //! - **error**: Comprehensive error types with contextual help messages
 //! - **executor**: Plan execution engine for deterministic operations
+//! - **history**: Record of applied plans, used by `cargo rail undo` (`.git/rail/applied/`)
+//! - **journal**: Crash-safe sync journal and per-split lock (`.git/rail/`)
 //! - **mapping**: Git commit mapping storage for split/sync operations
+//! - **mirror**: Push fan-out to a split's mirror remotes
 //! - **plan**: Operation planning and serialization
+//! - **retire**: Stopping a split for good: archival README, archived notes, rail.toml cleanup
+//! - **resolve**: Conflicts of a sync stopped mid-commit, settled by `cargo rail resolve`
 //! - **security**: Security validation for remotes, SSH, and protected branches
 //! - **split**: Split monorepo crates to separate repositories
 //! - **sync**: Bidirectional synchronization between monorepo and split repos
+//! - **trust**: Inbound trust policy for imported commits, and the quarantine of those failing it
 //! - **conflict**: Conflict detection and resolution strategies
+//! - **verify**: Standalone build verification of split output before pushing
 //! - **vcs**: Git operations abstraction (SystemGit)
 
+pub mod auth;
+pub mod cache;
+pub mod closure;
 pub mod config;
 pub mod conflict;
 pub mod context;
 pub mod error;
 pub mod executor;
+pub mod history;
+pub mod journal;
 pub mod mapping;
+pub mod mirror;
 pub mod plan;
+pub mod resolve;
+pub mod retire;
 pub mod security;
 pub mod split;
 pub mod sync;
+pub mod trust;
 pub mod vcs;
+pub mod verify;
@@ -12,7 +12,7 @@
 //! - **Idempotency**: Same input → same plan → same result
 //! - **Auditability**: Plans are JSON-serializable for logging/review
 //! - **Caching**: Plans can be hashed and cached
-//! - **Rollback**: Plans can be reversed (future)
+//! - **Rollback**: Applied plans are recorded and can be reversed (`cargo rail undo`)
 //!
 //! # Architecture
 //!
@@ -41,7 +41,10 @@
 //! }
 //! ```
 
+use crate::core::auth::GitAuth;
+use crate::core::config::RemoteConfig;
 use crate::core::error::RailResult;
+use crate::utils;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -60,6 +63,11 @@ impl PlanId {
     Self(format!("{:x}", result))
   }
 
+  /// Get the full ID
+  pub fn full(&self) -> &str {
+    &self.0
+  }
+
   /// Get the short ID (first 12 characters)
   pub fn short(&self) -> &str {
     &self.0[..12.min(self.0.len())]
@@ -80,7 +88,13 @@ pub enum Operation {
   InitRepo { path: String },
 
   /// Clone a repository
-  Clone { url: String, path: String },
+  Clone {
+    url: String,
+    path: String,
+    /// How git authenticates to the remote (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
+  },
 
   /// Create a commit
   CreateCommit { message: String, files: Vec<String> },
@@ -90,10 +104,19 @@ pub enum Operation {
     remote: String,
     branch: String,
     force: bool,
+    /// How git authenticates to the remote (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
   },
 
   /// Pull from remote
-  Pull { remote: String, branch: String },
+  Pull {
+    remote: String,
+    branch: String,
+    /// How git authenticates to the remote (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
+  },
 
   /// Transform a file
   Transform { path: String, transform_type: String },
@@ -134,9 +157,30 @@ pub enum Operation {
     crate_name: String,
     crate_paths: Vec<String>,
     mode: String,
+    /// Combined-mode layout ("Standalone" or "Workspace")
+    #[serde(default)]
+    workspace_mode: String,
     target_repo_path: String,
     branch: String,
     remote_url: Option<String>,
+    /// Subdirectory of the target repo holding the split (repo root if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target_dir: Option<String>,
+    /// Commit a Cargo.lock derived from the mono lockfile
+    #[serde(default)]
+    lockfile: bool,
+    /// Verify the split builds standalone before pushing
+    #[serde(default)]
+    verify_build: bool,
+    /// How unpublished workspace dependencies are provided to the split repo
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dependency_sources: Vec<DependencySource>,
+    /// Remotes pushed to after `remote_url`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mirrors: Vec<RemoteConfig>,
+    /// How git authenticates to the remotes (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
   },
 
   /// Execute a sync workflow
@@ -145,12 +189,199 @@ pub enum Operation {
     crate_name: String,
     crate_paths: Vec<String>,
     mode: String,
+    /// Combined-mode layout ("Standalone" or "Workspace")
+    #[serde(default)]
+    workspace_mode: String,
     target_repo_path: String,
     branch: String,
     remote_url: String,
     direction: String,
     conflict_strategy: String,
+    /// Finish an interrupted sync recorded in the journal
+    #[serde(default)]
+    resume: bool,
+    /// Subdirectory of the target repo holding the split (repo root if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target_dir: Option<String>,
+    /// Commit a Cargo.lock derived from the mono lockfile
+    #[serde(default)]
+    lockfile: bool,
+    /// Verify the split builds standalone before pushing
+    #[serde(default)]
+    verify_build: bool,
+    /// How unpublished workspace dependencies are provided to the split repo
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dependency_sources: Vec<DependencySource>,
+    /// What to do when the remote has commits that were never imported (abort, import, rebase)
+    #[serde(default)]
+    on_diverged: String,
+    /// Git bundle written (to_remote) or read (from_remote) instead of the remote
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bundle: Option<String>,
+    /// Remotes pushed to after `remote_url`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mirrors: Vec<RemoteConfig>,
+    /// How git authenticates to the remotes (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
+  },
+
+  /// Reset a branch to an earlier commit (only if it still points at `from`)
+  ResetBranch {
+    repo: String,
+    branch: String,
+    from: String,
+    to: String,
+  },
+
+  /// Revert the commits in `from..to` on a branch, then push the reverts
+  RevertCommits {
+    repo: String,
+    branch: String,
+    from: String,
+    to: String,
+    push_to: Option<String>,
+    /// Plan being undone (recorded as a trailer on the revert commits)
+    undo_of: String,
+    /// How git authenticates to the remote (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
+  },
+
+  /// Delete a branch, locally and on the remote it was pushed to
+  DeleteBranch {
+    repo: String,
+    name: String,
+    /// Branch to check out if `name` is checked out
+    checkout: String,
+    remote: Option<String>,
+    /// How git authenticates to the remote (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
+  },
+
+  /// Remove mapping notes for the given commits
+  RemoveNotes {
+    repo: String,
+    crate_name: String,
+    commits: Vec<String>,
+    push_to: Option<String>,
+    /// How git authenticates to the remote (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
   },
+
+  /// Restore a file's contents (delete it if `content` is None)
+  RestoreFile { path: String, content: Option<String> },
+
+  /// Delete a tag
+  DeleteTag { repo: String, name: String },
+
+  /// Remove a repository created by a split
+  RemoveRepo { path: String },
+
+  /// Commit an archival README to a retired split's repo and push it
+  ArchiveSplitRepo {
+    crate_name: String,
+    target_repo_path: String,
+    branch: String,
+    /// Primary remote (not pushed to if it is a local path)
+    remote_url: String,
+    /// README path in the split repo (under `target_dir` if the split has one)
+    readme_path: String,
+    readme: String,
+    message: String,
+    /// Remotes pushed to after `remote_url`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mirrors: Vec<RemoteConfig>,
+    /// How git authenticates to the remotes (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
+  },
+
+  /// Move mapping notes from `refs/notes/rail/{crate}` to `refs/notes/rail-archive/{crate}`
+  ArchiveNotes {
+    repo: String,
+    crate_name: String,
+    /// Remotes whose live notes ref is replaced by `repo`'s archive ref
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    remotes: Vec<RemoteConfig>,
+    /// How git authenticates to the remotes (plain SSH if unset)
+    #[serde(default, skip_serializing_if = "GitAuth::is_default")]
+    auth: GitAuth,
+  },
+
+  /// Rewrite the rail config (`changes` describes the edit)
+  UpdateConfig {
+    path: String,
+    content: String,
+    changes: Vec<String>,
+  },
+}
+
+/// How an unpublished workspace dependency reaches a split repo
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DependencySource {
+  /// Bundled into the split repo at its mono path (path dependencies are kept)
+  Bundled { name: String, path: String },
+
+  /// Pulled from the dependency's own split remote
+  Git { name: String, url: String, branch: String },
+}
+
+impl fmt::Display for DependencySource {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DependencySource::Bundled { name, path } => write!(f, "{} → bundled ({})", name, path),
+      DependencySource::Git { name, url, branch } => write!(f, "{} → git {} ({})", name, url, branch),
+    }
+  }
+}
+
+/// What a sync would replay, computed for dry-runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SyncPreview {
+  /// Mono commits replayed into the split repo, oldest first
+  pub to_remote: Vec<PlannedCommit>,
+  /// Split repo commits replayed into the monorepo, oldest first
+  pub from_remote: Vec<PlannedCommit>,
+}
+
+impl SyncPreview {
+  /// Number of files predicted to conflict
+  pub fn conflict_count(&self) -> usize {
+    self
+      .to_remote
+      .iter()
+      .chain(&self.from_remote)
+      .map(|c| c.conflicts.len())
+      .sum()
+  }
+}
+
+/// A commit a sync would replay
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlannedCommit {
+  pub sha: String,
+  /// First line of the commit message
+  pub summary: String,
+  /// Files written on the receiving side (paths as they land there)
+  pub files: Vec<PlannedFile>,
+  /// Files predicted to conflict (receiving-side paths)
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub conflicts: Vec<String>,
+}
+
+/// A file a replayed commit writes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlannedFile {
+  pub path: String,
+  /// Git change type (A, M, D, ...)
+  pub change: String,
+  /// Whether the content is transformed on the way (Cargo.toml rewriting)
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub transformed: bool,
 }
 
 /// Plan metadata
@@ -183,6 +414,8 @@ pub enum OperationType {
   Sync,
   Release,
   Init,
+  Undo,
+  Retire,
 }
 
 impl fmt::Display for OperationType {
@@ -192,6 +425,8 @@ impl fmt::Display for OperationType {
       OperationType::Sync => write!(f, "sync"),
       OperationType::Release => write!(f, "release"),
       OperationType::Init => write!(f, "init"),
+      OperationType::Undo => write!(f, "undo"),
+      OperationType::Retire => write!(f, "retire"),
     }
   }
 }
@@ -211,6 +446,10 @@ pub struct Plan {
 
   /// Human-readable summary
   pub summary: String,
+
+  /// Commits and predicted conflicts of a sync (dry-run only; not part of the plan ID)
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub sync_preview: Option<SyncPreview>,
 }
 
 impl Plan {
@@ -234,6 +473,7 @@ impl Plan {
       operations,
       checksums: HashMap::new(),
       summary,
+      sync_preview: None,
     }
   }
 
@@ -307,6 +547,43 @@ impl Plan {
       output.push_str(&format!("   {}. {}\n", i + 1, operation_to_string(op)));
     }
 
+    let dependency_sources: Vec<&DependencySource> = self
+      .operations
+      .iter()
+      .flat_map(|op| match op {
+        Operation::ExecuteSplit { dependency_sources, .. } | Operation::ExecuteSync { dependency_sources, .. } => {
+          dependency_sources.as_slice()
+        }
+        _ => &[],
+      })
+      .collect();
+    if !dependency_sources.is_empty() {
+      output.push_str("\n   Unpublished workspace deps:\n");
+      for source in dependency_sources {
+        output.push_str(&format!("     • {}\n", source));
+      }
+    }
+
+    let lockfile = self.operations.iter().any(|op| match op {
+      Operation::ExecuteSplit { lockfile, .. } | Operation::ExecuteSync { lockfile, .. } => *lockfile,
+      _ => false,
+    });
+    if lockfile {
+      output.push_str("\n   Lockfile: Cargo.lock derived from the monorepo lockfile\n");
+    }
+
+    if let Some(ref preview) = self.sync_preview {
+      let strategy = self
+        .operations
+        .iter()
+        .find_map(|op| match op {
+          Operation::ExecuteSync { conflict_strategy, .. } => Some(conflict_strategy.as_str()),
+          _ => None,
+        })
+        .unwrap_or("manual");
+      output.push_str(&preview_to_string(preview, strategy));
+    }
+
     if self.metadata.is_destructive {
       output.push_str("\n⚠️  NOTE: This operation will modify the target repository\n");
       output.push_str("   (Pushes to remote - ensure target is empty or has been backed up)\n");
@@ -334,18 +611,20 @@ impl Plan {
 fn operation_to_string(op: &Operation) -> String {
   match op {
     Operation::InitRepo { path } => format!("Initialize repository at {}", path),
-    Operation::Clone { url, path } => format!("Clone {} to {}", url, path),
+    Operation::Clone { url, path, .. } => format!("Clone {} to {}", url, path),
     Operation::CreateCommit { message, files } => {
       format!("Create commit: {} ({} files)", message, files.len())
     }
-    Operation::Push { remote, branch, force } => {
+    Operation::Push {
+      remote, branch, force, ..
+    } => {
       if *force {
         format!("Force push to {}/{}", remote, branch)
       } else {
         format!("Push to {}/{}", remote, branch)
       }
     }
-    Operation::Pull { remote, branch } => format!("Pull from {}/{}", remote, branch),
+    Operation::Pull { remote, branch, .. } => format!("Pull from {}/{}", remote, branch),
     Operation::Transform { path, transform_type } => format!("Transform {} ({})", path, transform_type),
     Operation::Copy { from, to } => format!("Copy {} → {}", from, to),
     Operation::CreateBranch { name, from } => format!("Create branch {} from {}", name, from),
@@ -362,9 +641,143 @@ fn operation_to_string(op: &Operation) -> String {
     Operation::ExecuteSync {
       crate_name, direction, ..
     } => format!("Sync crate '{}' (direction: {})", crate_name, direction),
+    Operation::ResetBranch { repo, branch, to, .. } => {
+      format!("Reset {} in {} to {}", branch, repo, short_sha(to))
+    }
+    Operation::RevertCommits {
+      repo,
+      branch,
+      from,
+      to,
+      push_to,
+      ..
+    } => {
+      let revert = format!(
+        "Revert {}..{} on {} in {}",
+        short_sha(from),
+        short_sha(to),
+        branch,
+        repo
+      );
+      match push_to {
+        Some(remote) => format!("{} and push to {}/{}", revert, remote, branch),
+        None => revert,
+      }
+    }
+    Operation::DeleteBranch { repo, name, remote, .. } => match remote {
+      Some(remote) => format!("Delete branch {} in {} and on {}", name, repo, remote),
+      None => format!("Delete branch {} in {}", name, repo),
+    },
+    Operation::RemoveNotes {
+      repo,
+      crate_name,
+      commits,
+      push_to,
+      ..
+    } => {
+      let remove = format!(
+        "Remove {} git-notes refs/notes/rail/{} in {}",
+        commits.len(),
+        crate_name,
+        repo
+      );
+      match push_to {
+        Some(remote) => format!("{} and push to {}", remove, remote),
+        None => remove,
+      }
+    }
+    Operation::RestoreFile { path, content } => match content {
+      Some(_) => format!("Restore {}", path),
+      None => format!("Delete {}", path),
+    },
+    Operation::DeleteTag { repo, name } => format!("Delete tag {} in {}", name, repo),
+    Operation::RemoveRepo { path } => format!("Remove repository {}", path),
+    Operation::ArchiveSplitRepo {
+      target_repo_path,
+      branch,
+      remote_url,
+      readme_path,
+      mirrors,
+      ..
+    } => {
+      let commit = format!("Commit archival {} in {}", readme_path, target_repo_path);
+      if utils::is_local_path(remote_url) {
+        commit
+      } else if mirrors.is_empty() {
+        format!("{} and push to {}/{}", commit, remote_url, branch)
+      } else {
+        format!(
+          "{} and push to {}/{} (+{} mirror(s))",
+          commit,
+          remote_url,
+          branch,
+          mirrors.len()
+        )
+      }
+    }
+    Operation::ArchiveNotes {
+      repo,
+      crate_name,
+      remotes,
+      ..
+    } => {
+      let mut line = format!(
+        "Move git-notes refs/notes/rail/{0} to refs/notes/rail-archive/{0} in {1}",
+        crate_name, repo
+      );
+      if !remotes.is_empty() {
+        let names: Vec<&str> = remotes.iter().map(|r| r.name.as_str()).collect();
+        line.push_str(&format!(" and on {}", names.join(", ")));
+      }
+      line
+    }
+    Operation::UpdateConfig { path, changes, .. } => format!("Update {}: {}", path, changes.join(", ")),
   }
 }
 
+/// Render the commits a sync would replay
+fn preview_to_string(preview: &SyncPreview, strategy: &str) -> String {
+  let mut output = String::new();
+
+  for (label, commits) in [
+    ("monorepo → remote", &preview.to_remote),
+    ("remote → monorepo", &preview.from_remote),
+  ] {
+    if commits.is_empty() {
+      continue;
+    }
+    output.push_str(&format!("\n   Commits to replay ({}): {}\n", label, commits.len()));
+    for commit in commits {
+      output.push_str(&format!("     • {} {}\n", short_sha(&commit.sha), commit.summary));
+      for file in &commit.files {
+        let transformed = if file.transformed { " (transformed)" } else { "" };
+        output.push_str(&format!("         {} {}{}\n", file.change, file.path, transformed));
+      }
+      for path in &commit.conflicts {
+        output.push_str(&format!("         ⚠️  conflict: {}\n", path));
+      }
+    }
+  }
+
+  if preview.to_remote.is_empty() && preview.from_remote.is_empty() {
+    output.push_str("\n   Nothing to replay: both sides are in sync\n");
+  }
+
+  let conflicts = preview.conflict_count();
+  if conflicts > 0 {
+    output.push_str(&format!(
+      "\n   Predicted conflicts: {} (strategy: {})\n",
+      conflicts, strategy
+    ));
+  }
+
+  output
+}
+
+fn short_sha(sha: &str) -> &str {
+  &sha[..7.min(sha.len())]
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -402,11 +815,13 @@ mod tests {
     plan.add_operation(Operation::Pull {
       remote: "origin".to_string(),
       branch: "main".to_string(),
+      auth: GitAuth::default(),
     });
     plan.add_operation(Operation::Push {
       remote: "origin".to_string(),
       branch: "main".to_string(),
       force: false,
+      auth: GitAuth::default(),
     });
 
     let output = plan.to_human_readable();
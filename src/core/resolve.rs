@@ -0,0 +1,490 @@
+//! Conflicts of a sync stopped mid-commit, and the resolutions `cargo rail resolve` applies
+//!
+//! With `--strategy manual`, a remote → mono sync that hits conflicts writes the conflict
+//! markers, leaves the commit uncommitted and stops. What it needs to finish that commit is
+//! saved next to the sync journal:
+//!
+//! ```text
+//! .git/rail/conflicts-{crate}.json
+//! ```
+//!
+//! The base/ours/theirs versions of each file are kept as blobs in the monorepo's object
+//! store, so a side can be (re-)picked at any time. Resuming the sync (`cargo rail resolve
+//! --continue`) commits the stopped commit with its mapping, then carries on from the journal.
+
+use crate::core::conflict::{ConflictInfo, ConflictResolver, ConflictStrategy, MergeResult};
+use crate::core::error::{RailError, RailResult, ResultExt};
+use crate::core::journal::rail_dir;
+use crate::core::vcs::CommitInfo;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// How a conflicted file was settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+  /// Monorepo version
+  Ours,
+  /// Remote version
+  Theirs,
+  /// Both versions, line by line
+  Union,
+  /// Edited by hand ($EDITOR or a mergetool)
+  Edited,
+}
+
+impl std::fmt::Display for Resolution {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Resolution::Ours => write!(f, "ours"),
+      Resolution::Theirs => write!(f, "theirs"),
+      Resolution::Union => write!(f, "union"),
+      Resolution::Edited => write!(f, "edited"),
+    }
+  }
+}
+
+/// A file the stopped commit conflicts on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictedFile {
+  /// Monorepo-relative path
+  pub path: PathBuf,
+  pub message: String,
+  /// Blob IDs of the three merge sides
+  pub base: String,
+  pub ours: String,
+  pub theirs: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub resolution: Option<Resolution>,
+}
+
+/// A remote → mono sync stopped on conflicts
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConflictState {
+  pub crate_name: String,
+  /// Remote the sync ran against (what `--continue` resumes with)
+  pub remote_url: String,
+  /// Strategy the sync ran with
+  pub strategy: String,
+  /// Monorepo branch the commit is being applied to
+  pub branch: String,
+  /// Monorepo commit the stopped commit goes on top of
+  pub parent: String,
+  /// Remote commit being replayed
+  pub commit: CommitInfo,
+  pub files: Vec<ConflictedFile>,
+  /// Where this state is stored
+  #[serde(skip)]
+  path: PathBuf,
+}
+
+impl ConflictState {
+  /// Record a stopped commit; the unresolved conflicts' merge sides are written as blobs
+  #[allow(clippy::too_many_arguments)]
+  pub fn save(
+    workspace_root: &Path,
+    crate_name: &str,
+    remote_url: &str,
+    strategy: ConflictStrategy,
+    branch: &str,
+    parent: &str,
+    commit: &CommitInfo,
+    conflicts: &[ConflictInfo],
+  ) -> RailResult<Self> {
+    let mut files = Vec::new();
+    for conflict in conflicts.iter().filter(|c| !c.resolved) {
+      let Some(ref versions) = conflict.versions else {
+        continue;
+      };
+      files.push(ConflictedFile {
+        path: conflict.file_path.clone(),
+        message: conflict.message.clone(),
+        base: write_blob(workspace_root, &versions.base)?,
+        ours: write_blob(workspace_root, &versions.ours)?,
+        theirs: write_blob(workspace_root, &versions.theirs)?,
+        resolution: None,
+      });
+    }
+
+    let state = Self {
+      crate_name: crate_name.to_string(),
+      remote_url: remote_url.to_string(),
+      strategy: format!("{:?}", strategy).to_lowercase(),
+      branch: branch.to_string(),
+      parent: parent.to_string(),
+      commit: commit.clone(),
+      files,
+      path: state_path(workspace_root, crate_name)?,
+    };
+    state.persist()?;
+    Ok(state)
+  }
+
+  /// Load the stopped sync of a split, if there is one
+  pub fn load(workspace_root: &Path, crate_name: &str) -> RailResult<Option<Self>> {
+    let path = state_path(workspace_root, crate_name)?;
+    if !path.exists() {
+      return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    let mut state: Self = serde_json::from_str(&content).map_err(|e| {
+      RailError::with_help(
+        format!("Corrupt conflict state at {}: {}", path.display(), e),
+        format!(
+          "Roll the sync back with `cargo rail sync {} --abort`, or delete the file",
+          crate_name
+        ),
+      )
+    })?;
+    state.path = path;
+    Ok(Some(state))
+  }
+
+  /// All stopped syncs in the workspace
+  pub fn list(workspace_root: &Path) -> RailResult<Vec<Self>> {
+    let mut states = Vec::new();
+    for entry in std::fs::read_dir(rail_dir(workspace_root)?)? {
+      let name = entry?.file_name().to_string_lossy().to_string();
+      if let Some(crate_name) = name.strip_prefix("conflicts-").and_then(|n| n.strip_suffix(".json"))
+        && let Some(state) = Self::load(workspace_root, crate_name)?
+      {
+        states.push(state);
+      }
+    }
+    states.sort_by(|a, b| a.crate_name.cmp(&b.crate_name));
+    Ok(states)
+  }
+
+  /// Files that still contain conflict markers
+  pub fn unresolved(&self, workspace_root: &Path) -> RailResult<Vec<&ConflictedFile>> {
+    let mut unresolved = Vec::new();
+    for file in &self.files {
+      if has_conflict_markers(&workspace_root.join(&file.path))? {
+        unresolved.push(file);
+      }
+    }
+    Ok(unresolved)
+  }
+
+  /// Settle a file with one side (or both); rewrites the worktree file from the stored blobs
+  pub fn pick(&mut self, workspace_root: &Path, path: &Path, resolution: Resolution) -> RailResult<()> {
+    let file = self.file(path)?;
+    let target = workspace_root.join(&file.path);
+
+    match resolution {
+      Resolution::Ours => std::fs::write(&target, read_blob(workspace_root, &file.ours)?)?,
+      Resolution::Theirs => std::fs::write(&target, read_blob(workspace_root, &file.theirs)?)?,
+      Resolution::Union => {
+        std::fs::write(&target, read_blob(workspace_root, &file.ours)?)?;
+        let scratch = scratch_dir(workspace_root)?;
+        let base = read_blob(workspace_root, &file.base)?;
+        let theirs = read_blob(workspace_root, &file.theirs)?;
+        let merged =
+          ConflictResolver::new(ConflictStrategy::Union, scratch.clone()).resolve_file(&target, &base, &theirs);
+        let _ = std::fs::remove_dir_all(&scratch);
+        if let MergeResult::Failed(msg) = merged? {
+          return Err(RailError::message(format!(
+            "Union merge of {} failed: {}",
+            file.path.display(),
+            msg
+          )));
+        }
+      }
+      Resolution::Edited => {
+        return Err(RailError::message("Edited files are resolved with `edit`, not picked"));
+      }
+    }
+
+    self.mark(path, resolution)
+  }
+
+  /// Open a file in `$VISUAL`/`$EDITOR`; resolved once the markers are gone
+  pub fn edit(&mut self, workspace_root: &Path, path: &Path) -> RailResult<bool> {
+    let target = workspace_root.join(&self.file(path)?.path);
+    let editor = std::env::var("VISUAL")
+      .or_else(|_| std::env::var("EDITOR"))
+      .unwrap_or_else(|_| "vi".to_string());
+
+    let status = Command::new("sh")
+      .args(["-c", &format!("{} \"$@\"", editor), "editor"])
+      .arg(&target)
+      .status()
+      .with_context(|| format!("Failed to run editor '{}'", editor))?;
+    if !status.success() {
+      return Err(RailError::message(format!(
+        "Editor '{}' exited with {}",
+        editor, status
+      )));
+    }
+
+    self.settle_edit(workspace_root, path)
+  }
+
+  /// Run a git-configured mergetool (`mergetool.<name>.cmd`) on a file
+  ///
+  /// The command sees git's usual `$BASE`, `$LOCAL`, `$REMOTE` and `$MERGED` variables.
+  pub fn mergetool(&mut self, workspace_root: &Path, path: &Path, tool: Option<&str>) -> RailResult<bool> {
+    let tool = match tool {
+      Some(tool) => tool.to_string(),
+      None => git_config(workspace_root, "merge.tool")?.ok_or_else(|| {
+        RailError::with_help(
+          "No mergetool configured",
+          "Pass one with --tool <name>, or set it: git config merge.tool <name>",
+        )
+      })?,
+    };
+    let cmd = git_config(workspace_root, &format!("mergetool.{}.cmd", tool))?.ok_or_else(|| {
+      RailError::with_help(
+        format!("Mergetool '{}' has no command configured", tool),
+        format!(
+          "Set one, e.g.: git config mergetool.{}.cmd '{} \"$LOCAL\" \"$BASE\" \"$REMOTE\" -o \"$MERGED\"'",
+          tool, tool
+        ),
+      )
+    })?;
+
+    let file = self.file(path)?.clone();
+    let scratch = scratch_dir(workspace_root)?;
+    let stem = file.path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let sides = [("BASE", &file.base), ("LOCAL", &file.ours), ("REMOTE", &file.theirs)];
+
+    let mut command = Command::new("sh");
+    command
+      .current_dir(workspace_root)
+      .args(["-c", &cmd])
+      .env("MERGED", workspace_root.join(&file.path));
+    for (name, blob) in sides {
+      let side_path = scratch.join(format!("{}.{}", name, stem));
+      std::fs::write(&side_path, read_blob(workspace_root, blob)?)?;
+      command.env(name, side_path);
+    }
+
+    let status = command
+      .status()
+      .with_context(|| format!("Failed to run mergetool '{}'", tool))?;
+    let _ = std::fs::remove_dir_all(&scratch);
+    if !status.success() {
+      return Err(RailError::message(format!(
+        "Mergetool '{}' exited with {}",
+        tool, status
+      )));
+    }
+
+    self.settle_edit(workspace_root, path)
+  }
+
+  /// Write the state atomically (temp file + rename)
+  pub fn persist(&self) -> RailResult<()> {
+    let tmp = self.path.with_extension("json.tmp");
+    let content = serde_json::to_vec_pretty(self)
+      .map_err(|e| RailError::message(format!("Failed to serialize conflict state: {}", e)))?;
+    std::fs::write(&tmp, content).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, &self.path).with_context(|| format!("Failed to write {}", self.path.display()))?;
+    Ok(())
+  }
+
+  /// The stopped commit was committed (or rolled back); drop the state
+  pub fn finish(self) -> RailResult<()> {
+    if self.path.exists() {
+      std::fs::remove_file(&self.path)
+        .with_context(|| format!("Failed to remove conflict state {}", self.path.display()))?;
+    }
+    Ok(())
+  }
+
+  fn file(&self, path: &Path) -> RailResult<&ConflictedFile> {
+    self.files.iter().find(|f| f.path == path).ok_or_else(|| {
+      RailError::with_help(
+        format!(
+          "{} is not conflicted in the stopped sync of '{}'",
+          path.display(),
+          self.crate_name
+        ),
+        format!("List the conflicts with `cargo rail resolve {}`", self.crate_name),
+      )
+    })
+  }
+
+  fn mark(&mut self, path: &Path, resolution: Resolution) -> RailResult<()> {
+    if let Some(file) = self.files.iter_mut().find(|f| f.path == path) {
+      file.resolution = Some(resolution);
+    }
+    self.persist()
+  }
+
+  fn settle_edit(&mut self, workspace_root: &Path, path: &Path) -> RailResult<bool> {
+    if has_conflict_markers(&workspace_root.join(path))? {
+      return Ok(false);
+    }
+    self.mark(path, Resolution::Edited)?;
+    Ok(true)
+  }
+}
+
+/// Whether a file still contains `git merge-file` conflict markers
+pub fn has_conflict_markers(path: &Path) -> RailResult<bool> {
+  if !path.exists() {
+    return Ok(false);
+  }
+  let content = std::fs::read(path)?;
+  Ok(
+    String::from_utf8_lossy(&content)
+      .lines()
+      .any(|line| line.starts_with("<<<<<<< ") || line.starts_with(">>>>>>> ") || line == "======="),
+  )
+}
+
+/// Path of a split's conflict state under `.git/rail/`
+fn state_path(workspace_root: &Path, crate_name: &str) -> RailResult<PathBuf> {
+  Ok(rail_dir(workspace_root)?.join(format!("conflicts-{}.json", crate_name)))
+}
+
+/// Scratch directory for merge sides, under `.git/rail/`
+fn scratch_dir(workspace_root: &Path) -> RailResult<PathBuf> {
+  let dir = rail_dir(workspace_root)?.join(format!("merge-{}", std::process::id()));
+  std::fs::create_dir_all(&dir)?;
+  Ok(dir)
+}
+
+/// Store content in the object database; returns the blob ID
+fn write_blob(repo: &Path, content: &[u8]) -> RailResult<String> {
+  let mut child = Command::new("git")
+    .current_dir(repo)
+    .args(["hash-object", "-w", "--stdin"])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .context("Failed to run git hash-object")?;
+  if let Some(mut stdin) = child.stdin.take() {
+    stdin.write_all(content)?;
+  }
+
+  let output = child.wait_with_output()?;
+  if !output.status.success() {
+    return Err(RailError::message(format!(
+      "git hash-object failed: {}",
+      String::from_utf8_lossy(&output.stderr)
+    )));
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn read_blob(repo: &Path, blob: &str) -> RailResult<Vec<u8>> {
+  let output = Command::new("git")
+    .current_dir(repo)
+    .args(["cat-file", "blob", blob])
+    .output()
+    .context("Failed to run git cat-file")?;
+  if !output.status.success() {
+    return Err(RailError::message(format!(
+      "Failed to read blob {}: {}",
+      blob,
+      String::from_utf8_lossy(&output.stderr)
+    )));
+  }
+  Ok(output.stdout)
+}
+
+fn git_config(repo: &Path, key: &str) -> RailResult<Option<String>> {
+  let output = Command::new("git")
+    .current_dir(repo)
+    .args(["config", "--get", key])
+    .output()
+    .context("Failed to run git config")?;
+  let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+  Ok((output.status.success() && !value.is_empty()).then_some(value))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::conflict::MergeVersions;
+  use tempfile::TempDir;
+
+  fn repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    Command::new("git")
+      .arg("init")
+      .current_dir(dir.path())
+      .output()
+      .unwrap();
+    dir
+  }
+
+  fn stopped(root: &Path) -> ConflictState {
+    let marked = "<<<<<<< ours\nline mono\n=======\nline remote\n>>>>>>> theirs\n";
+    std::fs::write(root.join("lib.rs"), marked).unwrap();
+
+    let commit = CommitInfo {
+      sha: "abc".to_string(),
+      author: "A".to_string(),
+      author_email: "a@example.com".to_string(),
+      committer: "A".to_string(),
+      committer_email: "a@example.com".to_string(),
+      message: "Remote change".to_string(),
+      timestamp: 0,
+      parent_shas: Vec::new(),
+    };
+    let conflict = ConflictInfo {
+      file_path: PathBuf::from("lib.rs"),
+      message: "Conflict in lib.rs".to_string(),
+      resolved: false,
+      versions: Some(MergeVersions {
+        base: b"line\n".to_vec(),
+        ours: b"line mono\n".to_vec(),
+        theirs: b"line remote\n".to_vec(),
+      }),
+    };
+    ConflictState::save(
+      root,
+      "my-crate",
+      "../split",
+      ConflictStrategy::Manual,
+      "main",
+      "def",
+      &commit,
+      &[conflict],
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn test_state_roundtrip_and_markers() {
+    let dir = repo();
+    let state = stopped(dir.path());
+    assert_eq!(state.unresolved(dir.path()).unwrap().len(), 1);
+
+    let loaded = ConflictState::load(dir.path(), "my-crate").unwrap().unwrap();
+    assert_eq!(loaded.commit.sha, "abc");
+    assert_eq!(loaded.files[0].path, PathBuf::from("lib.rs"));
+    assert_eq!(ConflictState::list(dir.path()).unwrap().len(), 1);
+
+    loaded.finish().unwrap();
+    assert!(ConflictState::load(dir.path(), "my-crate").unwrap().is_none());
+  }
+
+  #[test]
+  fn test_pick_sides() {
+    let dir = repo();
+    let mut state = stopped(dir.path());
+    let lib = dir.path().join("lib.rs");
+
+    state.pick(dir.path(), Path::new("lib.rs"), Resolution::Theirs).unwrap();
+    assert_eq!(std::fs::read_to_string(&lib).unwrap(), "line remote\n");
+
+    state.pick(dir.path(), Path::new("lib.rs"), Resolution::Ours).unwrap();
+    assert_eq!(std::fs::read_to_string(&lib).unwrap(), "line mono\n");
+
+    state.pick(dir.path(), Path::new("lib.rs"), Resolution::Union).unwrap();
+    let union = std::fs::read_to_string(&lib).unwrap();
+    assert!(union.contains("line mono") && union.contains("line remote"));
+    assert!(state.unresolved(dir.path()).unwrap().is_empty());
+
+    let loaded = ConflictState::load(dir.path(), "my-crate").unwrap().unwrap();
+    assert_eq!(loaded.files[0].resolution, Some(Resolution::Union));
+  }
+}
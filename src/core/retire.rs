@@ -0,0 +1,240 @@
+//! Retiring a split: stop syncing a crate without losing its history
+//!
+//! `cargo rail retire <crate>` builds a `Plan` that optionally commits an archival README to
+//! the split repo, moves the crate's mapping notes from `refs/notes/rail/{crate}` to
+//! `refs/notes/rail-archive/{crate}` (where no sync or check reads them) in the monorepo, the
+//! split repo and the split's remotes, and removes the `[[splits]]` entry from rail.toml along with the `split` link of its `[[releases]]`.
+
+use crate::core::auth::GitAuth;
+use crate::core::error::{ConfigError, GitError, RailError, RailResult, ResultExt};
+use std::path::Path;
+use std::process::Command;
+use toml_edit::{DocumentMut, Item};
+
+/// Mapping notes of a live split
+pub fn notes_ref(crate_name: &str) -> String {
+  format!("refs/notes/rail/{}", crate_name)
+}
+
+/// Where a retired split's mapping notes are kept
+pub fn archived_notes_ref(crate_name: &str) -> String {
+  format!("refs/notes/rail-archive/{}", crate_name)
+}
+
+/// README committed to a retired split's repo
+///
+/// `crate_paths` are the split's paths in the monorepo; `mono_url` links back to it if known.
+pub fn archive_readme(crate_name: &str, crate_paths: &[String], mono_url: Option<&str>) -> String {
+  let monorepo = match mono_url {
+    Some(url) => format!("[its monorepo]({})", url),
+    None => "its monorepo".to_string(),
+  };
+  let paths = crate_paths
+    .iter()
+    .map(|p| format!("`{}`", p))
+    .collect::<Vec<_>>()
+    .join(", ");
+
+  format!(
+    "# {0} (archived)\n\n\
+     This repository is no longer updated. `{0}` is developed in {1}, under {2}.\n\n\
+     Please open issues and pull requests there. The history here is kept as it was when the \
+     split was retired.\n",
+    crate_name, monorepo, paths
+  )
+}
+
+/// rail.toml with a split's `[[splits]]` entry removed and its `[[releases]]` unlinked
+///
+/// Edits the document in place, so comments and formatting elsewhere are kept. Returns the new
+/// contents and a description of each change.
+pub fn retired_config(content: &str, split: &str) -> RailResult<(String, Vec<String>)> {
+  let mut doc: DocumentMut = content
+    .parse()
+    .map_err(|e| RailError::message(format!("Failed to parse rail.toml: {}", e)))?;
+  let mut changes = Vec::new();
+
+  let splits = doc
+    .get_mut("splits")
+    .and_then(Item::as_array_of_tables_mut)
+    .ok_or_else(|| {
+      RailError::with_help(
+        "rail.toml has no [[splits]] tables",
+        "Remove the split from rail.toml by hand",
+      )
+    })?;
+  let index = splits
+    .iter()
+    .position(|table| table.get("name").and_then(Item::as_str) == Some(split))
+    .ok_or_else(|| {
+      RailError::Config(ConfigError::CrateNotFound {
+        name: split.to_string(),
+      })
+    })?;
+  splits.remove(index);
+  changes.push(format!("remove [[splits]] entry '{}'", split));
+
+  if let Some(releases) = doc.get_mut("releases").and_then(Item::as_array_of_tables_mut) {
+    for release in releases.iter_mut() {
+      if release.get("split").and_then(Item::as_str) == Some(split) {
+        release.remove("split");
+        let name = release.get("name").and_then(Item::as_str).unwrap_or("?");
+        changes.push(format!("unlink release '{}'", name));
+      }
+    }
+  }
+
+  Ok((doc.to_string(), changes))
+}
+
+/// Whether the repository has mapping notes for a crate
+pub fn has_notes(repo: &Path, crate_name: &str) -> bool {
+  Command::new("git")
+    .current_dir(repo)
+    .args(["rev-parse", "--verify", "--quiet", &notes_ref(crate_name)])
+    .output()
+    .is_ok_and(|o| o.status.success())
+}
+
+/// Move a crate's mapping notes to the archive ref
+///
+/// Refuses to overwrite an archive left by an earlier retirement of the same name.
+pub fn archive_notes(repo: &Path, crate_name: &str) -> RailResult<()> {
+  let live = notes_ref(crate_name);
+  let archived = archived_notes_ref(crate_name);
+
+  // An empty old value makes update-ref fail if the archive ref already exists
+  git(repo, &["update-ref", &archived, &live, ""])?;
+  git(repo, &["update-ref", "-d", &live])
+}
+
+/// Fetch a crate's mapping notes from a remote (a plain clone doesn't fetch notes)
+pub fn fetch_notes(repo: &Path, crate_name: &str, url: &str, auth: &GitAuth) -> RailResult<()> {
+  let live = notes_ref(crate_name);
+  match remote_git(repo, &["fetch", url, &format!("{0}:{0}", live)], auth) {
+    Err(e) if !e.to_string().contains("couldn't find remote ref") => Err(e),
+    _ => Ok(()),
+  }
+}
+
+/// Move a crate's mapping notes to the archive ref on a remote
+///
+/// Pushes `repo`'s archive ref (if it has one) without forcing, so an earlier archive on the
+/// remote is never overwritten, then deletes the live ref there.
+pub fn archive_remote_notes(repo: &Path, crate_name: &str, url: &str, auth: &GitAuth) -> RailResult<()> {
+  let live = notes_ref(crate_name);
+  let archived = archived_notes_ref(crate_name);
+
+  let has_archive = Command::new("git")
+    .current_dir(repo)
+    .args(["rev-parse", "--verify", "--quiet", &archived])
+    .output()
+    .is_ok_and(|o| o.status.success());
+  if has_archive {
+    remote_git(repo, &["push", url, &format!("{0}:{0}", archived)], auth)?;
+  }
+
+  match remote_git(repo, &["push", url, "--delete", &live], auth) {
+    Err(e) if !e.to_string().contains("remote ref does not exist") => Err(e),
+    _ => Ok(()),
+  }
+}
+
+fn git(repo: &Path, args: &[&str]) -> RailResult<()> {
+  remote_git(repo, args, &GitAuth::default())
+}
+
+fn remote_git(repo: &Path, args: &[&str], auth: &GitAuth) -> RailResult<()> {
+  let mut cmd = Command::new("git");
+  auth.apply(&mut cmd);
+  let output = cmd
+    .current_dir(repo)
+    .args(args)
+    .output()
+    .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+
+  if !output.status.success() {
+    return Err(RailError::Git(GitError::CommandFailed {
+      command: format!("git {}", args.join(" ")),
+      stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    }));
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CONFIG: &str = r#"[workspace]
+root = "."
+
+[[splits]]
+name = "lib-a"
+remote = "git@github.com:org/lib-a.git"
+branch = "main"
+mode = "single"
+
+[[splits.paths]]
+crate = "crates/lib-a"
+
+[[splits]]
+name = "lib-b"
+remote = "git@github.com:org/lib-b.git"
+branch = "main"
+mode = "single"
+
+[[splits.paths]]
+crate = "crates/lib-b"
+
+# Release tracking
+[[releases]]
+name = "lib-a"
+crate = "crates/lib-a"
+split = "lib-a"
+last_version = "0.3.1"
+"#;
+
+  #[test]
+  fn test_retired_config_removes_split_and_unlinks_releases() {
+    let (content, changes) = retired_config(CONFIG, "lib-a").unwrap();
+
+    assert_eq!(
+      changes,
+      vec![
+        "remove [[splits]] entry 'lib-a'".to_string(),
+        "unlink release 'lib-a'".to_string()
+      ]
+    );
+    let doc: DocumentMut = content.parse().unwrap();
+    let splits: Vec<&str> = doc["splits"]
+      .as_array_of_tables()
+      .unwrap()
+      .iter()
+      .map(|split| split["name"].as_str().unwrap())
+      .collect();
+    assert_eq!(splits, ["lib-b"]);
+    assert!(content.contains("# Release tracking\n[[releases]]"));
+
+    let release = content.split("[[releases]]").nth(1).unwrap();
+    assert!(release.contains("last_version = \"0.3.1\""));
+    assert!(!release.contains("split ="));
+
+    let err = retired_config(CONFIG, "lib-c").unwrap_err();
+    assert!(err.to_string().contains("lib-c"), "{}", err);
+  }
+
+  #[test]
+  fn test_archive_readme_links_back_to_the_monorepo() {
+    let readme = archive_readme(
+      "lib-a",
+      &["crates/lib-a".to_string()],
+      Some("https://github.com/org/mono"),
+    );
+    assert!(readme.starts_with("# lib-a (archived)\n"));
+    assert!(readme.contains("[its monorepo](https://github.com/org/mono), under `crates/lib-a`"));
+
+    let readme = archive_readme("lib-a", &["a".to_string(), "b".to_string()], None);
+    assert!(readme.contains("developed in its monorepo, under `a`, `b`."));
+  }
+}
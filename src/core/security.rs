@@ -6,14 +6,28 @@ use std::process::Command;
 
 use super::config::SecurityConfig;
 
+/// Principal the signing key is listed under in the allowed-signers file
+const SIGNER_PRINCIPAL: &str = "cargo-rail";
+
 /// Security validator for SSH keys and signing
 pub struct SecurityValidator {
   config: SecurityConfig,
+  /// Allowed-signers file written from the signing key and used to verify signatures
+  allowed_signers: Option<PathBuf>,
 }
 
 impl SecurityValidator {
   pub fn new(config: SecurityConfig) -> Self {
-    Self { config }
+    Self {
+      config,
+      allowed_signers: None,
+    }
+  }
+
+  /// Verify signatures against an allowed-signers file at `path`, generated from the signing key
+  pub fn with_allowed_signers(mut self, path: PathBuf) -> Self {
+    self.allowed_signers = Some(path);
+    self
   }
 
   /// Find and validate SSH key for git operations
@@ -66,6 +80,13 @@ impl SecurityValidator {
     Ok(ssh_key)
   }
 
+  /// The key git is bound to, if `ssh_key_path` is configured
+  ///
+  /// Without one, ssh picks the key itself (agent, `~/.ssh/config`, default identities).
+  pub fn ssh_key_path(&self) -> Option<PathBuf> {
+    self.config.ssh_key_path.clone()
+  }
+
   /// Find default SSH key in standard locations
   fn find_default_ssh_key(&self) -> RailResult<PathBuf> {
     let home = std::env::var("HOME")
@@ -104,7 +125,20 @@ impl SecurityValidator {
     }))
   }
 
-  /// Validate signing key (if required)
+  /// Key recreated commits are signed with (None unless `require_signed_commits`)
+  pub fn signing_key_path(&self) -> Option<PathBuf> {
+    if !self.config.require_signed_commits {
+      return None;
+    }
+    self
+      .config
+      .signing_key_path
+      .clone()
+      .or_else(|| self.ssh_key_path())
+      .or_else(|| self.find_default_ssh_key().ok())
+  }
+
+  /// Validate signing key (if required), refreshing the allowed-signers file from it
   pub fn validate_signing_key(&self) -> RailResult<Option<PathBuf>> {
     if !self.config.require_signed_commits {
       return Ok(None);
@@ -126,18 +160,22 @@ impl SecurityValidator {
            \n\
            To fix this:\n\
            1. Generate a signing key: ssh-keygen -t ed25519 -f ~/.ssh/id_signing\n\
-           2. Configure git to use it: git config --global gpg.format ssh\n\
-           3. git config --global user.signingkey {}\n\
+           2. Point rail.toml at it:\n\
+           [security]\n\
+           signing_key_path = \"~/.ssh/id_signing\"\n\
            \n\
            Or disable signing in rail.toml:\n\
            [security]\n\
            require_signed_commits = false",
-          signing_key.display(),
           signing_key.display()
         ),
       }));
     }
 
+    if let Some(ref allowed_signers) = self.allowed_signers {
+      write_allowed_signers(allowed_signers, &signing_key)?;
+    }
+
     println!("✅ Signing key validated: {}", signing_key.display());
 
     Ok(Some(signing_key))
@@ -158,14 +196,34 @@ impl SecurityValidator {
       .replace("{timestamp}", &timestamp.to_string())
   }
 
+  /// Pattern (for `git for-each-ref`) matching every PR branch generated for a crate
+  pub fn pr_branch_glob(&self, crate_name: &str) -> String {
+    self
+      .config
+      .pr_branch_pattern
+      .replace("{crate}", crate_name)
+      .replace("{timestamp}", "*")
+  }
+
+  /// Whether every sync of a crate reuses the same PR branch (a pattern without `{timestamp}`)
+  pub fn reuses_pr_branch(&self) -> bool {
+    !self.config.pr_branch_pattern.contains("{timestamp}")
+  }
+
   /// Verify a commit is signed (if required)
   pub fn verify_commit_signature(&self, repo_path: &Path, commit_sha: &str) -> RailResult<bool> {
     if !self.config.require_signed_commits {
       return Ok(true); // Not required, so pass
     }
 
-    // Use git to verify signature
-    let output = Command::new("git")
+    // Use git to verify signature, trusting only the signing key when its file was generated
+    let mut cmd = Command::new("git");
+    if let Some(ref allowed_signers) = self.allowed_signers {
+      cmd
+        .arg("-c")
+        .arg(format!("gpg.ssh.allowedSignersFile={}", allowed_signers.display()));
+    }
+    let output = cmd
       .args(["verify-commit", commit_sha])
       .current_dir(repo_path)
       .output()
@@ -183,6 +241,75 @@ impl SecurityValidator {
 
     Ok(true)
   }
+
+  /// Fail unless `commit_sha` carries a signature from the signing key (if required)
+  pub fn require_commit_signature(&self, repo_path: &Path, commit_sha: &str) -> RailResult<()> {
+    if self.verify_commit_signature(repo_path, commit_sha)? {
+      return Ok(());
+    }
+    Err(RailError::with_help(
+      format!("Commit {} is not signed by the configured signing key", commit_sha),
+      "Check signing_key_path in rail.toml points at the key the allowed-signers file in .git/rail was generated from",
+    ))
+  }
+}
+
+/// `git -c` options making `commit-tree -S` produce an SSH signature with `key`
+///
+/// Ed25519 (and RSA) SSH signatures are deterministic, so re-splitting the same history with
+/// the same key reproduces the same commit SHAs; ECDSA signatures are randomized.
+pub fn ssh_signing_args(key: &Path) -> [String; 4] {
+  [
+    "-c".to_string(),
+    "gpg.format=ssh".to_string(),
+    "-c".to_string(),
+    format!("user.signingKey={}", key.display()),
+  ]
+}
+
+/// Write an allowed-signers file trusting `signing_key`, if it doesn't already say so
+fn write_allowed_signers(path: &Path, signing_key: &Path) -> RailResult<()> {
+  let public_key = public_key_for(signing_key)?;
+  let content = format!("{} namespaces=\"git\" {}\n", SIGNER_PRINCIPAL, public_key);
+  if std::fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+    return Ok(());
+  }
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+  }
+  std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Public half of an SSH key: `<key>.pub` if present, else derived with `ssh-keygen -y`
+pub(crate) fn public_key_for(key: &Path) -> RailResult<String> {
+  let public_path = PathBuf::from(format!("{}.pub", key.display()));
+  let public_key = if public_path.is_file() {
+    std::fs::read_to_string(&public_path).with_context(|| format!("Failed to read {}", public_path.display()))?
+  } else {
+    let output = Command::new("ssh-keygen")
+      .arg("-y")
+      .arg("-f")
+      .arg(key)
+      .output()
+      .context("Failed to run ssh-keygen")?;
+    if !output.status.success() {
+      return Err(RailError::with_help(
+        format!("Could not read the public key of {}", key.display()),
+        format!("Put the public key next to it as {}", public_path.display()),
+      ));
+    }
+    String::from_utf8_lossy(&output.stdout).to_string()
+  };
+
+  // "<type> <base64> [comment]": the comment isn't part of the key
+  let mut fields = public_key.split_whitespace();
+  match (fields.next(), fields.next()) {
+    (Some(kind), Some(blob)) => Ok(format!("{} {}", kind, blob)),
+    _ => Err(RailError::message(format!(
+      "{} is not an SSH public key",
+      public_path.display()
+    ))),
+  }
 }
 
 #[cfg(test)]
@@ -200,5 +327,74 @@ mod tests {
     let timestamp_part = branch.split('/').next_back().unwrap();
     let timestamp: u64 = timestamp_part.parse().expect("timestamp should be numeric");
     assert!(timestamp > 1600000000, "timestamp should be recent (post-2020)");
+    assert!(!validator.reuses_pr_branch());
+    assert_eq!(validator.pr_branch_glob("test-crate"), "rail/sync/test-crate/*");
+
+    let stable = SecurityValidator::new(SecurityConfig {
+      pr_branch_pattern: "rail/sync/{crate}".to_string(),
+      ..SecurityConfig::default()
+    });
+    assert_eq!(stable.generate_pr_branch("test-crate"), "rail/sync/test-crate");
+    assert!(stable.reuses_pr_branch());
+    assert_eq!(stable.pr_branch_glob("test-crate"), "rail/sync/test-crate");
+  }
+
+  fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git").current_dir(dir).args(args).status().unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+  }
+
+  /// Commit the same file into a fresh repo, signed with `key`
+  fn signed_commit(dir: &Path, key: &Path) -> String {
+    use crate::core::vcs::SystemGit;
+
+    git(dir, &["init", "--quiet"]);
+    std::fs::write(dir.join("lib.rs"), "pub fn f() {}\n").unwrap();
+    SystemGit::open(dir)
+      .unwrap()
+      .with_signing_key(Some(key.to_path_buf()))
+      .create_commit_with_metadata("Add f", "Dev", "dev@example.com", 1_700_000_000, &[])
+      .unwrap()
+  }
+
+  #[test]
+  fn test_signed_commits_are_deterministic_and_verify() {
+    let temp = tempfile::tempdir().unwrap();
+    let key = temp.path().join("id_signing");
+    let status = Command::new("ssh-keygen")
+      .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+      .arg(&key)
+      .status();
+    if !status.is_ok_and(|s| s.success()) {
+      return; // ssh-keygen unavailable
+    }
+
+    let validator = SecurityValidator::new(SecurityConfig {
+      require_signed_commits: true,
+      signing_key_path: Some(key.clone()),
+      ..SecurityConfig::default()
+    })
+    .with_allowed_signers(temp.path().join("rail/allowed_signers"));
+    assert_eq!(validator.validate_signing_key().unwrap(), Some(key.clone()));
+
+    let (first, second) = (temp.path().join("a"), temp.path().join("b"));
+    std::fs::create_dir_all(&first).unwrap();
+    std::fs::create_dir_all(&second).unwrap();
+    let sha = signed_commit(&first, &key);
+    assert_eq!(sha, signed_commit(&second, &key), "same key, same history, same SHA");
+
+    validator.require_commit_signature(&first, &sha).unwrap();
+
+    // A different key isn't in the allowed-signers file
+    let other = temp.path().join("id_other");
+    Command::new("ssh-keygen")
+      .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+      .arg(&other)
+      .status()
+      .unwrap();
+    let third = temp.path().join("c");
+    std::fs::create_dir_all(&third).unwrap();
+    let foreign = signed_commit(&third, &other);
+    assert!(validator.require_commit_signature(&third, &foreign).is_err());
   }
 }
@@ -1,15 +1,20 @@
 use crate::core::error::{GitError, RailError, RailResult, ResultExt};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use crate::cargo::files::{AuxiliaryFiles, ProjectFiles};
 use crate::cargo::helpers;
-use crate::cargo::metadata::WorkspaceMetadata;
-use crate::cargo::transform::{CargoTransform, TransformContext};
-use crate::core::config::{SecurityConfig, SplitMode};
+use crate::cargo::lockfile::{self, LockfileTarget};
+use crate::cargo::transform::{CargoTransform, DependencyOverrides, TransformContext};
+use crate::core::auth::GitAuth;
+use crate::core::config::{RemoteConfig, SecurityConfig, SplitMode, WorkspaceMode};
+use crate::core::journal::rail_dir;
 use crate::core::mapping::MappingStore;
-use crate::core::security::SecurityValidator;
+use crate::core::mirror;
+use crate::core::security::{SecurityValidator, ssh_signing_args};
 use crate::core::vcs::CommitInfo;
 use crate::core::vcs::SystemGit;
+use crate::core::verify::BuildVerifier;
 use crate::utils;
 
 /// Configuration for a split operation
@@ -17,9 +22,31 @@ pub struct SplitConfig {
   pub crate_name: String,
   pub crate_paths: Vec<PathBuf>,
   pub mode: SplitMode,
+  pub workspace_mode: WorkspaceMode,
   pub target_repo_path: PathBuf,
   pub branch: String,
   pub remote_url: Option<String>,
+  /// Subdirectory of the target repo holding the split (empty = repo root)
+  pub target_dir: PathBuf,
+  /// Commit a Cargo.lock derived from the mono lockfile
+  pub lockfile: bool,
+  /// Verify the split builds standalone before pushing
+  pub verify_build: bool,
+  /// Remotes pushed to after the primary
+  pub mirrors: Vec<RemoteConfig>,
+  /// Credentials for the remotes
+  pub auth: GitAuth,
+}
+
+impl SplitConfig {
+  /// Directory in the target repo the split tree is written to
+  pub fn split_root(&self) -> PathBuf {
+    if self.target_dir.as_os_str().is_empty() {
+      self.target_repo_path.clone()
+    } else {
+      self.target_repo_path.join(&self.target_dir)
+    }
+  }
 }
 
 /// Parameters for recreating a commit in the target repository
@@ -27,11 +54,17 @@ struct RecreateCommitParams<'a> {
   commit: &'a CommitInfo,
   crate_paths: &'a [PathBuf],
   target_repo_path: &'a Path,
+  /// Where the split tree lives inside the target repo (`target_repo_path` unless `target_dir` is set)
+  split_root: &'a Path,
   workspace_root: &'a Path,
   crate_name: &'a str,
   mode: &'a SplitMode,
+  /// Crates kept as workspace members (Some only for workspace-mode combined splits)
+  workspace_members: Option<&'a HashSet<String>>,
   mapping_store: &'a MappingStore,
   last_recreated_sha: Option<&'a str>,
+  /// Lockfiles to derive from the mono Cargo.lock, and the crates shipped in the split
+  lockfiles: Option<(&'a [LockfileTarget], &'a HashSet<String>)>,
 }
 
 /// Parameters for creating a git commit
@@ -57,11 +90,12 @@ pub struct Splitter {
 
 impl Splitter {
   /// Create a new splitter for a workspace
-  pub fn new(workspace_root: PathBuf, security_config: SecurityConfig) -> RailResult<Self> {
+  pub fn new(workspace_root: PathBuf, transform: CargoTransform, security_config: SecurityConfig) -> RailResult<Self> {
     let git = SystemGit::open(&workspace_root)?;
-    let metadata = WorkspaceMetadata::load(&workspace_root)?;
-    let transform = CargoTransform::new(metadata);
-    let security_validator = SecurityValidator::new(security_config);
+    let mut security_validator = SecurityValidator::new(security_config);
+    if security_validator.signing_key_path().is_some() {
+      security_validator = security_validator.with_allowed_signers(rail_dir(&workspace_root)?.join("allowed_signers"));
+    }
 
     Ok(Self {
       workspace_root,
@@ -71,6 +105,12 @@ impl Splitter {
     })
   }
 
+  /// Provide unpublished workspace dependencies from bundled paths or git remotes
+  pub fn with_dependency_overrides(mut self, overrides: DependencyOverrides) -> Self {
+    self.transform = self.transform.with_dependency_overrides(overrides);
+    self
+  }
+
   /// Walk commit history and filter commits that touch the given paths
   /// Returns commits in chronological order (oldest first)
   fn walk_filtered_history(&self, paths: &[PathBuf]) -> RailResult<Vec<CommitInfo>> {
@@ -89,7 +129,15 @@ impl Splitter {
 
   /// Apply Cargo.toml transformation to a manifest file
   /// Returns Ok(()) if transform succeeded or file doesn't exist
-  fn apply_manifest_transform(&self, manifest_path: &Path, crate_name: &str) -> RailResult<()> {
+  ///
+  /// With `workspace_members` set (workspace-mode combined splits), workspace inheritance
+  /// and path dependencies between members are kept instead of being flattened.
+  fn apply_manifest_transform(
+    &self,
+    manifest_path: &Path,
+    crate_name: &str,
+    workspace_members: Option<&HashSet<String>>,
+  ) -> RailResult<()> {
     if !manifest_path.exists() {
       return Ok(());
     }
@@ -99,19 +147,65 @@ impl Splitter {
       crate_name: crate_name.to_string(),
       workspace_root: self.workspace_root.clone(),
     };
-    let transformed = self.transform.transform_to_split(&content, &context)?;
+    let transformed = match workspace_members {
+      Some(members) => self.transform.transform_to_split_member(&content, &context, members)?,
+      None => self.transform.transform_to_split(&content, &context)?,
+    };
     std::fs::write(manifest_path, transformed)?;
     Ok(())
   }
 
+  /// Resolve the crates kept as workspace members for a split
+  /// Returns None unless this is a workspace-mode combined split
+  fn workspace_members_for(
+    &self,
+    mode: &SplitMode,
+    workspace_mode: &WorkspaceMode,
+    crate_paths: &[PathBuf],
+  ) -> Option<HashSet<String>> {
+    match (mode, workspace_mode) {
+      (SplitMode::Combined, WorkspaceMode::Workspace) => Some(self.transform.crate_names_for_paths(crate_paths)),
+      _ => None,
+    }
+  }
+
+  /// Write the generated root Cargo.toml for a workspace-mode combined split
+  fn write_workspace_manifest(
+    &self,
+    target_repo_path: &Path,
+    crate_paths: &[PathBuf],
+    workspace_members: &HashSet<String>,
+  ) -> RailResult<()> {
+    let mut member_paths = Vec::new();
+    let mut member_manifests = Vec::new();
+    for crate_path in crate_paths {
+      let manifest_path = target_repo_path.join(crate_path).join("Cargo.toml");
+      if manifest_path.exists() {
+        member_paths.push(crate_path.clone());
+        member_manifests.push(std::fs::read_to_string(&manifest_path)?);
+      }
+    }
+
+    let root_manifest =
+      self
+        .transform
+        .generate_split_workspace_manifest(&member_paths, &member_manifests, workspace_members)?;
+    std::fs::write(target_repo_path.join("Cargo.toml"), root_manifest)?;
+    Ok(())
+  }
+
   /// Recreate a commit in the target repository with transforms applied
   /// Returns the new commit SHA
   fn recreate_commit_in_target(&self, params: &RecreateCommitParams) -> RailResult<String> {
-    // Collect all files for the crate at this commit
+    // Collect all files for the crate at this commit (paths relative to the workspace root)
     let mut all_files = Vec::new();
     for crate_path in params.crate_paths {
       let files = self.git.collect_tree_files(&params.commit.sha, crate_path)?;
-      all_files.extend(files);
+      all_files.extend(
+        files
+          .into_iter()
+          .map(|(file, content)| (crate_path.join(file), content)),
+      );
     }
 
     if all_files.is_empty() {
@@ -133,11 +227,11 @@ impl Splitter {
               break;
             }
           }
-          params.target_repo_path.join(relative)
+          params.split_root.join(relative)
         }
         SplitMode::Combined => {
           // For combined mode, preserve paths
-          params.target_repo_path.join(file_path)
+          params.split_root.join(file_path)
         }
       };
 
@@ -151,10 +245,15 @@ impl Splitter {
 
       // Apply Cargo.toml transformation if applicable
       if file_path.file_name() == Some(std::ffi::OsStr::new("Cargo.toml")) {
-        self.apply_manifest_transform(&target_path, params.crate_name)?;
+        self.apply_manifest_transform(&target_path, params.crate_name, params.workspace_members)?;
       }
     }
 
+    // Regenerate the root manifest so the split stays a buildable workspace
+    if let Some(members) = params.workspace_members {
+      self.write_workspace_manifest(params.split_root, params.crate_paths, members)?;
+    }
+
     // Copy auxiliary files for Cargo crates (e.g., rust-toolchain.toml, .cargo/config.toml)
     for crate_path in params.crate_paths {
       let package_root = params.workspace_root.join(crate_path);
@@ -168,11 +267,11 @@ impl Splitter {
           let target_path = match params.mode {
             SplitMode::Single => {
               // For single mode, copy to root
-              params.target_repo_path.join(&aux_file)
+              params.split_root.join(&aux_file)
             }
             SplitMode::Combined => {
               // For combined mode, preserve the crate path structure
-              params.target_repo_path.join(crate_path).join(&aux_file)
+              params.split_root.join(crate_path).join(&aux_file)
             }
           };
 
@@ -187,6 +286,11 @@ impl Splitter {
       }
     }
 
+    // Pin dependencies to what the monorepo resolved at this commit
+    if let Some((targets, local_crates)) = params.lockfiles {
+      lockfile::write_split_lockfiles(&self.git, &params.commit.sha, params.split_root, targets, local_crates)?;
+    }
+
     // Create commit using git command for determinism
     // Map parent SHAs from monorepo to split repo
     let mut mapped_parents: Vec<String> = params
@@ -256,7 +360,11 @@ impl Splitter {
     let commit_date = format!("{} +0000", params.timestamp);
 
     // Build commit-tree command
+    let signing_key = self.security_validator.signing_key_path();
     let mut cmd = Command::new("git");
+    if let Some(ref key) = signing_key {
+      cmd.args(ssh_signing_args(key));
+    }
     cmd
       .current_dir(params.repo_path)
       .env("GIT_AUTHOR_NAME", params.author_name)
@@ -269,6 +377,9 @@ impl Splitter {
       .arg(&tree_sha)
       .arg("-m")
       .arg(params.message);
+    if signing_key.is_some() {
+      cmd.arg("-S");
+    }
 
     // Add parent arguments
     for parent in params.parent_shas {
@@ -299,10 +410,12 @@ impl Splitter {
   }
 
   /// Check if remote repository exists and has content
-  fn check_remote_exists(&self, remote_url: &str) -> RailResult<bool> {
+  fn check_remote_exists(&self, remote_url: &str, auth: &GitAuth) -> RailResult<bool> {
     use std::process::Command;
 
-    let output = Command::new("git")
+    let mut cmd = Command::new("git");
+    auth.apply(&mut cmd);
+    let output = cmd
       .args(["ls-remote", "--heads", remote_url])
       .output()
       .context("Failed to check remote")?;
@@ -315,12 +428,24 @@ impl Splitter {
   pub fn split(&self, config: &SplitConfig) -> RailResult<()> {
     println!("🚂 Splitting crate: {}", config.crate_name);
     println!("   Mode: {:?}", config.mode);
+    if matches!(config.mode, SplitMode::Combined) {
+      println!("   Workspace mode: {:?}", config.workspace_mode);
+    }
     println!("   Target: {}", config.target_repo_path.display());
+    if !config.target_dir.as_os_str().is_empty() {
+      println!("   Target dir: {}", config.target_dir.display());
+    }
+
+    // Recreated commits get signed, so the key must be usable before any are written
+    self.security_validator.validate_signing_key()?;
 
     // Check if remote already exists - if so, error with helpful message
+    // (splits into a subdirectory may land in an existing repo, as long as the subdirectory is new)
     if let Some(ref remote_url) = config.remote_url {
-      let remote_exists = self.check_remote_exists(remote_url)?;
-      if remote_exists {
+      let remote_exists = self.check_remote_exists(remote_url, &config.auth)?;
+      if remote_exists && !config.target_dir.as_os_str().is_empty() {
+        self.prepare_existing_target(config, remote_url)?;
+      } else if remote_exists {
         return Err(RailError::with_help(
           format!("Split already exists at {}", remote_url),
           format!(
@@ -337,6 +462,7 @@ impl Splitter {
     self.ensure_target_repo(&config.target_repo_path)?;
 
     // Discover workspace-level auxiliary files from workspace
+    let split_root = config.split_root();
     let aux_files = AuxiliaryFiles::discover(&self.workspace_root)?;
     println!("   Found {} workspace config files", aux_files.count());
 
@@ -349,6 +475,8 @@ impl Splitter {
     let mut mapping_store = MappingStore::new(config.crate_name.clone());
     mapping_store.load(&self.workspace_root)?;
 
+    let workspace_members = self.workspace_members_for(&config.mode, &config.workspace_mode, &config.crate_paths);
+
     // Walk filtered history to find commits touching the crate
     let filtered_commits = self.walk_filtered_history(&config.crate_paths)?;
 
@@ -360,14 +488,15 @@ impl Splitter {
       match config.mode {
         SplitMode::Single => {
           let crate_path = &config.crate_paths[0];
-          self.split_single_crate(crate_path, &config.target_repo_path, &aux_files, &config.crate_name)?;
+          self.split_single_crate(crate_path, &split_root, &aux_files, &config.crate_name)?;
         }
         SplitMode::Combined => {
           self.split_combined_crates(
             &config.crate_paths,
-            &config.target_repo_path,
+            &split_root,
             &aux_files,
             &config.crate_name,
+            workspace_members.as_ref(),
           )?;
         }
       }
@@ -380,18 +509,32 @@ impl Splitter {
         format!("Processing {} commits", filtered_commits.len()),
       );
 
-      let mut last_recreated_sha: Option<String> = None;
+      // Build on top of the existing history when splitting into a subdirectory of an existing repo
+      let mut last_recreated_sha: Option<String> = SystemGit::open(&config.target_repo_path)?.head_commit().ok();
+
+      let lockfile_targets = config.lockfile.then(|| {
+        lockfile::lockfile_targets(
+          &self.transform,
+          &config.mode,
+          &config.workspace_mode,
+          &config.crate_paths,
+        )
+      });
+      let local_crates = self.transform.crate_names_for_paths(&config.crate_paths);
 
       for commit in filtered_commits.iter() {
         let new_sha = self.recreate_commit_in_target(&RecreateCommitParams {
           commit,
           crate_paths: &config.crate_paths,
           target_repo_path: &config.target_repo_path,
+          split_root: &split_root,
           workspace_root: &self.workspace_root,
           crate_name: &config.crate_name,
           mode: &config.mode,
+          workspace_members: workspace_members.as_ref(),
           mapping_store: &mapping_store,
           last_recreated_sha: last_recreated_sha.as_deref(),
+          lockfiles: lockfile_targets.as_deref().map(|targets| (targets, &local_crates)),
         })?;
 
         // Record mapping
@@ -408,8 +551,8 @@ impl Splitter {
       let has_files = !aux_files.is_empty() || project_files.count() > 0;
       if has_files {
         println!("   Copying workspace configs and project files...");
-        aux_files.copy_to_split(&self.workspace_root, &config.target_repo_path)?;
-        project_files.copy_to_split(&self.workspace_root, &config.target_repo_path)?;
+        aux_files.copy_to_split(&self.workspace_root, &split_root)?;
+        project_files.copy_to_split(&self.workspace_root, &split_root)?;
 
         // Create a final commit if any files were added
         // git add -A is safe to run unconditionally (no-op if no changes)
@@ -427,7 +570,11 @@ impl Splitter {
         if !diff_cached.success() {
           // Exit code 1 means there are differences (i.e., staged changes)
           println!("   Creating commit for auxiliary files");
-          std::process::Command::new("git")
+          let mut cmd = std::process::Command::new("git");
+          if let Some(key) = self.security_validator.signing_key_path() {
+            cmd.args(ssh_signing_args(&key)).arg("-c").arg("commit.gpgSign=true");
+          }
+          cmd
             .current_dir(&config.target_repo_path)
             .args(["commit", "-m", "Add workspace configs and project files"])
             .status()?;
@@ -435,6 +582,24 @@ impl Splitter {
       }
     }
 
+    // Refuse to hand over a split whose tip doesn't carry a valid signature
+    let tip = SystemGit::open(&config.target_repo_path)?.head_commit()?;
+    self
+      .security_validator
+      .require_commit_signature(&config.target_repo_path, &tip)?;
+
+    // Verify the split builds on its own before its mappings are recorded or anything is
+    // pushed, so a failed verification leaves no notes pointing at the rejected commits
+    if config.verify_build {
+      BuildVerifier::new(
+        self.workspace_root.clone(),
+        config.crate_paths.clone(),
+        config.mode.clone(),
+      )
+      .with_subdir(config.target_dir.clone())
+      .verify(&config.target_repo_path, &tip)?;
+    }
+
     // Save mappings to both workspace and target repo
     mapping_store.save(&self.workspace_root)?;
     mapping_store.save(&config.target_repo_path)?;
@@ -446,10 +611,10 @@ impl Splitter {
 
         // Security checks before push
         // Note: Branch protection doesn't apply to remote repos - only to monorepo
-        self.security_validator.validate_ssh_key()?;
+        config.auth.validate(&self.security_validator)?;
 
         // Open the target repo
-        let target_git = SystemGit::open(&config.target_repo_path)?;
+        let target_git = SystemGit::open(&config.target_repo_path)?.with_auth(config.auth.clone());
 
         // Add or update remote
         if !target_git.has_remote("origin")? {
@@ -463,7 +628,7 @@ impl Splitter {
         target_git.push_to_remote("origin", &config.branch)?;
 
         // Push git-notes
-        mapping_store.push_notes(&config.target_repo_path, "origin")?;
+        mapping_store.push_notes(&config.target_repo_path, "origin", &config.auth)?;
 
         println!("   ✅ Pushed to {}", remote_url);
       } else {
@@ -490,12 +655,74 @@ impl Splitter {
       println!("   git push -u origin {}", config.branch);
     }
 
+    // Mirrors get the same branch and notes; a failed one is retried by the next split or sync
+    if !config.mirrors.is_empty() {
+      println!("\n🪞 Pushing to {} mirror(s)...", config.mirrors.len());
+      mirror::report(&mirror::push_to_mirrors(
+        &config.target_repo_path,
+        &config.mirrors,
+        &config.branch,
+        &config.crate_name,
+        &config.auth,
+        &self.workspace_root,
+      ))?;
+    }
+
     println!("\n✅ Split complete!");
     println!("   Target repo: {}", config.target_repo_path.display());
 
     Ok(())
   }
 
+  /// Get an existing target repo ready to receive a split into `target_dir`
+  ///
+  /// Clones the remote if there is no local checkout yet. The split is refused if the
+  /// subdirectory already exists, since updating it is what `sync` is for.
+  fn prepare_existing_target(&self, config: &SplitConfig, remote_url: &str) -> RailResult<()> {
+    use std::process::Command;
+
+    if !config.target_repo_path.join(".git").exists() {
+      println!("   Cloning existing repository {}", remote_url);
+      let mut cmd = Command::new("git");
+      config.auth.apply(&mut cmd);
+      let output = cmd
+        .args(["clone", "--quiet", "--branch", &config.branch, remote_url])
+        .arg(&config.target_repo_path)
+        .output()
+        .context("Failed to clone target repository")?;
+      if !output.status.success() {
+        return Err(RailError::Git(GitError::CommandFailed {
+          command: "git clone".to_string(),
+          stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        }));
+      }
+      configure_git_identity(&self.workspace_root, &config.target_repo_path)?;
+    }
+
+    let output = Command::new("git")
+      .current_dir(&config.target_repo_path)
+      .args(["ls-tree", "--name-only", "HEAD", "--"])
+      .arg(&config.target_dir)
+      .output()
+      .context("Failed to inspect target repository")?;
+    if output.status.success() && !output.stdout.is_empty() {
+      return Err(RailError::with_help(
+        format!(
+          "Split already exists at {} in {}",
+          config.target_dir.display(),
+          remote_url
+        ),
+        format!(
+          "Split is a one-time operation. To update the split directory, use:\n  \
+           cargo rail sync {}",
+          config.crate_name
+        ),
+      ));
+    }
+
+    Ok(())
+  }
+
   /// Ensure target repository exists and is initialized
   fn ensure_target_repo(&self, target_path: &Path) -> RailResult<()> {
     if !target_path.exists() {
@@ -517,74 +744,7 @@ impl Splitter {
         .with_context(|| format!("Failed to initialize git repository at {}", target_path.display()))?;
 
       // Configure git identity from source repository
-      self.configure_git_identity(target_path)?;
-    }
-
-    Ok(())
-  }
-
-  /// Configure git identity in the target repository by copying from source
-  fn configure_git_identity(&self, target_path: &Path) -> RailResult<()> {
-    use std::process::Command;
-
-    // Get identity from source repository
-    let user_name = Command::new("git")
-      .current_dir(&self.workspace_root)
-      .args(["config", "user.name"])
-      .output()
-      .ok()
-      .and_then(|o| {
-        if o.status.success() {
-          Some(String::from_utf8_lossy(&o.stdout).trim().to_string())
-        } else {
-          None
-        }
-      });
-
-    let user_email = Command::new("git")
-      .current_dir(&self.workspace_root)
-      .args(["config", "user.email"])
-      .output()
-      .ok()
-      .and_then(|o| {
-        if o.status.success() {
-          Some(String::from_utf8_lossy(&o.stdout).trim().to_string())
-        } else {
-          None
-        }
-      });
-
-    // Set identity in target repository
-    // Use a fallback if source doesn't have identity configured
-    let name = user_name.as_deref().unwrap_or("Cargo Rail");
-    let email = user_email.as_deref().unwrap_or("cargo-rail@localhost");
-
-    let output = Command::new("git")
-      .current_dir(target_path)
-      .args(["config", "user.name", name])
-      .output()
-      .context("Failed to configure git user.name")?;
-
-    if !output.status.success() {
-      let stderr = String::from_utf8_lossy(&output.stderr);
-      return Err(RailError::Git(GitError::CommandFailed {
-        command: "git config user.name".to_string(),
-        stderr: stderr.to_string(),
-      }));
-    }
-
-    let output = Command::new("git")
-      .current_dir(target_path)
-      .args(["config", "user.email", email])
-      .output()
-      .context("Failed to configure git user.email")?;
-
-    if !output.status.success() {
-      let stderr = String::from_utf8_lossy(&output.stderr);
-      return Err(RailError::Git(GitError::CommandFailed {
-        command: "git config user.email".to_string(),
-        stderr: stderr.to_string(),
-      }));
+      configure_git_identity(&self.workspace_root, target_path)?;
     }
 
     Ok(())
@@ -607,7 +767,7 @@ impl Splitter {
     // Transform Cargo.toml manifest
     println!("   Transforming Cargo.toml");
     let manifest_path = target_repo_path.join("Cargo.toml");
-    self.apply_manifest_transform(&manifest_path, crate_name)?;
+    self.apply_manifest_transform(&manifest_path, crate_name, None)?;
 
     // Copy auxiliary files
     if !aux_files.is_empty() {
@@ -625,6 +785,7 @@ impl Splitter {
     target_repo_path: &Path,
     aux_files: &AuxiliaryFiles,
     crate_name: &str,
+    workspace_members: Option<&HashSet<String>>,
   ) -> RailResult<()> {
     for crate_path in crate_paths {
       let source_path = self.workspace_root.join(crate_path);
@@ -641,7 +802,12 @@ impl Splitter {
 
       // Transform Cargo.toml manifest
       let manifest_path = target_path.join("Cargo.toml");
-      self.apply_manifest_transform(&manifest_path, crate_name)?;
+      self.apply_manifest_transform(&manifest_path, crate_name, workspace_members)?;
+    }
+
+    if let Some(members) = workspace_members {
+      println!("   Generating workspace Cargo.toml");
+      self.write_workspace_manifest(target_repo_path, crate_paths, members)?;
     }
 
     // Copy auxiliary files
@@ -701,9 +867,77 @@ fn copy_directory_recursive_impl(source: &Path, target: &Path) -> RailResult<()>
   Ok(())
 }
 
+/// Configure git identity in the target repository by copying from source
+pub(crate) fn configure_git_identity(workspace_root: &Path, target_path: &Path) -> RailResult<()> {
+  use std::process::Command;
+
+  // Get identity from source repository
+  let user_name = Command::new("git")
+    .current_dir(workspace_root)
+    .args(["config", "user.name"])
+    .output()
+    .ok()
+    .and_then(|o| {
+      if o.status.success() {
+        Some(String::from_utf8_lossy(&o.stdout).trim().to_string())
+      } else {
+        None
+      }
+    });
+
+  let user_email = Command::new("git")
+    .current_dir(workspace_root)
+    .args(["config", "user.email"])
+    .output()
+    .ok()
+    .and_then(|o| {
+      if o.status.success() {
+        Some(String::from_utf8_lossy(&o.stdout).trim().to_string())
+      } else {
+        None
+      }
+    });
+
+  // Set identity in target repository
+  // Use a fallback if source doesn't have identity configured
+  let name = user_name.as_deref().unwrap_or("Cargo Rail");
+  let email = user_email.as_deref().unwrap_or("cargo-rail@localhost");
+
+  let output = Command::new("git")
+    .current_dir(target_path)
+    .args(["config", "user.name", name])
+    .output()
+    .context("Failed to configure git user.name")?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(RailError::Git(GitError::CommandFailed {
+      command: "git config user.name".to_string(),
+      stderr: stderr.to_string(),
+    }));
+  }
+
+  let output = Command::new("git")
+    .current_dir(target_path)
+    .args(["config", "user.email", email])
+    .output()
+    .context("Failed to configure git user.email")?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(RailError::Git(GitError::CommandFailed {
+      command: "git config user.email".to_string(),
+      stderr: stderr.to_string(),
+    }));
+  }
+
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::cargo::metadata::WorkspaceMetadata;
   use std::fs;
   use tempfile::TempDir;
 
@@ -731,7 +965,8 @@ mod tests {
     fs::create_dir(source.join(".git")).unwrap(); // Should be excluded
 
     let workspace_root = find_git_root();
-    let splitter = Splitter::new(workspace_root, SecurityConfig::default()).unwrap();
+    let transform = CargoTransform::new(WorkspaceMetadata::load(&workspace_root).unwrap());
+    let splitter = Splitter::new(workspace_root, transform, SecurityConfig::default()).unwrap();
 
     splitter.copy_directory_recursive(&source, &target).unwrap();
 
@@ -1,16 +1,26 @@
 use crate::core::error::RailResult;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use crate::cargo::helpers;
-use crate::cargo::metadata::WorkspaceMetadata;
-use crate::cargo::transform::{CargoTransform, TransformContext};
-use crate::core::config::{SecurityConfig, SplitMode};
-use crate::core::conflict::{ConflictInfo, ConflictResolver, ConflictStrategy};
+use crate::cargo::lockfile::{self, LockfileTarget};
+use crate::cargo::transform::{CargoTransform, DependencyOverrides, TransformContext};
+use crate::core::auth::GitAuth;
+use crate::core::config::{ForgeConfig, RemoteConfig, SecurityConfig, SplitMode, WorkspaceMode};
+use crate::core::conflict::{self, ConflictInfo, ConflictResolver, ConflictStrategy, MergeInput, MergeVersions};
+use crate::core::error::{GitError, RailError, ResultExt};
+use crate::core::history::{UNDO_TRAILER, is_ancestor};
+use crate::core::journal::{JournalDirection, SyncJournal, SyncLock, rail_dir};
 use crate::core::mapping::MappingStore;
+use crate::core::mirror;
+use crate::core::plan::{PlannedCommit, PlannedFile, SyncPreview};
+use crate::core::resolve::ConflictState;
 use crate::core::security::SecurityValidator;
-use crate::core::vcs::SystemGit;
+use crate::core::trust::{InboundPolicy, QuarantineReport, QuarantinedCommit};
+use crate::core::vcs::{CommitInfo, SystemGit};
+use crate::core::verify::BuildVerifier;
+use crate::forge;
 use crate::ui::progress::FileProgress;
 use crate::utils;
 
@@ -19,11 +29,69 @@ pub struct SyncConfig {
   pub crate_name: String,
   pub crate_paths: Vec<PathBuf>,
   pub mode: SplitMode,
+  pub workspace_mode: WorkspaceMode,
   pub target_repo_path: PathBuf,
   pub branch: String,
   pub remote_url: String,
+  /// Bundled / git sources for unpublished workspace dependencies
+  pub dependency_overrides: DependencyOverrides,
+  /// Subdirectory of the target repo holding the split (empty = repo root)
+  pub target_dir: PathBuf,
+  /// Commit a Cargo.lock derived from the mono lockfile
+  pub lockfile: bool,
+  /// Verify the split builds standalone before pushing
+  pub verify_build: bool,
+  /// Finish an interrupted sync recorded in the journal
+  pub resume: bool,
+  /// What to do when the remote has commits that were never imported
+  pub on_diverged: DivergencePolicy,
+  /// Exchange commits through a git bundle instead of the network
+  pub bundle: Option<BundleSync>,
+  /// Remotes pushed to after the primary
+  pub mirrors: Vec<RemoteConfig>,
+  /// Credentials for the remotes
+  pub auth: GitAuth,
+  /// Forge PRs are opened on for pushed PR branches
+  pub forge: Option<ForgeConfig>,
 }
 
+/// Offline sync through a `git bundle` carried by hand to or from the split repo
+#[derive(Debug, Clone)]
+pub enum BundleSync {
+  /// Write the split branch and rail notes to this bundle instead of pushing
+  To(PathBuf),
+  /// Read the split branch from this bundle instead of fetching
+  From(PathBuf),
+}
+
+impl SyncConfig {
+  /// Directory in the target repo the split tree lives in
+  pub fn split_root(&self) -> PathBuf {
+    if self.target_dir.as_os_str().is_empty() {
+      self.target_repo_path.clone()
+    } else {
+      self.target_repo_path.join(&self.target_dir)
+    }
+  }
+}
+
+/// What preparing a reused PR branch did to commits already on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PrBranchUpdate {
+  /// Newly created, appended to, or fast-forwarded to its base
+  Unchanged,
+  /// Squash-merged, so reset to its base; pushing it needs a force
+  Restarted,
+  /// Rebased onto its base; it's force-pushed even without new imports
+  Rebased,
+}
+
+/// Where branches read from a bundle are kept in the target clone
+const BUNDLE_REFS: &str = "refs/remotes/rail-bundle";
+
+/// Held while a sync writes to the monorepo, so parallel syncs take turns
+static MONO_WRITES: Mutex<()> = Mutex::new(());
+
 /// Result of a sync operation
 pub struct SyncResult {
   pub commits_synced: usize,
@@ -31,6 +99,8 @@ pub struct SyncResult {
   #[allow(dead_code)]
   pub direction: SyncDirection,
   pub conflicts: Vec<ConflictInfo>,
+  /// Remote commits held back by the inbound trust policy
+  pub quarantined: Vec<QuarantinedCommit>,
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +111,40 @@ pub enum SyncDirection {
   None,
 }
 
+/// What a mono → remote sync does when the split remote has commits that were never imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DivergencePolicy {
+  /// Stop before replaying anything (default for `--to-remote`)
+  #[default]
+  Abort,
+  /// Import the remote commits first, then replay on top (default for bidirectional syncs)
+  Import,
+  /// Replay the outgoing commits on top of the remote commits, leaving them for a later import
+  Rebase,
+}
+
+impl DivergencePolicy {
+  pub fn from_str(s: &str) -> RailResult<Self> {
+    match s.to_lowercase().as_str() {
+      "abort" => Ok(Self::Abort),
+      "import" => Ok(Self::Import),
+      "rebase" => Ok(Self::Rebase),
+      _ => Err(RailError::with_help(
+        format!("Invalid divergence policy '{}'", s),
+        "Valid options: abort, import, rebase",
+      )),
+    }
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Abort => "abort",
+      Self::Import => "import",
+      Self::Rebase => "rebase",
+    }
+  }
+}
+
 /// Result of conflict resolution containing both conflict info and changed files
 /// Changed files are cached for reuse in the apply step to avoid redundant git calls
 type ConflictResolutionResult = (Vec<ConflictInfo>, Vec<(PathBuf, char)>);
@@ -55,7 +159,17 @@ pub struct SyncEngine {
   /// Wrapped in Arc for cheap cloning in parallel execution
   security_config: Arc<SecurityConfig>,
   security_validator: SecurityValidator,
+  /// Which remote commits may be imported
+  inbound_policy: InboundPolicy,
   conflict_resolver: ConflictResolver,
+  /// Crates kept as workspace members (Some only for workspace-mode combined splits)
+  workspace_members: Option<HashSet<String>>,
+  /// Lockfiles derived from the mono Cargo.lock (Some only when `lockfile` is enabled)
+  lockfile_targets: Option<Vec<LockfileTarget>>,
+  /// Workspace crates shipped in the split
+  local_crates: HashSet<String>,
+  /// Held for the engine's lifetime so syncs of the same split never overlap
+  _lock: SyncLock,
   /// Track which repos we've loaded mappings from (to avoid redundant loads)
   loaded_repos: std::collections::HashSet<PathBuf>,
 }
@@ -63,15 +177,21 @@ pub struct SyncEngine {
 impl SyncEngine {
   pub fn new(
     workspace_root: PathBuf,
+    transform: CargoTransform,
     config: SyncConfig,
     security_config: Arc<SecurityConfig>,
     conflict_strategy: ConflictStrategy,
   ) -> RailResult<Self> {
-    let mono_git = SystemGit::open(&workspace_root)?;
+    let lock = SyncLock::acquire(&workspace_root, &config.crate_name)?;
     let mapping_store = MappingStore::new(config.crate_name.clone());
-    let metadata = WorkspaceMetadata::load(&workspace_root)?;
-    let transform = CargoTransform::new(metadata); // No clone needed - metadata moved into transform
-    let security_validator = SecurityValidator::new((*security_config).clone());
+    let transform = transform.with_dependency_overrides(config.dependency_overrides.clone());
+    let mut security_validator = SecurityValidator::new((*security_config).clone());
+    if security_validator.signing_key_path().is_some() {
+      security_validator = security_validator.with_allowed_signers(rail_dir(&workspace_root)?.join("allowed_signers"));
+    }
+    let mono_git = SystemGit::open(&workspace_root)?.with_signing_key(security_validator.signing_key_path());
+    let inbound_policy =
+      InboundPolicy::from_config(&security_config, &workspace_root).with_crate_paths(config.crate_paths.clone());
 
     // Create unique temporary directory for conflict resolution (avoid conflicts in parallel tests)
     let temp_dir = std::env::temp_dir().join(format!(
@@ -86,6 +206,16 @@ impl SyncEngine {
     std::fs::create_dir_all(&temp_dir)?;
     let conflict_resolver = ConflictResolver::new(conflict_strategy, temp_dir);
 
+    let workspace_members = match (&config.mode, &config.workspace_mode) {
+      (SplitMode::Combined, WorkspaceMode::Workspace) => Some(transform.crate_names_for_paths(&config.crate_paths)),
+      _ => None,
+    };
+
+    let lockfile_targets = config
+      .lockfile
+      .then(|| lockfile::lockfile_targets(&transform, &config.mode, &config.workspace_mode, &config.crate_paths));
+    let local_crates = transform.crate_names_for_paths(&config.crate_paths);
+
     Ok(Self {
       workspace_root,
       config,
@@ -94,7 +224,12 @@ impl SyncEngine {
       transform,
       security_config,
       security_validator,
+      inbound_policy,
       conflict_resolver,
+      workspace_members,
+      lockfile_targets,
+      local_crates,
+      _lock: lock,
       loaded_repos: std::collections::HashSet::new(),
     })
   }
@@ -108,9 +243,25 @@ impl SyncEngine {
     Ok(())
   }
 
+  /// Path in the remote repo whose history belongs to the split
+  fn remote_subtree(&self) -> &Path {
+    if self.config.target_dir.as_os_str().is_empty() {
+      Path::new(".")
+    } else {
+      &self.config.target_dir
+    }
+  }
+
+  /// No network access to the remote: local remotes, and syncs through a bundle
+  fn is_offline(&self) -> bool {
+    utils::is_local_path(&self.config.remote_url) || self.config.bundle.is_some()
+  }
+
   /// Get the appropriate branch reference (origin/branch for remotes, just branch for local)
   fn get_branch_ref(&self) -> String {
-    if utils::is_local_path(&self.config.remote_url) {
+    if let Some(BundleSync::From(_)) = self.config.bundle {
+      format!("{}/{}", BUNDLE_REFS, self.config.branch)
+    } else if self.is_offline() {
       self.config.branch.clone()
     } else {
       format!("origin/{}", self.config.branch)
@@ -121,8 +272,8 @@ impl SyncEngine {
     println!("   Syncing monorepo → remote...");
 
     // Validate SSH key before any remote operations
-    if !utils::is_local_path(&self.config.remote_url) {
-      self.security_validator.validate_ssh_key()?;
+    if !self.is_offline() {
+      self.config.auth.validate(&self.security_validator)?;
       self.security_validator.validate_signing_key()?;
     }
 
@@ -132,12 +283,16 @@ impl SyncEngine {
 
     // Open remote repo
     let target_repo_path = self.config.target_repo_path.clone();
-    let remote_git = SystemGit::open(&target_repo_path)?;
+    let remote_git = SystemGit::open(&target_repo_path)?
+      .with_auth(self.config.auth.clone())
+      .with_signing_key(self.security_validator.signing_key_path());
 
-    // Fetch latest from remote (skip for local paths)
-    if !utils::is_local_path(&self.config.remote_url) {
+    // Fetch latest from remote (skip for local paths and bundles)
+    if !self.is_offline() {
       remote_git.fetch_from_remote("origin")?;
-      self.mapping_store.fetch_notes(&target_repo_path, "origin")?;
+      self
+        .mapping_store
+        .fetch_notes(&target_repo_path, "origin", &self.config.auth)?;
     } else {
       println!("   Skipping fetch (local testing mode)");
     }
@@ -146,15 +301,38 @@ impl SyncEngine {
     self.loaded_repos.remove(&target_repo_path);
     self.ensure_mappings_loaded(&target_repo_path)?;
 
-    // Find last synced commit in mono
+    // Squash-merged PR branches count as synced, so their content isn't echoed back
+    let repointed = self.reconcile_merged_imports()?;
+    self.save_repointed(&repointed)?;
+
+    // Find last synced commit in mono (before an import adds mapped commits on top of it)
     let last_synced_mono = self.find_last_synced_mono_commit()?;
 
+    // The remote branch as fetched; the push only goes through if it is still there
+    let remote_tip = remote_git.resolve_reference(&self.get_branch_ref()).ok();
+
+    // Outgoing commits build on the remote branch, unless an interrupted sync already started
+    let mut imported = None;
+    if SyncJournal::load(&self.workspace_root, &self.config.crate_name)?.is_none()
+      && let Some(ref tip) = remote_tip
+    {
+      imported = self.reconcile_remote_head(&remote_git, tip)?;
+    }
+
+    // Pick up (or start) the journal before looking at what still needs syncing
+    let (mut journal, resumed) = self.open_journal(
+      JournalDirection::ToRemote,
+      &remote_git.head_commit()?,
+      &remote_git.current_branch()?,
+    )?;
+
     // Get new commits in mono that touch any of the crate paths (handles both single and combined modes)
     let new_commits =
       self
         .mono_git
         .get_commits_touching_paths(&self.config.crate_paths, last_synced_mono.as_deref(), "HEAD")?;
 
+    let mut synced_count = 0;
     if new_commits.is_empty() {
       println!("   No new commits to sync");
     } else {
@@ -165,18 +343,11 @@ impl SyncEngine {
         format!("Syncing {} commits to remote", new_commits.len()),
       );
 
-      let mut synced_count = 0;
       let mut current_remote_head = remote_git.head_commit()?; // Cache HEAD, update after each commit
 
       for commit in &new_commits {
-        // Skip if already synced
-        if self.mapping_store.has_mapping(&commit.sha) {
-          progress.inc();
-          continue;
-        }
-
-        // Skip if this commit came from remote (check trailer)
-        if commit.message.contains("Rail-Origin: remote@") {
+        // Skip commits already synced, from remote, or reverting an undone sync
+        if !self.should_replay_to_remote(commit) {
           progress.inc();
           continue;
         }
@@ -184,60 +355,304 @@ impl SyncEngine {
         // Apply commit to remote
         let remote_sha = self.apply_mono_commit_to_remote(commit, &remote_git, &current_remote_head)?;
 
-        // Record mapping
+        // Journal first so an interruption from here on is recoverable, then record mapping
+        journal.record(&commit.sha, &remote_sha)?;
         self.mapping_store.record_mapping(&commit.sha, &remote_sha)?;
         synced_count += 1;
         current_remote_head = remote_sha.clone(); // Update cached HEAD
 
         progress.inc();
       }
-
-      // Save mappings after processing commits
-      self.mapping_store.save(&self.workspace_root)?;
-      self.mapping_store.save(&self.config.target_repo_path)?;
-
-      // Push to remote (skip for local paths)
-      if synced_count > 0 && !utils::is_local_path(&self.config.remote_url) {
-        remote_git.push_to_remote("origin", &self.config.branch)?;
-        self.mapping_store.push_notes(&self.config.target_repo_path, "origin")?;
-      }
-
-      return Ok(SyncResult {
-        commits_synced: synced_count,
-        direction: SyncDirection::MonoToRemote,
-        conflicts: Vec::new(),
-      });
     }
 
-    let synced_count = 0;
+    // Commits applied before an interruption still need verifying and pushing
+    let needs_push = synced_count > 0 || (resumed && !journal.applied.is_empty());
+
+    // Verify the split builds on its own before its commits are recorded as synced or pushed
+    if needs_push && self.config.verify_build {
+      BuildVerifier::new(
+        self.workspace_root.clone(),
+        self.config.crate_paths.clone(),
+        self.config.mode.clone(),
+      )
+      .with_subdir(self.config.target_dir.clone())
+      .verify(&self.config.target_repo_path, &remote_git.head_commit()?)?;
+    }
 
-    // Save mappings
+    // Save mappings after processing commits
     self.mapping_store.save(&self.workspace_root)?;
     self.mapping_store.save(&self.config.target_repo_path)?;
 
-    // Push to remote (skip for local paths)
-    if synced_count > 0 {
+    // Push to remote (skip for local paths; bundles carry the branch instead)
+    if let Some(BundleSync::To(ref bundle)) = self.config.bundle {
+      if needs_push {
+        write_bundle(
+          &self.config.target_repo_path,
+          bundle,
+          &self.config.branch,
+          &self.config.crate_name,
+        )?;
+      } else {
+        println!("   No new commits - no bundle written");
+      }
+    } else if needs_push {
       if !utils::is_local_path(&self.config.remote_url) {
-        remote_git.push_to_remote("origin", &self.config.branch)?;
-        self.mapping_store.push_notes(&self.config.target_repo_path, "origin")?;
+        remote_git.push_with_lease("origin", &self.config.branch, remote_tip.as_deref())?;
+        self
+          .mapping_store
+          .push_notes(&self.config.target_repo_path, "origin", &self.config.auth)?;
       } else {
         println!("   Skipping push (local testing mode)");
       }
     }
 
+    journal.finish()?;
+
+    // Mirrors follow the primary on every sync, so one that failed before catches up
+    if !self.config.mirrors.is_empty() && self.config.bundle.is_none() {
+      mirror::report(&mirror::push_to_mirrors(
+        &self.config.target_repo_path,
+        &self.config.mirrors,
+        &self.config.branch,
+        &self.config.crate_name,
+        &self.config.auth,
+        &self.workspace_root,
+      ))?;
+    }
+
+    let (imported_count, conflicts, quarantined) = imported.map_or((0, Vec::new(), Vec::new()), |r| {
+      (r.commits_synced, r.conflicts, r.quarantined)
+    });
     Ok(SyncResult {
-      commits_synced: synced_count,
+      commits_synced: synced_count + imported_count,
       direction: SyncDirection::MonoToRemote,
-      conflicts: Vec::new(),
+      conflicts,
+      quarantined,
     })
   }
 
+  /// Move the target clone to the remote branch before outgoing commits are replayed on it
+  ///
+  /// Remote commits touching the split that were never imported mean the remote diverged from
+  /// the last sync; `on_diverged` decides whether to import them first, replay on top of them,
+  /// or stop. Returns the import's result when one ran.
+  fn reconcile_remote_head(&mut self, remote_git: &SystemGit, remote_tip: &str) -> RailResult<Option<SyncResult>> {
+    let unimported = self.unimported_remote_commits(remote_git, remote_tip)?;
+
+    let imported = if unimported.is_empty() {
+      None
+    } else {
+      match self.config.on_diverged {
+        DivergencePolicy::Abort => return Err(diverged(&self.config.crate_name, &unimported)),
+        DivergencePolicy::Import => {
+          println!(
+            "   ⚠️  Remote has {} commit(s) not yet imported; importing them first",
+            unimported.len()
+          );
+          Some(self.sync_from_remote()?)
+        }
+        DivergencePolicy::Rebase => {
+          println!(
+            "   ⚠️  Remote has {} commit(s) not yet imported; replaying on top of them",
+            unimported.len()
+          );
+          None
+        }
+      }
+    };
+
+    let head = remote_git.head_commit()?;
+    if head != remote_tip && !is_ancestor(&self.config.target_repo_path, remote_tip, &head)? {
+      if !is_ancestor(&self.config.target_repo_path, &head, remote_tip)? {
+        return Err(RailError::with_help(
+          format!(
+            "{} has commits that are not on the remote '{}' branch",
+            self.config.target_repo_path.display(),
+            self.config.branch
+          ),
+          "Push or drop them in the split clone, then sync again",
+        ));
+      }
+      remote_git.reset_hard(remote_tip)?;
+    }
+
+    Ok(imported)
+  }
+
+  /// Check out the PR branch a sync off the protected branch `base` commits onto
+  ///
+  /// A timestamped pattern gets a fresh branch every sync. A stable one keeps a single branch
+  /// per crate (also picked up from `origin`): new imports are appended to it, it starts over
+  /// from `base` once merged (squash merges included), and when `base` moved on it's rebased
+  /// if every commit on it is an import that still applies cleanly.
+  fn checkout_pr_branch(&mut self, pr_branch: &str, base: &str) -> RailResult<PrBranchUpdate> {
+    let local = self
+      .mono_git
+      .resolve_reference(&format!("refs/heads/{}", pr_branch))
+      .is_ok();
+    let tracking = format!("refs/remotes/origin/{}", pr_branch);
+    let on_origin = self.mono_git.resolve_reference(&tracking).is_ok();
+
+    if !self.security_validator.reuses_pr_branch() || !(local || on_origin) {
+      println!("   📝 Creating PR branch: {}", pr_branch);
+      self.mono_git.create_and_checkout_branch(pr_branch)?;
+      return Ok(PrBranchUpdate::Unchanged);
+    }
+
+    let base_tip = self.mono_git.head_commit()?;
+    if !local {
+      let output = self
+        .mono_git
+        .git_cmd()
+        .args(["branch", "--track", pr_branch, &tracking])
+        .output()
+        .context("Failed to create PR branch")?;
+      if !output.status.success() {
+        return Err(RailError::Git(GitError::CommandFailed {
+          command: "git branch --track".to_string(),
+          stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        }));
+      }
+    }
+    self.mono_git.checkout_branch(pr_branch)?;
+    let branch_tip = self.mono_git.head_commit()?;
+
+    if is_ancestor(&self.workspace_root, &branch_tip, &base_tip)? {
+      if branch_tip != base_tip {
+        println!(
+          "   📝 PR branch {} was merged; starting it over from {}",
+          pr_branch, base
+        );
+        self.mono_git.reset_hard(&base_tip)?;
+      } else {
+        println!("   📝 Reusing PR branch: {}", pr_branch);
+      }
+      return Ok(PrBranchUpdate::Unchanged);
+    }
+    if is_ancestor(&self.workspace_root, &base_tip, &branch_tip)? {
+      println!("   📝 Appending to PR branch: {}", pr_branch);
+      return Ok(PrBranchUpdate::Unchanged);
+    }
+
+    // Imports merged into base by a squash or forge rebase are left behind
+    let mut pending = Vec::new();
+    for commit in self.pr_branch_commits(&base_tip, &branch_tip)? {
+      if !self.import_merged_into(&commit, &base_tip)? {
+        pending.push(commit);
+      }
+    }
+    if pending.is_empty() {
+      println!(
+        "   📝 PR branch {} was squash-merged; starting it over from {}",
+        pr_branch, base
+      );
+      self.mono_git.reset_hard(&base_tip)?;
+      return Ok(PrBranchUpdate::Restarted);
+    }
+
+    if self.rebase_pr_branch(&base_tip, &pending)? {
+      println!("   📝 Rebased PR branch {} onto {}", pr_branch, base);
+      Ok(PrBranchUpdate::Rebased)
+    } else {
+      println!(
+        "   ⚠️  PR branch {} can't be rebased onto {} safely; appending to it",
+        pr_branch, base
+      );
+      self.mono_git.reset_hard(&branch_tip)?;
+      Ok(PrBranchUpdate::Unchanged)
+    }
+  }
+
+  /// Commits on a PR branch that aren't on its base, oldest first
+  fn pr_branch_commits(&self, base_tip: &str, branch_tip: &str) -> RailResult<Vec<CommitInfo>> {
+    let output = self
+      .mono_git
+      .git_cmd()
+      .args(["rev-list", "--reverse", &format!("{}..{}", base_tip, branch_tip)])
+      .output()
+      .context("Failed to list PR branch commits")?;
+    if !output.status.success() {
+      return Err(RailError::Git(GitError::CommandFailed {
+        command: "git rev-list".to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+      }));
+    }
+    let shas: Vec<String> = String::from_utf8_lossy(&output.stdout)
+      .lines()
+      .map(str::to_string)
+      .collect();
+    self.mono_git.get_commits_bulk(&shas)
+  }
+
+  /// Replay imported PR branch commits onto `base_tip`
+  ///
+  /// Refuses (returning false, worktree to be reset by the caller) when the branch holds
+  /// anything but imports - a reviewer's fixup, a merge - or an import no longer applies
+  /// cleanly. Mappings and notes of the replayed imports move to their new commits.
+  fn rebase_pr_branch(&mut self, base_tip: &str, commits: &[CommitInfo]) -> RailResult<bool> {
+    let imports_only = commits.iter().all(|c| {
+      c.parent_shas.len() == 1 && c.message.contains("Rail-Origin: remote@") && self.mapping_store.has_mapping(&c.sha)
+    });
+    if !imports_only {
+      return Ok(false);
+    }
+
+    self.mono_git.reset_hard(base_tip)?;
+    let mut head = base_tip.to_string();
+    let mut rewritten = Vec::new();
+    for commit in commits {
+      // An import that no longer changes anything is already in base: not a clean replay either
+      let applied = self.mono_git.cherry_pick_no_commit(&commit.sha)?
+        && !self
+          .mono_git
+          .git_cmd()
+          .args(["diff", "--cached", "--quiet", "HEAD"])
+          .status()
+          .context("Failed to run git diff")?
+          .success();
+      if !applied {
+        return Ok(false);
+      }
+
+      head = self.mono_git.create_commit_with_metadata(
+        &commit.message,
+        &commit.author,
+        &commit.author_email,
+        commit.timestamp,
+        std::slice::from_ref(&head),
+      )?;
+      rewritten.push((commit.sha.clone(), head.clone()));
+    }
+
+    for (old, new) in &rewritten {
+      self.mapping_store.repoint_mapping(old, new)?;
+    }
+    self.save_repointed(&rewritten)?;
+
+    Ok(true)
+  }
+
+  /// Remote commits touching the split since the last sync that never made it into the monorepo
+  fn unimported_remote_commits(&self, remote_git: &SystemGit, remote_tip: &str) -> RailResult<Vec<CommitInfo>> {
+    let last_synced_remote = self.find_last_synced_remote_commit(remote_git)?;
+    let commits =
+      remote_git.get_commits_touching_path(self.remote_subtree(), last_synced_remote.as_deref(), remote_tip)?;
+    Ok(
+      commits
+        .into_iter()
+        .filter(|commit| self.should_replay_from_remote(commit))
+        .collect(),
+    )
+  }
+
   pub fn sync_from_remote(&mut self) -> RailResult<SyncResult> {
+    // Splits synced in parallel all commit into the same monorepo worktree
+    let _mono_writes = MONO_WRITES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
     println!("   Syncing remote → monorepo...");
 
     // Validate SSH key before any remote operations
-    if !utils::is_local_path(&self.config.remote_url) {
-      self.security_validator.validate_ssh_key()?;
+    if !self.is_offline() {
+      self.config.auth.validate(&self.security_validator)?;
       self.security_validator.validate_signing_key()?;
     }
 
@@ -245,31 +660,50 @@ impl SyncEngine {
     let current_branch = self.mono_git.current_branch()?;
     let needs_pr_branch = self.security_config.protected_branches.contains(&current_branch);
 
+    // Load mappings (cached - only loads if not already loaded)
+    let workspace_root = self.workspace_root.clone();
+    self.ensure_mappings_loaded(&workspace_root)?;
+
+    // Squash-merged PR branches count as synced (before a reused one is looked at)
+    let repointed = self.reconcile_merged_imports()?;
+    self.save_repointed(&repointed)?;
+
+    // A rebased PR branch is force-pushed, but only over what we last saw on origin
+    let mut pr_branch_lease = None;
+    let mut pr_branch_update = PrBranchUpdate::Unchanged;
     let pr_branch_name = if needs_pr_branch {
       let pr_branch = self.security_validator.generate_pr_branch(&self.config.crate_name);
       println!("   ⚠️  Current branch '{}' is protected", current_branch);
-      println!("   📝 Creating PR branch: {}", pr_branch);
 
-      // Create and checkout the PR branch
-      self.mono_git.create_and_checkout_branch(&pr_branch)?;
+      pr_branch_lease = self
+        .mono_git
+        .resolve_reference(&format!("refs/remotes/origin/{}", pr_branch))
+        .ok();
+      pr_branch_update = self.checkout_pr_branch(&pr_branch, &current_branch)?;
 
       Some(pr_branch)
     } else {
       None
     };
 
-    // Load mappings (cached - only loads if not already loaded)
-    let workspace_root = self.workspace_root.clone();
-    self.ensure_mappings_loaded(&workspace_root)?;
-
     // Open remote repo
     let target_repo_path = self.config.target_repo_path.clone();
-    let remote_git = SystemGit::open(&target_repo_path)?;
-
-    // Fetch latest from remote (skip for local paths)
-    if !utils::is_local_path(&self.config.remote_url) {
+    let remote_git = SystemGit::open(&target_repo_path)?.with_auth(self.config.auth.clone());
+
+    // Fetch latest from remote (skip for local paths; bundles are read instead)
+    if let Some(BundleSync::From(ref bundle)) = self.config.bundle {
+      fetch_bundle(
+        &remote_git,
+        &self.config.target_repo_path,
+        bundle,
+        &self.config.branch,
+        true,
+      )?;
+    } else if !utils::is_local_path(&self.config.remote_url) {
       remote_git.fetch_from_remote("origin")?;
-      self.mapping_store.fetch_notes(&target_repo_path, "origin")?;
+      self
+        .mapping_store
+        .fetch_notes(&target_repo_path, "origin", &self.config.auth)?;
     } else {
       println!("   Skipping fetch (local testing mode)");
     }
@@ -278,18 +712,32 @@ impl SyncEngine {
     self.loaded_repos.remove(&target_repo_path);
     self.ensure_mappings_loaded(&target_repo_path)?;
 
+    // A sync stopped on conflicts goes on with the commit it stopped on
+    if self.config.resume {
+      self.commit_resolved_conflicts()?;
+    }
+
+    // Pick up (or start) the journal before looking at what still needs syncing
+    let (mut journal, resumed) = self.open_journal(
+      JournalDirection::FromRemote,
+      &self.mono_git.head_commit()?,
+      &self.mono_git.current_branch()?,
+    )?;
+
     // Find last synced commit in remote
     let last_synced_remote = self.find_last_synced_remote_commit(&remote_git)?;
 
     // Get new commits in remote
     let branch_ref = self.get_branch_ref();
     let new_commits = if let Some(ref last) = last_synced_remote {
-      remote_git.get_commits_touching_path(Path::new("."), Some(last), &branch_ref)?
+      remote_git.get_commits_touching_path(self.remote_subtree(), Some(last), &branch_ref)?
     } else {
-      remote_git.get_commits_touching_path(Path::new("."), None, &branch_ref)?
+      remote_git.get_commits_touching_path(self.remote_subtree(), None, &branch_ref)?
     };
 
     let mut conflicts = Vec::new();
+    let mut quarantined: Vec<QuarantinedCommit> = Vec::new();
+    let mut imported = Vec::new();
 
     let synced_count = if new_commits.is_empty() {
       println!("   No new commits to sync");
@@ -306,25 +754,62 @@ impl SyncEngine {
       let mut current_mono_head = self.mono_git.head_commit()?; // Cache HEAD, update after each commit
 
       for commit in &new_commits {
-        // Skip if this commit came from mono (check trailer)
-        if commit.message.contains("Rail-Origin: mono@") {
+        // Skip commits from mono, reverting an undone sync, or already synced
+        if !self.should_replay_from_remote(commit) {
+          progress.inc();
+          continue;
+        }
+
+        // Hold back the first untrusted commit and everything built on it
+        if let Some(held) = quarantined.first() {
+          let reason = format!("builds on held-back commit {}", &held.sha[..7]);
+          quarantined.push(QuarantinedCommit::new(commit, reason));
+          progress.inc();
+          continue;
+        }
+        if self.inbound_policy.is_enforced()
+          && let Some(reason) = self.inbound_policy.check(&target_repo_path, commit)?
+        {
+          quarantined.push(QuarantinedCommit::new(commit, reason));
           progress.inc();
           continue;
         }
 
-        // Skip if already synced (O(1) reverse mapping lookup)
-        if self.mapping_store.has_reverse_mapping(&commit.sha) {
+        // Nothing is written for a commit reaching protected paths or outside the split
+        let changed_files = self.split_changed_files(&remote_git, &commit.sha)?;
+        let mono_paths: Vec<PathBuf> = changed_files
+          .iter()
+          .filter_map(|(remote_path, _)| self.importable_remote_path(remote_path))
+          .collect();
+        if let Some(held) = self.inbound_policy.check_paths(commit, &mono_paths) {
+          quarantined.push(held);
           progress.inc();
           continue;
         }
 
         // Resolve conflicts using 3-way merge (returns conflicts + changed_files for caching)
-        let (conflict_infos, changed_files) = self.resolve_conflicts_for_commit(commit, &remote_git)?;
+        let (conflict_infos, changed_files) = self.resolve_conflicts_for_commit(commit, &remote_git, changed_files)?;
 
         // Collect paths of resolved files (don't overwrite these in apply_remote_commit_to_mono)
         // Using HashSet for O(1) membership testing instead of O(n)
         let resolved_files: HashSet<PathBuf> = conflict_infos.iter().map(|c| c.file_path.clone()).collect();
 
+        // Unresolved conflicts stop the sync: the commit waits for `cargo rail resolve`
+        if conflict_infos.iter().any(|c| !c.resolved) {
+          self.write_remote_commit_files(commit, &remote_git, &resolved_files, &changed_files)?;
+          let state = ConflictState::save(
+            &self.workspace_root,
+            &self.config.crate_name,
+            &self.config.remote_url,
+            self.conflict_resolver.strategy(),
+            &self.mono_git.current_branch()?,
+            &current_mono_head,
+            commit,
+            &conflict_infos,
+          )?;
+          return Err(stopped_on_conflicts(&state));
+        }
+
         if !conflict_infos.is_empty() {
           conflicts.extend(conflict_infos);
           // Continue applying commit - files already merged by conflict resolver
@@ -334,8 +819,10 @@ impl SyncEngine {
         let mono_sha =
           self.apply_remote_commit_to_mono(commit, &remote_git, &resolved_files, &current_mono_head, &changed_files)?;
 
-        // Record mapping (remote -> mono)
+        // Journal first so an interruption from here on is recoverable, then record mapping (remote -> mono)
+        journal.record(&mono_sha, &commit.sha)?;
         self.mapping_store.record_mapping(&mono_sha, &commit.sha)?;
+        imported.push(commit.clone());
         count += 1;
         current_mono_head = mono_sha.clone(); // Update cached HEAD
 
@@ -348,21 +835,68 @@ impl SyncEngine {
     // Save mappings
     self.mapping_store.save(&self.workspace_root)?;
 
+    self.quarantine(&quarantined)?;
+
+    // Commits applied before an interruption count toward what gets pushed
+    let synced_count = if resumed {
+      synced_count.max(journal.applied.len())
+    } else {
+      synced_count
+    };
+
     // If we created a PR branch, push it to remote and remind user to create PR
     if let Some(ref pr_branch) = pr_branch_name {
       println!("\n   🎯 Changes synced to PR branch: {}", pr_branch);
 
       // Push PR branch to remote (skip for local testing)
-      if !utils::is_local_path(&self.config.remote_url) && synced_count > 0 {
+      if !utils::is_local_path(&self.config.remote_url)
+        && (synced_count > 0 || pr_branch_update == PrBranchUpdate::Rebased)
+      {
         println!("   📤 Pushing PR branch to remote...");
-        self.mono_git.push_to_remote("origin", pr_branch)?;
+        if pr_branch_update != PrBranchUpdate::Unchanged {
+          self
+            .mono_git
+            .push_with_lease("origin", pr_branch, pr_branch_lease.as_deref())?;
+        } else {
+          self.mono_git.push_to_remote("origin", pr_branch)?;
+        }
         println!("   ✅ PR branch pushed to origin/{}", pr_branch);
 
-        println!("\n   📝 Next step:");
-        println!("      • Create a pull request on GitHub/GitLab:");
-        println!("        {} → {}", pr_branch, current_branch);
-        println!("      • Or visit your repository's PR creation page");
-      } else if synced_count == 0 {
+        match self.config.forge {
+          Some(ref forge_config) => {
+            // The sync itself is done; a PR that couldn't be opened can still be opened by hand
+            let spec = forge::PullRequestSpec {
+              head: pr_branch.clone(),
+              base: current_branch.clone(),
+              title: format!("Sync {} from its split repository", self.config.crate_name),
+              body: forge::pull_request_body(&self.config.crate_name, &imported, &conflicts, &quarantined),
+              labels: forge_config.labels.clone(),
+              reviewers: forge_config.reviewers.clone(),
+            };
+            let opened =
+              forge::client(forge_config).and_then(|client| Ok((client.name(), client.open_or_update(&spec)?)));
+            match opened {
+              Ok((forge_name, pr)) => println!(
+                "   🔗 {} {} pull request #{}: {}",
+                if pr.created { "Opened" } else { "Updated" },
+                forge_name,
+                pr.number,
+                pr.url
+              ),
+              Err(e) => eprintln!(
+                "   ⚠️  Could not open a pull request for {} → {}: {}",
+                pr_branch, current_branch, e
+              ),
+            }
+          }
+          None => {
+            println!("\n   📝 Next step:");
+            println!("      • Create a pull request on GitHub/GitLab:");
+            println!("        {} → {}", pr_branch, current_branch);
+            println!("      • Or visit your repository's PR creation page");
+          }
+        }
+      } else if synced_count == 0 && pr_branch_update != PrBranchUpdate::Rebased {
         println!("   ℹ️  No new commits to sync - PR branch not pushed");
         println!("   📝 To review: git diff {}..{}", current_branch, pr_branch);
       } else {
@@ -385,16 +919,51 @@ impl SyncEngine {
       );
     }
 
+    journal.finish()?;
+
+    if self.inbound_policy.rejects_protected_paths()
+      && let Some(held) = quarantined.iter().find(|c| !c.paths.is_empty())
+    {
+      return Err(RailError::with_help(
+        format!("Remote commit {} {}", &held.sha[..7], held.reason),
+        format!(
+          "inbound_protected_action = \"reject\"; review it on branch {}",
+          QuarantineReport::branch_name(&self.config.crate_name)
+        ),
+      ));
+    }
+
     Ok(SyncResult {
       commits_synced: synced_count,
       direction: SyncDirection::RemoteToMono,
       conflicts,
+      quarantined,
     })
   }
 
   pub fn sync_bidirectional(&mut self) -> RailResult<SyncResult> {
+    // Finish an interrupted sync first; its direction is recorded in the journal
+    let resumed_count = match SyncJournal::load(&self.workspace_root, &self.config.crate_name)?.map(|j| j.direction) {
+      Some(JournalDirection::ToRemote) => self.sync_to_remote()?.commits_synced,
+      Some(JournalDirection::FromRemote) => self.sync_from_remote()?.commits_synced,
+      None => 0,
+    };
+
+    let mut result = self.sync_detected_changes()?;
+    result.commits_synced += resumed_count;
+    Ok(result)
+  }
+
+  /// Sync whichever sides have changes since the last sync
+  fn sync_detected_changes(&mut self) -> RailResult<SyncResult> {
     println!("   Detecting changes...");
 
+    // Both checks look for the last mapped commit on their side
+    let workspace_root = self.workspace_root.clone();
+    let target_repo_path = self.config.target_repo_path.clone();
+    self.ensure_mappings_loaded(&workspace_root)?;
+    self.ensure_mappings_loaded(&target_repo_path)?;
+
     // Check both directions
     let mono_has_changes = self.check_mono_has_changes()?;
     let remote_has_changes = self.check_remote_has_changes()?;
@@ -410,13 +979,17 @@ impl SyncEngine {
       }
       (true, true) => {
         println!("   Both sides have changes, syncing both directions");
-        let to_remote = self.sync_to_remote()?;
+        // Remote commits are unimported at this point; `on_diverged` decides how to_remote treats them
+        let mut to_remote = self.sync_to_remote()?;
         let from_remote = self.sync_from_remote()?;
+        to_remote.conflicts.extend(from_remote.conflicts);
+        to_remote.quarantined.extend(from_remote.quarantined);
 
         Ok(SyncResult {
           commits_synced: to_remote.commits_synced + from_remote.commits_synced,
           direction: SyncDirection::Both,
-          conflicts: from_remote.conflicts,
+          conflicts: to_remote.conflicts,
+          quarantined: to_remote.quarantined,
         })
       }
       (false, false) => {
@@ -425,38 +998,263 @@ impl SyncEngine {
           commits_synced: 0,
           direction: SyncDirection::None,
           conflicts: Vec::new(),
+          quarantined: Vec::new(),
         })
       }
     }
   }
 
-  // Helper methods
+  /// Predict what a sync would replay, without writing to either repository
+  ///
+  /// Lists the commits each direction would replay and the files they write on the receiving
+  /// side. For remote → mono, files changed on both sides since the last sync go through
+  /// `ConflictResolver`; which of them will conflict is predicted with `git merge-tree`.
+  pub fn preview(&mut self, direction: &SyncDirection) -> RailResult<SyncPreview> {
+    let workspace_root = self.workspace_root.clone();
+    self.ensure_mappings_loaded(&workspace_root)?;
 
-  fn find_last_synced_mono_commit(&self) -> RailResult<Option<String>> {
-    // Find the most recent mono commit that has a mapping
-    let commits = self.mono_git.commit_history(Path::new("."), Some(100))?;
+    let target_repo_path = self.config.target_repo_path.clone();
+    let remote_git = SystemGit::open(&target_repo_path)?.with_auth(self.config.auth.clone());
+    if let Some(BundleSync::From(ref bundle)) = self.config.bundle {
+      fetch_bundle(&remote_git, &target_repo_path, bundle, &self.config.branch, false)?;
+    } else if !self.is_offline() {
+      remote_git.fetch_from_remote("origin")?;
+      self
+        .mapping_store
+        .fetch_notes(&target_repo_path, "origin", &self.config.auth)?;
+      self.loaded_repos.remove(&target_repo_path);
+    }
+    self.ensure_mappings_loaded(&target_repo_path)?;
 
-    for commit in commits {
-      if self.mapping_store.has_mapping(&commit.sha) {
-        return Ok(Some(commit.sha));
+    let mut preview = SyncPreview::default();
+
+    if matches!(direction, SyncDirection::MonoToRemote | SyncDirection::Both) {
+      // In memory only: squash-merged PR branches aren't previewed as outgoing
+      self.reconcile_merged_imports()?;
+      let last_synced = self.find_last_synced_mono_commit()?;
+      let commits =
+        self
+          .mono_git
+          .get_commits_touching_paths(&self.config.crate_paths, last_synced.as_deref(), "HEAD")?;
+
+      for commit in commits.iter().filter(|c| self.should_replay_to_remote(c)) {
+        let mut files = Vec::new();
+        for (mono_path, change) in self.mono_commit_files(&commit.sha)? {
+          files.push(planned_file(&self.map_mono_path_to_remote(&mono_path)?, change));
+        }
+        preview.to_remote.push(planned_commit(commit, files, Vec::new()));
+      }
+    }
+
+    if matches!(direction, SyncDirection::RemoteToMono | SyncDirection::Both) {
+      let last_synced_remote = self.find_last_synced_remote_commit(&remote_git)?;
+      let commits = remote_git.get_commits_touching_path(
+        self.remote_subtree(),
+        last_synced_remote.as_deref(),
+        &self.get_branch_ref(),
+      )?;
+
+      // Same base as resolve_conflicts_for_commit: mono changes since the last synced commit
+      let last_synced_mono = self.find_last_synced_mono_commit()?;
+      let mono_changed: HashSet<PathBuf> = match last_synced_mono {
+        Some(ref last) => self
+          .mono_git
+          .get_changed_files_between(last, "HEAD")?
+          .into_iter()
+          .map(|(path, _)| path)
+          .collect(),
+        None => HashSet::new(),
+      };
+
+      for commit in commits.iter().filter(|c| self.should_replay_from_remote(c)) {
+        let mut files = Vec::new();
+        let mut merges = Vec::new();
+        for (remote_path, change) in remote_git.get_changed_files(&commit.sha)? {
+          if !remote_path.starts_with(&self.config.target_dir) {
+            continue;
+          }
+          let Some(mono_path) = self.importable_remote_path(&remote_path) else {
+            continue;
+          };
+
+          files.push(planned_file(&mono_path, change));
+          if change != 'D' && mono_changed.contains(&mono_path) && self.workspace_root.join(&mono_path).exists() {
+            merges.push((remote_path, mono_path));
+          }
+        }
+
+        let conflicts =
+          self.predict_commit_conflicts(&remote_git, &commit.sha, last_synced_mono.as_deref(), &merges)?;
+        preview.from_remote.push(planned_commit(commit, files, conflicts));
       }
     }
 
-    Ok(None)
+    Ok(preview)
   }
 
-  fn find_last_synced_remote_commit(&self, remote_git: &SystemGit) -> RailResult<Option<String>> {
-    // Find the most recent remote commit that has a reverse mapping (O(1) lookups)
-    let commits = remote_git.commit_history(Path::new("."), Some(100))?;
+  /// Predict which of a remote commit's files conflict with the mono worktree
+  fn predict_commit_conflicts(
+    &self,
+    remote_git: &SystemGit,
+    remote_sha: &str,
+    base_sha: Option<&str>,
+    merges: &[(PathBuf, PathBuf)],
+  ) -> RailResult<Vec<String>> {
+    if merges.is_empty() {
+      return Ok(Vec::new());
+    }
 
-    for commit in commits {
-      // Check if this remote commit has been mapped (O(1) reverse lookup)
-      if self.mapping_store.has_reverse_mapping(&commit.sha) {
-        return Ok(Some(commit.sha));
+    let incoming_items: Vec<(String, PathBuf)> = merges
+      .iter()
+      .map(|(remote_path, _)| (remote_sha.to_string(), remote_path.clone()))
+      .collect();
+    let incoming = remote_git.read_files_bulk(&incoming_items)?;
+
+    let mut inputs = Vec::new();
+    for ((_, mono_path), theirs) in merges.iter().zip(incoming) {
+      let base = match base_sha {
+        Some(sha) => self.mono_git.get_file_at_commit(sha, mono_path)?,
+        None => None,
+      };
+      inputs.push(MergeInput {
+        path: mono_path.clone(),
+        base,
+        ours: Some(std::fs::read(self.workspace_root.join(mono_path))?),
+        theirs: Some(theirs),
+      });
+    }
+
+    Ok(
+      conflict::predict_conflicts(&self.workspace_root, &inputs)?
+        .into_iter()
+        .map(|path| path.display().to_string())
+        .collect(),
+    )
+  }
+
+  // Helper methods
+
+  /// Commit the remote commit a sync stopped on, once its conflicts are settled
+  ///
+  /// The commit is journaled like any other, so resuming records its mapping.
+  fn commit_resolved_conflicts(&mut self) -> RailResult<()> {
+    let crate_name = self.config.crate_name.clone();
+    let Some(state) = ConflictState::load(&self.workspace_root, &crate_name)? else {
+      return Ok(());
+    };
+
+    let unresolved = state.unresolved(&self.workspace_root)?;
+    if !unresolved.is_empty() {
+      let paths: Vec<String> = unresolved.iter().map(|f| f.path.display().to_string()).collect();
+      return Err(RailError::with_help(
+        format!("Conflict markers remain in: {}", paths.join(", ")),
+        format!("Settle them with `cargo rail resolve {}`", crate_name),
+      ));
+    }
+
+    let mut journal = SyncJournal::load(&self.workspace_root, &crate_name)?.ok_or_else(|| {
+      RailError::with_help(
+        format!("The sync of '{}' stopped on conflicts but has no journal", crate_name),
+        format!("Roll it back with `cargo rail sync {} --abort`", crate_name),
+      )
+    })?;
+
+    if self.mono_git.head_commit()? != state.parent {
+      return Err(RailError::with_help(
+        format!("HEAD moved since the sync of '{}' stopped on conflicts", crate_name),
+        format!(
+          "Check out '{}' at {} again, or roll back with `cargo rail sync {} --abort`",
+          state.branch,
+          &state.parent[..state.parent.len().min(7)],
+          crate_name
+        ),
+      ));
+    }
+
+    let mono_sha = self.commit_remote_commit(&state.commit, &state.parent)?;
+    journal.record(&mono_sha, &state.commit.sha)?;
+    println!(
+      "   ✅ Committed resolved {} as {}",
+      &state.commit.sha[..state.commit.sha.len().min(7)],
+      &mono_sha[..mono_sha.len().min(7)]
+    );
+
+    state.finish()
+  }
+
+  /// Resume the journal of an interrupted sync, or start a new one
+  ///
+  /// Returns the journal and whether it was resumed. An interrupted sync blocks new syncs
+  /// of the split until it is resumed or aborted.
+  fn open_journal(
+    &mut self,
+    direction: JournalDirection,
+    start_head: &str,
+    branch: &str,
+  ) -> RailResult<(SyncJournal, bool)> {
+    let crate_name = self.config.crate_name.clone();
+
+    if let Some(journal) = SyncJournal::load(&self.workspace_root, &crate_name)? {
+      if !self.config.resume && ConflictState::load(&self.workspace_root, &crate_name)?.is_some() {
+        return Err(RailError::with_help(
+          format!("The last sync of '{}' stopped on conflicts", crate_name),
+          format!(
+            "Settle them with `cargo rail resolve {}`, then `cargo rail resolve {} --continue` (or roll back with `cargo rail sync {} --abort`)",
+            crate_name, crate_name, crate_name
+          ),
+        ));
       }
+      if !self.config.resume {
+        return Err(RailError::with_help(
+          format!(
+            "An interrupted sync of '{}' was found ({} commit(s) applied, not yet finished)",
+            crate_name,
+            journal.applied.len()
+          ),
+          format!(
+            "Finish it with `cargo rail sync {} {} --resume`, or roll it back with `cargo rail sync {} --abort`",
+            crate_name,
+            journal.direction.flag(),
+            crate_name
+          ),
+        ));
+      }
+      if journal.direction != direction {
+        return Err(RailError::with_help(
+          format!("The interrupted sync of '{}' ran in the other direction", crate_name),
+          format!(
+            "Resume it with `cargo rail sync {} {} --resume`",
+            crate_name,
+            journal.direction.flag()
+          ),
+        ));
+      }
+
+      println!(
+        "   ♻️  Resuming interrupted sync ({} commit(s) already applied)",
+        journal.applied.len()
+      );
+      journal.recover(&self.workspace_root, &mut self.mapping_store)?;
+      return Ok((journal, true));
     }
 
-    Ok(None)
+    let journal = SyncJournal::begin(
+      &self.workspace_root,
+      &crate_name,
+      direction,
+      &self.config.target_repo_path,
+      start_head,
+      branch,
+    )?;
+    Ok((journal, false))
+  }
+
+  fn find_last_synced_mono_commit(&self) -> RailResult<Option<String>> {
+    last_synced_mono_commit(&self.mono_git, &self.mapping_store)
+  }
+
+  fn find_last_synced_remote_commit(&self, remote_git: &SystemGit) -> RailResult<Option<String>> {
+    last_synced_remote_commit(remote_git, &self.mapping_store)
   }
 
   fn apply_mono_commit_to_remote(
@@ -465,15 +1263,8 @@ impl SyncEngine {
     remote_git: &SystemGit,
     current_remote_head: &str,
   ) -> RailResult<String> {
-    // Get changed files in mono
-    let changed_files = self.mono_git.get_changed_files(&commit.sha)?;
-
-    // Filter to only files in crate path
-    let crate_path = &self.config.crate_paths[0];
-    let relevant_files: Vec<_> = changed_files
-      .into_iter()
-      .filter(|(path, _)| path.starts_with(crate_path) && !helpers::should_exclude_cargo_path(path))
-      .collect();
+    // Changed files in the crate path(s)
+    let relevant_files = self.mono_commit_files(&commit.sha)?;
 
     // Separate deletions from additions/modifications
     let (deletions, modifications): (Vec<_>, Vec<_>) =
@@ -527,7 +1318,10 @@ impl SyncEngine {
           crate_name: self.config.crate_name.clone(),
           workspace_root: self.workspace_root.clone(),
         };
-        let transformed = self.transform.transform_to_split(&content, &context)?;
+        let transformed = match self.workspace_members {
+          Some(ref members) => self.transform.transform_to_split_member(&content, &context, members)?,
+          None => self.transform.transform_to_split(&content, &context)?,
+        };
         std::fs::write(&full_remote_path, transformed)?;
       }
 
@@ -536,6 +1330,11 @@ impl SyncEngine {
       }
     }
 
+    // Regenerate the root manifest so the split stays a buildable workspace
+    if let Some(ref members) = self.workspace_members {
+      self.write_workspace_manifest(members)?;
+    }
+
     // Update progress for deletions
     if let Some(ref mut p) = progress {
       for _ in 0..deletions.len() {
@@ -543,6 +1342,17 @@ impl SyncEngine {
       }
     }
 
+    // Pin dependencies to what the monorepo resolved at this commit
+    if let Some(ref targets) = self.lockfile_targets {
+      lockfile::write_split_lockfiles(
+        &self.mono_git,
+        &commit.sha,
+        &self.config.split_root(),
+        targets,
+        &self.local_crates,
+      )?;
+    }
+
     // Security checks before creating commit
     // Note: Branch protection doesn't apply to remote repos - only to monorepo
     if self.security_config.require_signed_commits {
@@ -563,11 +1373,9 @@ impl SyncEngine {
     )?;
 
     // Verify commit signature if required
-    if self.security_config.require_signed_commits {
-      self
-        .security_validator
-        .verify_commit_signature(&self.config.target_repo_path, &new_commit_sha)?;
-    }
+    self
+      .security_validator
+      .require_commit_signature(&self.config.target_repo_path, &new_commit_sha)?;
 
     Ok(new_commit_sha)
   }
@@ -580,6 +1388,18 @@ impl SyncEngine {
     current_mono_head: &str,
     changed_files: &[(PathBuf, char)], // Pre-fetched from resolve_conflicts to avoid duplicate subprocess call
   ) -> RailResult<String> {
+    self.write_remote_commit_files(commit, remote_git, resolved_files, changed_files)?;
+    self.commit_remote_commit(commit, current_mono_head)
+  }
+
+  /// Write a remote commit's files into the mono worktree (without committing)
+  fn write_remote_commit_files(
+    &self,
+    commit: &crate::core::vcs::CommitInfo,
+    remote_git: &SystemGit,
+    resolved_files: &HashSet<PathBuf>,
+    changed_files: &[(PathBuf, char)],
+  ) -> RailResult<()> {
     // Use pre-fetched changed_files (already retrieved in resolve_conflicts_for_commit)
 
     // Apply each file to mono
@@ -593,12 +1413,7 @@ impl SyncEngine {
     let relevant_files: Vec<_> = changed_files
       .iter()
       .filter_map(|(remote_path, change_type)| {
-        let mono_path = self.map_remote_path_to_mono(remote_path).ok()?;
-
-        // Skip files excluded by Cargo helper (target, etc.)
-        if helpers::should_exclude_cargo_path(&mono_path) {
-          return None;
-        }
+        let mono_path = self.importable_remote_path(remote_path)?;
 
         // Skip files that were already resolved by conflict resolution (O(1) HashSet lookup)
         if resolved_files.contains(&mono_path) {
@@ -655,7 +1470,10 @@ impl SyncEngine {
           crate_name: self.config.crate_name.clone(),
           workspace_root: self.workspace_root.clone(),
         };
-        let transformed = self.transform.transform_to_mono(&content, &context)?;
+        let transformed = match self.workspace_members {
+          Some(ref members) => self.transform.transform_member_to_mono(&content, members)?,
+          None => self.transform.transform_to_mono(&content, &context)?,
+        };
         std::fs::write(&full_mono_path, transformed)?;
       }
 
@@ -671,6 +1489,11 @@ impl SyncEngine {
       }
     }
 
+    Ok(())
+  }
+
+  /// Commit the worktree as the mono counterpart of a remote commit
+  fn commit_remote_commit(&self, commit: &CommitInfo, current_mono_head: &str) -> RailResult<String> {
     // Security checks before creating commit
     // Note: Branch protection applies to the monorepo's current branch
     if self.security_config.require_signed_commits {
@@ -691,13 +1514,293 @@ impl SyncEngine {
     )?;
 
     // Verify commit signature if required
-    if self.security_config.require_signed_commits {
+    self
+      .security_validator
+      .require_commit_signature(&self.workspace_root, &new_commit_sha)?;
+
+    Ok(new_commit_sha)
+  }
+
+  /// Put held-back remote commits on the review branch and report why, or clear a stale report
+  fn quarantine(&self, quarantined: &[QuarantinedCommit]) -> RailResult<()> {
+    let Some(last) = quarantined.last() else {
+      return QuarantineReport::clear(&self.workspace_root, &self.config.crate_name);
+    };
+
+    // The review branch is the split's own history up to the last held commit
+    let branch = QuarantineReport::branch_name(&self.config.crate_name);
+    let output = self
+      .mono_git
+      .git_cmd()
+      .args(["fetch", "--quiet", "--no-tags"])
+      .arg(&self.config.target_repo_path)
+      .arg(format!("+{}:refs/heads/{}", last.sha, branch))
+      .output()
+      .context("Failed to fetch held-back commits")?;
+    if !output.status.success() {
+      return Err(RailError::Git(GitError::CommandFailed {
+        command: format!("git fetch {} {}", self.config.target_repo_path.display(), last.sha),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+      }));
+    }
+
+    let report = QuarantineReport {
+      crate_name: self.config.crate_name.clone(),
+      branch: branch.clone(),
+      commits: quarantined.to_vec(),
+    };
+    let path = report.save(&self.workspace_root)?;
+
+    println!(
+      "\n   🚫 {} remote commit(s) held back by the inbound trust policy:",
+      quarantined.len()
+    );
+    for line in report.to_text().lines() {
+      println!("      {}", line);
+    }
+    println!("   📝 Review them on branch {} (report: {})", branch, path.display());
+
+    Ok(())
+  }
+
+  /// Whether a mono commit still needs replaying into the split repo
+  fn should_replay_to_remote(&self, commit: &CommitInfo) -> bool {
+    !self.mapping_store.has_mapping(&commit.sha)
+      && !commit.message.contains("Rail-Origin: remote@")
+      && !commit.message.contains(UNDO_TRAILER)
+  }
+
+  /// Whether a split repo commit still needs replaying into the monorepo
+  fn should_replay_from_remote(&self, commit: &CommitInfo) -> bool {
+    !commit.message.contains("Rail-Origin: mono@")
+      && !commit.message.contains(UNDO_TRAILER)
+      && !self.mapping_store.has_reverse_mapping(&commit.sha)
+  }
+
+  /// Mono paths whose content makes up the split
+  fn split_crate_paths(&self) -> &[PathBuf] {
+    match self.config.mode {
+      SplitMode::Single => &self.config.crate_paths[..1],
+      SplitMode::Combined => &self.config.crate_paths[..],
+    }
+  }
+
+  /// Files a mono commit changes inside the crate path(s)
+  fn mono_commit_files(&self, sha: &str) -> RailResult<Vec<(PathBuf, char)>> {
+    let crate_paths = self.split_crate_paths();
+
+    Ok(
       self
-        .security_validator
-        .verify_commit_signature(&self.workspace_root, &new_commit_sha)?;
+        .mono_git
+        .get_changed_files(sha)?
+        .into_iter()
+        .filter(|(path, _)| {
+          crate_paths.iter().any(|crate_path| path.starts_with(crate_path)) && !helpers::should_exclude_cargo_path(path)
+        })
+        .collect(),
+    )
+  }
+
+  /// Split content of mono commits: the tree IDs of the crate path(s), in one `cat-file` call
+  ///
+  /// Commits without the crate path(s), or unknown to this repository, are left out.
+  fn split_trees(&self, shas: &[String]) -> RailResult<HashMap<String, String>> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let paths: Vec<String> = self
+      .split_crate_paths()
+      .iter()
+      .map(|path| utils::path_to_git_format(path))
+      .collect();
+    if shas.is_empty() || paths.is_empty() {
+      return Ok(HashMap::new());
     }
 
-    Ok(new_commit_sha)
+    let mut child = self
+      .mono_git
+      .git_cmd()
+      .args(["cat-file", "--batch-check=%(objectname) %(objecttype)"])
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .context("Failed to spawn git cat-file")?;
+    let mut stdin = child
+      .stdin
+      .take()
+      .ok_or_else(|| RailError::message("Failed to open stdin"))?;
+    for sha in shas {
+      for path in &paths {
+        stdin
+          .write_all(format!("{}:{}\n", sha, path).as_bytes())
+          .context("Failed to write to git cat-file stdin")?;
+      }
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output().context("Failed to read git cat-file output")?;
+    if !output.status.success() {
+      return Err(RailError::Git(GitError::CommandFailed {
+        command: "git cat-file --batch-check".to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+      }));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let mut trees = HashMap::new();
+    for sha in shas {
+      let ids: Vec<Option<&str>> = paths
+        .iter()
+        .map(|_| lines.next().and_then(|line| line.strip_suffix(" tree")))
+        .collect();
+      if ids.iter().all(Option::is_some) {
+        trees.insert(sha.clone(), ids.into_iter().flatten().collect::<Vec<_>>().join(" "));
+      }
+    }
+    Ok(trees)
+  }
+
+  /// Recognize imports that reached HEAD through a squash (or forge rebase) merge
+  ///
+  /// Imports onto a PR branch are mapped there, so merging the branch any way but a merge
+  /// commit lands unmapped commits on HEAD that would be replayed back to the split repo. A
+  /// commit whose split content equals an import's - or failing that, whose message kept the
+  /// imports' `Rail-Origin` trailers - takes over that import's mapping. Only the in-memory
+  /// mappings change; returns the (import, merged commit) pairs for `save_repointed`.
+  fn reconcile_merged_imports(&mut self) -> RailResult<Vec<(String, String)>> {
+    let last_synced = self.find_last_synced_mono_commit()?;
+    let candidates: Vec<CommitInfo> = self
+      .mono_git
+      .get_commits_touching_paths(&self.config.crate_paths, last_synced.as_deref(), "HEAD")?
+      .into_iter()
+      .filter(|c| !self.mapping_store.has_mapping(&c.sha) && !c.message.contains(UNDO_TRAILER))
+      .collect();
+    if candidates.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let mapped: Vec<String> = self.mapping_store.all_mappings().keys().cloned().collect();
+    let mapped_trees = self.split_trees(&mapped)?;
+    let mut by_tree: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (sha, tree) in &mapped_trees {
+      by_tree.entry(tree.as_str()).or_default().push(sha.as_str());
+    }
+    let candidate_shas: Vec<String> = candidates.iter().map(|c| c.sha.clone()).collect();
+    let candidate_trees = self.split_trees(&candidate_shas)?;
+
+    let mut repointed = Vec::new();
+    for candidate in &candidates {
+      // Same split content as an import that isn't in HEAD's history (a revert back to an
+      // earlier state still has to be synced)
+      let mut import = None;
+      if let Some(tree) = candidate_trees.get(&candidate.sha) {
+        for sha in by_tree.get(tree.as_str()).into_iter().flatten() {
+          if !self.mapping_store.has_mapping(sha) || is_ancestor(&self.workspace_root, sha, &candidate.sha)? {
+            continue;
+          }
+          if self.mono_git.get_commit(sha)?.message.contains("Rail-Origin: remote@") {
+            import = Some(sha.to_string());
+            break;
+          }
+        }
+      }
+      // A squash message listing the imports: the newest one known stands for all of them
+      if import.is_none() {
+        import = remote_origins(&candidate.message)
+          .filter_map(|remote| self.mapping_store.get_reverse_mapping(remote))
+          .rfind(|sha| self.mapping_store.has_mapping(sha));
+      }
+
+      if let Some(import) = import {
+        self.mapping_store.repoint_mapping(&import, &candidate.sha)?;
+        repointed.push((import, candidate.sha.clone()));
+      }
+    }
+
+    if !repointed.is_empty() {
+      println!(
+        "   🔗 Recognized {} merged import(s) from a PR branch; they won't be synced back",
+        repointed.len()
+      );
+    }
+    Ok(repointed)
+  }
+
+  /// Persist mappings moved to rewritten or merged commits, dropping the notes they left
+  fn save_repointed(&self, repointed: &[(String, String)]) -> RailResult<()> {
+    if repointed.is_empty() {
+      return Ok(());
+    }
+    let old_shas: Vec<String> = repointed.iter().map(|(old, _)| old.clone()).collect();
+    self.mapping_store.save(&self.workspace_root)?;
+    self.mapping_store.remove_notes(&self.workspace_root, &old_shas)?;
+    if self.config.target_repo_path.exists() {
+      self
+        .mapping_store
+        .remove_notes(&self.config.target_repo_path, &old_shas)?;
+    }
+    Ok(())
+  }
+
+  /// Whether an import on a PR branch already reached `base` (merged by any means)
+  fn import_merged_into(&self, commit: &CommitInfo, base_tip: &str) -> RailResult<bool> {
+    for remote in remote_origins(&commit.message) {
+      if let Some(mono) = self.mapping_store.get_reverse_mapping(remote)
+        && mono != commit.sha
+        && is_ancestor(&self.workspace_root, &mono, base_tip)?
+      {
+        return Ok(true);
+      }
+    }
+    Ok(false)
+  }
+
+  /// Mono path a remote file is imported to, or `None` if it is never imported
+  fn importable_remote_path(&self, remote_path: &Path) -> Option<PathBuf> {
+    let mono_path = self.map_remote_path_to_mono(remote_path).ok()?;
+
+    // Skip files excluded by Cargo helper (target, etc.)
+    if helpers::should_exclude_cargo_path(&mono_path) {
+      return None;
+    }
+
+    // The split's root manifest is generated from the mono's, never imported back
+    if self.workspace_members.is_some() && remote_path == self.config.target_dir.join("Cargo.toml") {
+      return None;
+    }
+
+    // Derived lockfiles are regenerated from the mono's Cargo.lock, never imported back
+    if let Some(ref targets) = self.lockfile_targets
+      && targets
+        .iter()
+        .any(|target| self.config.target_dir.join(&target.path) == remote_path)
+    {
+      return None;
+    }
+
+    Some(mono_path)
+  }
+
+  /// Write the generated root Cargo.toml for a workspace-mode combined split
+  fn write_workspace_manifest(&self, workspace_members: &HashSet<String>) -> RailResult<()> {
+    let mut member_paths = Vec::new();
+    let mut member_manifests = Vec::new();
+    for crate_path in &self.config.crate_paths {
+      let manifest_path = self.config.split_root().join(crate_path).join("Cargo.toml");
+      if manifest_path.exists() {
+        member_paths.push(crate_path.clone());
+        member_manifests.push(std::fs::read_to_string(&manifest_path)?);
+      }
+    }
+
+    let root_manifest =
+      self
+        .transform
+        .generate_split_workspace_manifest(&member_paths, &member_manifests, workspace_members)?;
+    std::fs::write(self.config.split_root().join("Cargo.toml"), root_manifest)?;
+    Ok(())
   }
 
   fn map_mono_path_to_remote(&self, mono_path: &Path) -> RailResult<PathBuf> {
@@ -706,17 +1809,19 @@ impl SyncEngine {
     match self.config.mode {
       SplitMode::Single => {
         // Strip crate path prefix
-        Ok(mono_path.strip_prefix(crate_path)?.to_path_buf())
+        Ok(self.config.target_dir.join(mono_path.strip_prefix(crate_path)?))
       }
       SplitMode::Combined => {
         // Keep full path
-        Ok(mono_path.to_path_buf())
+        Ok(self.config.target_dir.join(mono_path))
       }
     }
   }
 
+  /// Map a remote path back to the mono; fails for paths outside `target_dir`
   fn map_remote_path_to_mono(&self, remote_path: &Path) -> RailResult<PathBuf> {
     let crate_path = &self.config.crate_paths[0];
+    let remote_path = remote_path.strip_prefix(&self.config.target_dir)?;
 
     match self.config.mode {
       SplitMode::Single => {
@@ -730,18 +1835,23 @@ impl SyncEngine {
     }
   }
 
-  /// Resolve conflicts for a commit using 3-way merge
+  /// Files a remote commit changed in the split's subtree
+  fn split_changed_files(&self, remote_git: &SystemGit, sha: &str) -> RailResult<Vec<(PathBuf, char)>> {
+    let mut changed_files = remote_git.get_changed_files(sha)?;
+    changed_files.retain(|(path, _)| path.starts_with(&self.config.target_dir));
+    Ok(changed_files)
+  }
+
+  /// Resolve conflicts for a remote commit's changed files using 3-way merge
   /// Returns: (conflicts, changed_files) - the changed_files are cached for reuse in apply step
   fn resolve_conflicts_for_commit(
     &self,
     remote_commit: &crate::core::vcs::CommitInfo,
     remote_git: &SystemGit,
+    changed_files: Vec<(PathBuf, char)>,
   ) -> RailResult<ConflictResolutionResult> {
     let mut conflicts = Vec::new();
 
-    // Get files changed in this remote commit
-    let changed_files = remote_git.get_changed_files(&remote_commit.sha)?;
-
     // Show progress bar for conflict resolution if many files
     let mut progress = if changed_files.len() > 5 {
       Some(FileProgress::new(
@@ -822,6 +1932,11 @@ impl SyncEngine {
         &Vec::new()
       };
       let incoming_content = &incoming_contents[idx];
+      let versions = MergeVersions {
+        base: base_content.clone(),
+        ours: std::fs::read(full_mono_path)?,
+        theirs: incoming_content.clone(),
+      };
 
       // Perform 3-way merge
       match self
@@ -839,6 +1954,7 @@ impl SyncEngine {
               self.conflict_resolver.strategy()
             ),
             resolved: true,
+            versions: None,
           });
         }
         Ok(crate::core::conflict::MergeResult::Conflicts(_paths)) => {
@@ -851,6 +1967,7 @@ impl SyncEngine {
             file_path: mono_path.clone(),
             message: format!("Conflict in {}", mono_path.display()),
             resolved: is_auto_resolved,
+            versions: Some(versions),
           });
         }
         Ok(crate::core::conflict::MergeResult::Failed(msg)) => {
@@ -858,6 +1975,7 @@ impl SyncEngine {
             file_path: mono_path.clone(),
             message: format!("Merge failed: {}", msg),
             resolved: false,
+            versions: Some(versions),
           });
         }
         Err(e) => {
@@ -865,6 +1983,7 @@ impl SyncEngine {
             file_path: mono_path.clone(),
             message: format!("Merge error: {}", e),
             resolved: false,
+            versions: Some(versions),
           });
         }
       }
@@ -878,45 +1997,256 @@ impl SyncEngine {
   }
 
   fn check_mono_has_changes(&self) -> RailResult<bool> {
-    let last_synced = self.find_last_synced_mono_commit()?;
-    let crate_path = &self.config.crate_paths[0];
-
-    let new_commits = self
-      .mono_git
-      .get_commits_touching_path(crate_path, last_synced.as_deref(), "HEAD")?;
-
-    // Filter out commits from remote
-    let relevant_commits: Vec<_> = new_commits
-      .into_iter()
-      .filter(|c| !c.message.contains("Rail-Origin: remote@"))
-      .collect();
-
-    Ok(!relevant_commits.is_empty())
+    let new_commits = unsynced_mono_commits(&self.mono_git, &self.mapping_store, &self.config.crate_paths)?;
+    Ok(!new_commits.is_empty())
   }
 
   fn check_remote_has_changes(&self) -> RailResult<bool> {
-    let remote_git = SystemGit::open(&self.config.target_repo_path)?;
+    let remote_git = SystemGit::open(&self.config.target_repo_path)?.with_auth(self.config.auth.clone());
 
-    // Fetch from remote (skip for local paths)
-    if !utils::is_local_path(&self.config.remote_url) {
+    // Fetch from remote (skip for local paths and bundles)
+    if !self.is_offline() {
       remote_git.fetch_from_remote("origin")?;
     }
 
-    let last_synced = self.find_last_synced_remote_commit(&remote_git)?;
+    let new_commits = unsynced_remote_commits(
+      &remote_git,
+      &self.mapping_store,
+      self.remote_subtree(),
+      &self.get_branch_ref(),
+    )?;
+    Ok(!new_commits.is_empty())
+  }
+}
 
-    let branch_ref = self.get_branch_ref();
-    let new_commits = if let Some(ref last) = last_synced {
-      remote_git.get_commits_touching_path(Path::new("."), Some(last), &branch_ref)?
-    } else {
-      remote_git.get_commits_touching_path(Path::new("."), None, &branch_ref)?
-    };
+/// Most recent monorepo commit (of the last 100 on HEAD) with a mapping
+fn last_synced_mono_commit(mono_git: &SystemGit, mappings: &MappingStore) -> RailResult<Option<String>> {
+  let commits = mono_git.commit_history(Path::new("."), Some(100))?;
+  Ok(
+    commits
+      .into_iter()
+      .find(|commit| mappings.has_mapping(&commit.sha))
+      .map(|commit| commit.sha),
+  )
+}
 
-    // Filter out commits from mono
-    let relevant_commits: Vec<_> = new_commits
+/// Most recent split repo commit (of the last 100 on HEAD) with a reverse mapping
+fn last_synced_remote_commit(remote_git: &SystemGit, mappings: &MappingStore) -> RailResult<Option<String>> {
+  let commits = remote_git.commit_history(Path::new("."), Some(100))?;
+  Ok(
+    commits
       .into_iter()
-      .filter(|c| !c.message.contains("Rail-Origin: mono@"))
-      .collect();
+      .find(|commit| mappings.has_reverse_mapping(&commit.sha))
+      .map(|commit| commit.sha),
+  )
+}
+
+/// Monorepo commits on HEAD touching the split's paths since the last sync, excluding imports and undos
+///
+/// What a mono → remote sync would look at; `mappings` must hold the split's notes.
+pub fn unsynced_mono_commits(
+  mono_git: &SystemGit,
+  mappings: &MappingStore,
+  crate_paths: &[PathBuf],
+) -> RailResult<Vec<CommitInfo>> {
+  let last_synced = last_synced_mono_commit(mono_git, mappings)?;
+  let commits = mono_git.get_commits_touching_paths(crate_paths, last_synced.as_deref(), "HEAD")?;
+
+  Ok(
+    commits
+      .into_iter()
+      .filter(|c| !c.message.contains("Rail-Origin: remote@") && !c.message.contains(UNDO_TRAILER))
+      .collect(),
+  )
+}
+
+/// Split repo commits on `branch_ref` touching `subtree` since the last sync, excluding exports and undos
+///
+/// What a remote → mono sync would look at; `mappings` must hold the split's notes.
+pub fn unsynced_remote_commits(
+  remote_git: &SystemGit,
+  mappings: &MappingStore,
+  subtree: &Path,
+  branch_ref: &str,
+) -> RailResult<Vec<CommitInfo>> {
+  let last_synced = last_synced_remote_commit(remote_git, mappings)?;
+  let commits = remote_git.get_commits_touching_path(subtree, last_synced.as_deref(), branch_ref)?;
+
+  Ok(
+    commits
+      .into_iter()
+      .filter(|c| !c.message.contains("Rail-Origin: mono@") && !c.message.contains(UNDO_TRAILER))
+      .collect(),
+  )
+}
+
+/// A file written by a replayed commit, at its receiving-side path
+/// Split repo commits named by `Rail-Origin: remote@` trailers in a message, oldest first
+fn remote_origins(message: &str) -> impl DoubleEndedIterator<Item = &str> {
+  message
+    .lines()
+    .filter_map(|line| line.trim().strip_prefix("Rail-Origin: remote@"))
+    .map(str::trim)
+}
+
+fn planned_file(path: &Path, change: char) -> PlannedFile {
+  PlannedFile {
+    path: path.display().to_string(),
+    change: change.to_string(),
+    transformed: path.file_name() == Some(std::ffi::OsStr::new("Cargo.toml")),
+  }
+}
+
+fn planned_commit(commit: &CommitInfo, files: Vec<PlannedFile>, conflicts: Vec<String>) -> PlannedCommit {
+  PlannedCommit {
+    sha: commit.sha.clone(),
+    summary: commit.message.lines().next().unwrap_or_default().to_string(),
+    files,
+    conflicts,
+  }
+}
 
-    Ok(!relevant_commits.is_empty())
+/// Error ending a sync that stopped on conflicts, listing them
+/// Write the split branch and its rail notes to a bundle for the other side to fetch
+///
+/// Bundles hold the whole branch rather than just the new commits, so one can be applied no
+/// matter which earlier bundles made it across.
+fn write_bundle(repo: &Path, bundle: &Path, branch: &str, crate_name: &str) -> RailResult<()> {
+  let notes_ref = format!("refs/notes/rail/{}", crate_name);
+  let mut refs = vec![format!("refs/heads/{}", branch)];
+  if SystemGit::open(repo)?.resolve_reference(&notes_ref).is_ok() {
+    refs.push(notes_ref);
   }
+
+  if let Some(parent) = bundle.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  let output = std::process::Command::new("git")
+    .current_dir(repo)
+    .args(["bundle", "create", "--quiet"])
+    .arg(bundle)
+    .args(&refs)
+    .output()
+    .context("Failed to run git bundle create")?;
+  if !output.status.success() {
+    return Err(RailError::message(format!(
+      "git bundle create failed: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    )));
+  }
+
+  println!("   📦 Wrote {} ({})", bundle.display(), refs.join(", "));
+  Ok(())
+}
+
+/// Read the split branch from a bundle into `BUNDLE_REFS`
+///
+/// With `fast_forward`, the target clone's branch is moved up to the bundle's so later outgoing
+/// commits build on it. A bundle that diverged from the clone is refused.
+fn fetch_bundle(
+  remote_git: &SystemGit,
+  repo: &Path,
+  bundle: &Path,
+  branch: &str,
+  fast_forward: bool,
+) -> RailResult<()> {
+  println!("   Reading bundle {}...", bundle.display());
+  let bundle_arg = bundle.display().to_string();
+  let tracking = format!("{}/{}", BUNDLE_REFS, branch);
+
+  let verify = std::process::Command::new("git")
+    .current_dir(repo)
+    .args(["bundle", "verify", "--quiet", &bundle_arg])
+    .output()
+    .context("Failed to run git bundle verify")?;
+  if !verify.status.success() {
+    return Err(RailError::with_help(
+      format!(
+        "Bundle {} can't be applied: {}",
+        bundle.display(),
+        String::from_utf8_lossy(&verify.stderr).trim()
+      ),
+      "Create it on the other side with `git bundle create <file> <branch>`; it must contain the whole branch",
+    ));
+  }
+
+  let fetch = std::process::Command::new("git")
+    .current_dir(repo)
+    .args([
+      "fetch",
+      "--quiet",
+      &bundle_arg,
+      &format!("+refs/heads/{}:{}", branch, tracking),
+    ])
+    .output()
+    .context("Failed to fetch from bundle")?;
+  if !fetch.status.success() {
+    return Err(RailError::with_help(
+      format!(
+        "Bundle {} has no '{}' branch: {}",
+        bundle.display(),
+        branch,
+        String::from_utf8_lossy(&fetch.stderr).trim()
+      ),
+      "Bundle the branch the split is configured to sync",
+    ));
+  }
+
+  let tip = remote_git.resolve_reference(&tracking)?;
+  let head = remote_git.head_commit()?;
+  if tip == head || is_ancestor(repo, &tip, &head)? {
+    return Ok(());
+  }
+  if !is_ancestor(repo, &head, &tip)? {
+    return Err(RailError::with_help(
+      format!("The bundle's '{}' has diverged from {}", branch, repo.display()),
+      "Apply the last `--to-bundle` output on the other side and rebase their commits onto it, then bundle again",
+    ));
+  }
+  if fast_forward {
+    remote_git.reset_hard(&tip)?;
+  }
+  Ok(())
+}
+
+/// Error for a remote holding commits a mono → remote sync would build past
+fn diverged(crate_name: &str, unimported: &[CommitInfo]) -> RailError {
+  println!(
+    "\n   ⚠️  Remote has {} commit(s) that were never imported:",
+    unimported.len()
+  );
+  for commit in unimported {
+    println!(
+      "      {} {}",
+      &commit.sha[..commit.sha.len().min(7)],
+      commit.message.lines().next().unwrap_or_default()
+    );
+  }
+
+  RailError::with_help(
+    format!("The split remote of '{}' has diverged from the monorepo", crate_name),
+    format!(
+      "Import them first with `cargo rail sync {} --from-remote`, or re-run with `--on-diverged import` (import, then push) or `--on-diverged rebase` (push on top of them)",
+      crate_name
+    ),
+  )
+}
+
+fn stopped_on_conflicts(state: &ConflictState) -> RailError {
+  println!(
+    "\n   ⚠️  Stopped on {} conflict(s) replaying {}:",
+    state.files.len(),
+    &state.commit.sha[..state.commit.sha.len().min(7)]
+  );
+  for file in &state.files {
+    println!("      {}", file.message);
+  }
+
+  RailError::with_help(
+    format!("Sync of '{}' stopped on conflicts", state.crate_name),
+    format!(
+      "Conflict markers were left in the worktree. Settle them with `cargo rail resolve {}`, then run `cargo rail resolve {} --continue`",
+      state.crate_name, state.crate_name
+    ),
+  )
 }
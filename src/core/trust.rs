@@ -0,0 +1,456 @@
+//! Trust policy for commits imported from split repos
+//!
+//! Remote → mono syncs only import commits that pass the `[security]` inbound policy:
+//! a valid signature (SSH signatures checked against `inbound_allowed_signers`, GPG ones
+//! against the keyring) and/or an author on `inbound_allowed_authors`. Whatever the policy,
+//! a commit may only write inside the split's crate paths, and never to a file matching
+//! `inbound_protected_paths`.
+//!
+//! The first commit failing the policy is held back along with everything after it, since
+//! later commits build on its changes. Held commits are put on a review branch in the monorepo
+//! (`rail/quarantine/{crate}`, the split's own history) and explained in a report next to the
+//! sync journal:
+//!
+//! ```text
+//! .git/rail/quarantine-{crate}.json
+//! ```
+//!
+//! Nothing is recorded for them, so the next sync checks them again. With
+//! `inbound_protected_action = "reject"`, a commit touching off-limits paths also fails the sync.
+
+use crate::core::config::{ProtectedPathAction, SecurityConfig};
+use crate::core::error::{GitError, RailError, RailResult, ResultExt};
+use crate::core::journal::rail_dir;
+use crate::core::vcs::CommitInfo;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Inbound trust policy from `[security]`
+#[derive(Debug, Clone, Default)]
+pub struct InboundPolicy {
+  require_signed: bool,
+  allowed_signers: Option<PathBuf>,
+  allowed_authors: Vec<String>,
+  /// Monorepo paths imported changes must stay within (empty = anywhere)
+  crate_paths: Vec<PathBuf>,
+  protected_paths: Vec<String>,
+  protected_action: ProtectedPathAction,
+}
+
+impl InboundPolicy {
+  /// Policy from the security config; relative paths are resolved against `workspace_root`
+  pub fn from_config(security: &SecurityConfig, workspace_root: &Path) -> Self {
+    Self {
+      require_signed: security.inbound_require_signed || security.inbound_allowed_signers.is_some(),
+      allowed_signers: security
+        .inbound_allowed_signers
+        .as_ref()
+        .map(|path| workspace_root.join(path)),
+      allowed_authors: security.inbound_allowed_authors.clone(),
+      crate_paths: Vec::new(),
+      protected_paths: security.inbound_protected_paths.clone(),
+      protected_action: security.inbound_protected_action,
+    }
+  }
+
+  /// Only accept changes inside these monorepo paths
+  pub fn with_crate_paths(mut self, crate_paths: Vec<PathBuf>) -> Self {
+    self.crate_paths = crate_paths;
+    self
+  }
+
+  /// Whether a commit touching protected paths fails the sync instead of just being held back
+  pub fn rejects_protected_paths(&self) -> bool {
+    self.protected_action == ProtectedPathAction::Reject
+  }
+
+  /// Whether any check applies (otherwise every commit is trusted)
+  pub fn is_enforced(&self) -> bool {
+    self.require_signed || !self.allowed_authors.is_empty()
+  }
+
+  /// Why `commit` in `repo` fails the policy, or None if it passes
+  pub fn check(&self, repo: &Path, commit: &CommitInfo) -> RailResult<Option<String>> {
+    if !self.allowed_authors.is_empty() && !self.author_allowed(&commit.author_email) {
+      return Ok(Some(format!(
+        "author {} <{}> is not in inbound_allowed_authors",
+        commit.author, commit.author_email
+      )));
+    }
+
+    if self.require_signed {
+      return self.check_signature(repo, &commit.sha);
+    }
+
+    Ok(None)
+  }
+
+  /// Hold back `commit` if any of the monorepo paths it writes are off limits
+  pub fn check_paths(&self, commit: &CommitInfo, mono_paths: &[PathBuf]) -> Option<QuarantinedCommit> {
+    let mut reasons = Vec::new();
+    let mut paths = Vec::new();
+    for path in mono_paths {
+      let reason = if !self.crate_paths.is_empty() && !self.crate_paths.iter().any(|c| path.starts_with(c)) {
+        "outside the split's crate paths".to_string()
+      } else if let Some(pattern) = self.protected_paths.iter().find(|p| glob_match(p, path)) {
+        format!("protected by '{}'", pattern)
+      } else {
+        continue;
+      };
+      reasons.push(format!("{} ({})", path.display(), reason));
+      paths.push(path.clone());
+    }
+
+    if paths.is_empty() {
+      return None;
+    }
+    let mut held = QuarantinedCommit::new(commit, format!("touches {}", reasons.join(", ")));
+    held.paths = paths;
+    Some(held)
+  }
+
+  /// Exact email, or `@domain` for everyone at a domain (case-insensitive)
+  fn author_allowed(&self, email: &str) -> bool {
+    let email = email.to_lowercase();
+    self.allowed_authors.iter().any(|allowed| {
+      let allowed = allowed.to_lowercase();
+      if allowed.starts_with('@') {
+        email.ends_with(&allowed)
+      } else {
+        email == allowed
+      }
+    })
+  }
+
+  fn check_signature(&self, repo: &Path, sha: &str) -> RailResult<Option<String>> {
+    if let Some(ref allowed_signers) = self.allowed_signers
+      && !allowed_signers.is_file()
+    {
+      return Err(RailError::with_help(
+        format!("Allowed signers file not found: {}", allowed_signers.display()),
+        "Fix inbound_allowed_signers in the [security] section of rail.toml",
+      ));
+    }
+
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo);
+    if let Some(ref allowed_signers) = self.allowed_signers {
+      cmd
+        .arg("-c")
+        .arg(format!("gpg.ssh.allowedSignersFile={}", allowed_signers.display()));
+    }
+    let output = cmd
+      .args(["log", "-1", "--format=%G?%x00%GK", sha])
+      .output()
+      .context("Failed to check commit signature")?;
+    if !output.status.success() {
+      return Err(RailError::Git(GitError::CommandFailed {
+        command: format!("git log -1 --format=%G? {}", sha),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+      }));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (status, key) = stdout.trim().split_once('\0').unwrap_or((stdout.trim(), ""));
+    let reason = match status {
+      "G" => return Ok(None),
+      "N" if self.allowed_signers.is_none() => {
+        "not signed (SSH signatures need inbound_allowed_signers to be checked)".to_string()
+      }
+      "N" => "not signed".to_string(),
+      "U" => format!("signed by {}, which is not an allowed signer", key),
+      "B" => "bad signature".to_string(),
+      "X" | "Y" => format!("signature by {} has expired", key),
+      "R" => format!("signed by revoked key {}", key),
+      "E" => format!("signature by {} cannot be checked (key not available)", key),
+      other => format!("signature status '{}'", other),
+    };
+    Ok(Some(reason))
+  }
+}
+
+/// A remote commit held back from import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedCommit {
+  pub sha: String,
+  /// First line of the commit message
+  pub summary: String,
+  pub author: String,
+  pub author_email: String,
+  /// Why it wasn't imported
+  pub reason: String,
+  /// Protected or out-of-split paths it touches
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub paths: Vec<PathBuf>,
+}
+
+impl QuarantinedCommit {
+  pub fn new(commit: &CommitInfo, reason: impl Into<String>) -> Self {
+    Self {
+      sha: commit.sha.clone(),
+      summary: commit.message.lines().next().unwrap_or_default().to_string(),
+      author: commit.author.clone(),
+      author_email: commit.author_email.clone(),
+      reason: reason.into(),
+      paths: Vec::new(),
+    }
+  }
+}
+
+/// Commits a remote → mono sync held back, and the branch they can be reviewed on
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuarantineReport {
+  pub crate_name: String,
+  /// Monorepo branch pointing at the last held commit
+  pub branch: String,
+  pub commits: Vec<QuarantinedCommit>,
+}
+
+impl QuarantineReport {
+  /// Review branch for a split's held commits
+  pub fn branch_name(crate_name: &str) -> String {
+    format!("rail/quarantine/{}", crate_name)
+  }
+
+  /// Write the report under `.git/rail/`, replacing the previous one
+  pub fn save(&self, workspace_root: &Path) -> RailResult<PathBuf> {
+    let path = report_path(workspace_root, &self.crate_name)?;
+    let json = serde_json::to_string_pretty(self)?;
+    std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+  }
+
+  /// Remove a stale report once nothing is held back
+  pub fn clear(workspace_root: &Path, crate_name: &str) -> RailResult<()> {
+    let path = report_path(workspace_root, crate_name)?;
+    if path.exists() {
+      std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+  }
+
+  /// One line per held commit
+  pub fn to_text(&self) -> String {
+    self
+      .commits
+      .iter()
+      .map(|c| format!("{} {} — {}", &c.sha[..7.min(c.sha.len())], c.summary, c.reason))
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+fn report_path(workspace_root: &Path, crate_name: &str) -> RailResult<PathBuf> {
+  Ok(rail_dir(workspace_root)?.join(format!("quarantine-{}.json", crate_name)))
+}
+
+/// Match a monorepo path against a glob
+///
+/// `*` and `?` stay within a path component, `**` spans any number of them. A pattern without
+/// `/` matches the file name at any depth, like a `.gitignore` entry.
+fn glob_match(pattern: &str, path: &Path) -> bool {
+  let components: Vec<String> = path
+    .components()
+    .map(|c| c.as_os_str().to_string_lossy().to_string())
+    .collect();
+  let pattern = pattern.trim_matches('/');
+  if !pattern.contains('/') {
+    return components.last().is_some_and(|name| wildcard_match(pattern, name));
+  }
+  let parts: Vec<&str> = pattern.split('/').collect();
+  match_components(&parts, &components)
+}
+
+fn match_components(parts: &[&str], components: &[String]) -> bool {
+  match parts.split_first() {
+    None => components.is_empty(),
+    Some((&"**", rest)) => (0..=components.len()).any(|skip| match_components(rest, &components[skip..])),
+    Some((part, rest)) => components
+      .split_first()
+      .is_some_and(|(first, others)| wildcard_match(part, first) && match_components(rest, others)),
+  }
+}
+
+/// `*` / `?` wildcard match within one path component
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+  let pattern: Vec<char> = pattern.chars().collect();
+  let name: Vec<char> = name.chars().collect();
+  let (mut p, mut n) = (0, 0);
+  let mut backtrack = None;
+  while n < name.len() {
+    if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+      p += 1;
+      n += 1;
+    } else if p < pattern.len() && pattern[p] == '*' {
+      backtrack = Some((p, n));
+      p += 1;
+    } else if let Some((star, matched)) = backtrack {
+      p = star + 1;
+      n = matched + 1;
+      backtrack = Some((star, matched + 1));
+    } else {
+      return false;
+    }
+  }
+  pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn commit(email: &str) -> CommitInfo {
+    CommitInfo {
+      sha: "0123456789abcdef".to_string(),
+      author: "Dev".to_string(),
+      author_email: email.to_string(),
+      committer: "Dev".to_string(),
+      committer_email: email.to_string(),
+      message: "Change things\n\nBody".to_string(),
+      timestamp: 0,
+      parent_shas: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_author_allowlist_matches_emails_and_domains() {
+    let policy = InboundPolicy::from_config(
+      &SecurityConfig {
+        inbound_allowed_authors: vec!["lead@example.org".to_string(), "@Example.COM".to_string()],
+        ..SecurityConfig::default()
+      },
+      Path::new("/ws"),
+    );
+    assert!(policy.is_enforced());
+
+    let repo = Path::new(".");
+    assert!(policy.check(repo, &commit("Lead@example.org")).unwrap().is_none());
+    assert!(policy.check(repo, &commit("anyone@example.com")).unwrap().is_none());
+    let reason = policy.check(repo, &commit("mallory@evil.example")).unwrap().unwrap();
+    assert!(reason.contains("inbound_allowed_authors"), "{}", reason);
+    assert!(policy.check(repo, &commit("x@notexample.com")).unwrap().is_some());
+  }
+
+  #[test]
+  fn test_signatures_are_checked_against_allowed_signers() {
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path();
+    let keygen = |name: &str| {
+      Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+        .arg(dir.join(name))
+        .status()
+        .is_ok_and(|s| s.success())
+    };
+    if !keygen("trusted") || !keygen("other") {
+      return; // ssh-keygen unavailable
+    }
+    let public_key = std::fs::read_to_string(dir.join("trusted.pub")).unwrap();
+    std::fs::write(
+      dir.join("allowed_signers"),
+      format!("dev namespaces=\"git\" {}", public_key),
+    )
+    .unwrap();
+
+    let repo = dir.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    let git = |args: &[&str]| {
+      let output = Command::new("git").current_dir(&repo).args(args).output().unwrap();
+      assert!(output.status.success(), "git {:?}", args);
+      String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    git(&["init", "--quiet"]);
+
+    let policy = InboundPolicy::from_config(
+      &SecurityConfig {
+        inbound_allowed_signers: Some(PathBuf::from("allowed_signers")),
+        ..SecurityConfig::default()
+      },
+      dir,
+    );
+    // Commit signed with `key` (None = unsigned) and check it against the policy
+    let check = |key: Option<&str>| {
+      let mut args = vec!["-c", "user.name=Dev", "-c", "user.email=dev@example.com"];
+      let signing_key = key.map(|k| format!("user.signingKey={}", dir.join(k).display()));
+      if let Some(ref signing_key) = signing_key {
+        args.extend(["-c", "gpg.format=ssh", "-c", signing_key, "commit", "-S"]);
+      } else {
+        args.push("commit");
+      }
+      args.extend(["--quiet", "--allow-empty", "-m", "change"]);
+      git(&args);
+
+      let mut info = commit("dev@example.com");
+      info.sha = git(&["rev-parse", "HEAD"]);
+      policy.check(&repo, &info).unwrap()
+    };
+
+    assert_eq!(check(Some("trusted")), None);
+    assert_eq!(check(None).as_deref(), Some("not signed"));
+    assert!(check(Some("other")).unwrap().contains("not an allowed signer"));
+  }
+
+  #[test]
+  fn test_glob_match() {
+    let matches = |pattern: &str, path: &str| glob_match(pattern, Path::new(path));
+    assert!(matches("build.rs", "crates/foo/build.rs"));
+    assert!(!matches("build.rs", "crates/foo/build.rs.bak"));
+    assert!(matches("**/.cargo/config.toml", ".cargo/config.toml"));
+    assert!(matches("**/.cargo/config.toml", "crates/foo/.cargo/config.toml"));
+    assert!(matches("crates/*/src/lib.rs", "crates/macros/src/lib.rs"));
+    assert!(!matches("crates/*/src/lib.rs", "crates/a/b/src/lib.rs"));
+    assert!(matches("crates/foo/**", "crates/foo/src/deep/mod.rs"));
+    assert!(matches("*.sh", "scripts/release.sh"));
+    assert!(matches("crates/foo/build.r?", "crates/foo/build.rs"));
+  }
+
+  #[test]
+  fn test_paths_outside_split_or_protected_are_held() {
+    let policy = InboundPolicy::from_config(
+      &SecurityConfig {
+        inbound_protected_paths: vec!["build.rs".to_string()],
+        ..SecurityConfig::default()
+      },
+      Path::new("/ws"),
+    )
+    .with_crate_paths(vec![PathBuf::from("crates/foo")]);
+
+    let info = commit("dev@example.com");
+    assert!(
+      policy
+        .check_paths(&info, &[PathBuf::from("crates/foo/src/lib.rs")])
+        .is_none()
+    );
+
+    let held = policy
+      .check_paths(
+        &info,
+        &[
+          PathBuf::from("crates/foo/src/lib.rs"),
+          PathBuf::from("crates/foo/build.rs"),
+          PathBuf::from(".cargo/config.toml"),
+        ],
+      )
+      .unwrap();
+    assert_eq!(
+      held.paths,
+      vec![
+        PathBuf::from("crates/foo/build.rs"),
+        PathBuf::from(".cargo/config.toml")
+      ]
+    );
+    assert!(held.reason.contains("protected by 'build.rs'"), "{}", held.reason);
+    assert!(
+      held.reason.contains("outside the split's crate paths"),
+      "{}",
+      held.reason
+    );
+  }
+
+  #[test]
+  fn test_default_policy_trusts_everything() {
+    let policy = InboundPolicy::from_config(&SecurityConfig::default(), Path::new("/ws"));
+    assert!(!policy.is_enforced());
+  }
+}
@@ -4,7 +4,7 @@ mod system_git_ops;
 pub use system_git::SystemGit;
 
 /// Information about a commit
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CommitInfo {
   pub sha: String,
   pub author: String,
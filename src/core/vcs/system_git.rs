@@ -6,6 +6,7 @@
 //! - Safe subprocess execution (isolated environment)
 //! - Zero-copy parsing where possible
 
+use crate::core::auth::GitAuth;
 use crate::core::error::{GitError, RailError, RailResult, ResultExt};
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -17,6 +18,12 @@ pub struct SystemGit {
 
   /// Working tree root
   pub(crate) work_tree: PathBuf,
+
+  /// Credentials for commands that reach a remote (fetch, push)
+  pub(crate) auth: GitAuth,
+
+  /// SSH key recreated commits are signed with
+  pub(crate) signing_key: Option<PathBuf>,
 }
 
 impl SystemGit {
@@ -48,9 +55,23 @@ impl SystemGit {
     Ok(Self {
       repo_path: path.to_path_buf(),
       work_tree: PathBuf::from(work_tree),
+      auth: GitAuth::default(),
+      signing_key: None,
     })
   }
 
+  /// Authenticate to remotes with `auth` instead of the default SSH setup
+  pub fn with_auth(mut self, auth: GitAuth) -> Self {
+    self.auth = auth;
+    self
+  }
+
+  /// Sign commits created by `create_commit_with_metadata` with this SSH key
+  pub fn with_signing_key(mut self, signing_key: Option<PathBuf>) -> Self {
+    self.signing_key = signing_key;
+    self
+  }
+
   /// Get HEAD commit SHA
   ///
   /// Note: We don't cache this anymore to avoid interior mutability.
@@ -136,6 +157,7 @@ impl SystemGit {
     cmd.arg("-c").arg("advice.detachedHead=false");
     cmd.arg("-c").arg("core.quotePath=false"); // Don't escape non-ASCII
 
+    self.auth.apply(&mut cmd);
     cmd
   }
 }
@@ -3,6 +3,7 @@
 use super::CommitInfo;
 use super::system_git::SystemGit;
 use crate::core::error::{GitError, RailError, RailResult, ResultExt};
+use crate::core::security::ssh_signing_args;
 use crate::utils;
 use std::path::{Path, PathBuf};
 
@@ -150,8 +151,6 @@ impl SystemGit {
   ///
   /// NOTE: This is a convenience API kept for single-file reads. For multiple files,
   /// use `read_files_bulk()` which is significantly more efficient (single subprocess call).
-  /// Currently unused but maintained as a tested public API for future use cases.
-  #[allow(dead_code)]
   pub fn get_file_at_commit(&self, commit_sha: &str, path: &Path) -> RailResult<Option<Vec<u8>>> {
     let relative_path = self.normalize_path(path);
     let git_path = utils::path_to_git_format(relative_path);
@@ -454,6 +453,46 @@ impl SystemGit {
     Ok(())
   }
 
+  /// Push a branch only if the remote still has it at `expected` (`None`: must not exist yet)
+  ///
+  /// `--force-with-lease` against an explicit SHA, so commits pushed by someone else since the
+  /// last fetch are never overwritten.
+  pub fn push_with_lease(&self, remote_name: &str, branch: &str, expected: Option<&str>) -> RailResult<()> {
+    println!("   Pushing to remote '{}'...", remote_name);
+
+    let lease = format!(
+      "--force-with-lease=refs/heads/{}:{}",
+      branch,
+      expected.unwrap_or_default()
+    );
+    let output = self
+      .git_cmd()
+      .args(["push", "-u", &lease, remote_name, branch])
+      .output()
+      .context("Failed to push")?;
+
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      if stderr.contains("stale info") {
+        return Err(RailError::with_help(
+          format!(
+            "{}/{} moved since it was fetched; nothing was pushed",
+            remote_name, branch
+          ),
+          "Someone pushed to the split repo during the sync. Sync again to pick up their commits",
+        ));
+      }
+      return Err(RailError::Git(GitError::PushFailed {
+        remote: remote_name.to_string(),
+        branch: branch.to_string(),
+        reason: stderr.to_string(),
+      }));
+    }
+
+    println!("   ✅ Pushed to {}/{}", remote_name, branch);
+    Ok(())
+  }
+
   /// Fetch from remote
   pub fn fetch_from_remote(&self, remote_name: &str) -> RailResult<()> {
     println!("   Fetching from remote '{}'...", remote_name);
@@ -527,6 +566,56 @@ impl SystemGit {
     Ok(())
   }
 
+  /// Reset the current branch, index and working tree to a commit
+  pub fn reset_hard(&self, commit_sha: &str) -> RailResult<()> {
+    let output = self
+      .git_cmd()
+      .args(["reset", "--hard", "--quiet", commit_sha])
+      .output()
+      .context("Failed to reset working tree")?;
+
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(RailError::Git(GitError::CommandFailed {
+        command: "git reset --hard".to_string(),
+        stderr: stderr.to_string(),
+      }));
+    }
+
+    Ok(())
+  }
+
+  /// Apply a commit's changes to the index and working tree without committing
+  ///
+  /// Returns false (with the worktree left to be reset) when they don't apply cleanly.
+  pub fn cherry_pick_no_commit(&self, commit_sha: &str) -> RailResult<bool> {
+    let output = self
+      .git_cmd()
+      .args(["cherry-pick", "--no-commit", commit_sha])
+      .output()
+      .context("Failed to run git cherry-pick")?;
+    Ok(output.status.success())
+  }
+
+  /// Absolute path of the repository's git directory (`.git`)
+  pub fn git_dir(&self) -> RailResult<PathBuf> {
+    let output = self
+      .git_cmd()
+      .args(["rev-parse", "--absolute-git-dir"])
+      .output()
+      .context("Failed to locate git directory")?;
+
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(RailError::Git(GitError::CommandFailed {
+        command: "git rev-parse --absolute-git-dir".to_string(),
+        stderr: stderr.to_string(),
+      }));
+    }
+
+    Ok(PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+  }
+
   /// Create and checkout a branch
   pub fn create_and_checkout_branch(&self, branch_name: &str) -> RailResult<()> {
     self.create_branch(branch_name)?;
@@ -572,6 +661,12 @@ impl SystemGit {
     // Build commit-tree command
     let author_date = format!("{} +0000", timestamp);
     let mut cmd = self.git_cmd();
+    if let Some(ref key) = self.signing_key {
+      cmd.args(ssh_signing_args(key));
+      if let Some(sock) = std::env::var_os("SSH_AUTH_SOCK") {
+        cmd.env("SSH_AUTH_SOCK", sock);
+      }
+    }
     cmd
       .env("GIT_AUTHOR_NAME", author_name)
       .env("GIT_AUTHOR_EMAIL", author_email)
@@ -583,6 +678,9 @@ impl SystemGit {
       .arg(&tree_sha)
       .arg("-m")
       .arg(message);
+    if self.signing_key.is_some() {
+      cmd.arg("-S");
+    }
 
     // Add parent arguments
     for parent in parent_shas {
@@ -638,7 +736,6 @@ impl SystemGit {
   }
 
   /// Resolve a git reference (tag, branch) to a commit SHA
-  #[allow(dead_code)]
   pub fn resolve_reference(&self, ref_name: &str) -> RailResult<String> {
     let output = self
       .git_cmd()
@@ -1191,4 +1288,58 @@ mod tests {
     // Should return empty list for non-existent directory
     assert!(files.is_empty(), "Non-existent directory should return empty list");
   }
+  #[test]
+  fn test_push_with_lease_refuses_moved_branch() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let run = |cwd: &Path, args: &[&str]| {
+      let output = std::process::Command::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .unwrap();
+      assert!(
+        output.status.success(),
+        "git {:?}: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+      );
+    };
+    let bare = dir.path().join("remote.git");
+    run(dir.path(), &["init", "--bare", "--initial-branch=main", "remote.git"]);
+
+    let clone = |name: &str| {
+      let path = dir.path().join(name);
+      run(dir.path(), &["clone", "-q", bare.to_str().unwrap(), name]);
+      run(&path, &["config", "user.name", "Test User"]);
+      run(&path, &["config", "user.email", "test@example.com"]);
+      run(&path, &["checkout", "-q", "-B", "main"]);
+      path
+    };
+    let commit = |path: &Path, message: &str| {
+      std::fs::write(path.join(format!("{}.txt", message)), message).unwrap();
+      run(path, &["add", "."]);
+      run(path, &["commit", "-q", "-m", message]);
+    };
+
+    // The branch must not exist yet for an empty lease
+    let ours = clone("ours");
+    commit(&ours, "first");
+    let git = SystemGit::open(&ours).unwrap();
+    git.push_with_lease("origin", "main", None).unwrap();
+    let fetched = git.resolve_reference("origin/main").unwrap();
+
+    // Someone else pushes in between
+    let theirs = clone("theirs");
+    commit(&theirs, "theirs");
+    run(&theirs, &["push", "-q", "origin", "main"]);
+
+    commit(&ours, "second");
+    let err = git.push_with_lease("origin", "main", Some(&fetched)).unwrap_err();
+    assert!(err.to_string().contains("moved since it was fetched"), "{}", err);
+
+    // Their commit is still the remote tip
+    let theirs_head = SystemGit::open(&theirs).unwrap().head_commit().unwrap();
+    git.fetch_from_remote("origin").unwrap();
+    assert_eq!(git.resolve_reference("origin/main").unwrap(), theirs_head);
+  }
 }
@@ -0,0 +1,337 @@
+//! Standalone build verification for split repositories
+//!
+//! A split repo can look fine in git and still fail to build on its own: features that
+//! only unified inside the monorepo, files the split filter left behind, or a manifest
+//! transform that went wrong. `--verify-build` materializes the split tree at a commit
+//! into a scratch directory and runs, in order:
+//!
+//! 1. `cargo metadata --offline` - manifests parse and dependencies resolve
+//! 2. `cargo check --offline --workspace --all-targets` - the code compiles
+//! 3. `cargo package --list --offline --workspace` - the packaged file list is computable
+//!
+//! Everything runs `--offline` against the local registry cache, so verification never
+//! touches the network. Failures are reported with the split paths mapped back to the
+//! monorepo paths that produced them.
+
+use crate::core::config::SplitMode;
+use crate::core::error::{RailError, RailResult, ResultExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Verifies that a split tree builds on its own
+pub struct BuildVerifier {
+  /// Monorepo workspace root (used to map failures back)
+  workspace_root: PathBuf,
+  /// Crate paths in the monorepo (relative to workspace root)
+  crate_paths: Vec<PathBuf>,
+  mode: SplitMode,
+  /// Subdirectory of the split repo holding the split tree (empty = repo root)
+  subdir: PathBuf,
+}
+
+impl BuildVerifier {
+  pub fn new(workspace_root: PathBuf, crate_paths: Vec<PathBuf>, mode: SplitMode) -> Self {
+    Self {
+      workspace_root,
+      crate_paths,
+      mode,
+      subdir: PathBuf::new(),
+    }
+  }
+
+  /// Build from a subdirectory of the split repo (splits with `target_dir`)
+  pub fn with_subdir(mut self, subdir: PathBuf) -> Self {
+    self.subdir = subdir;
+    self
+  }
+
+  /// Verify the split repo at `repo_path` builds standalone at `rev`
+  ///
+  /// Only committed content is checked: the tree is cloned out of the repo, so files that
+  /// exist in the working directory but were never committed cannot mask a missing file.
+  pub fn verify(&self, repo_path: &Path, rev: &str) -> RailResult<()> {
+    println!("   🔎 Verifying standalone build of {}...", short_sha(rev));
+
+    let scratch = std::env::temp_dir().join(format!(
+      "cargo-rail-verify-{}-{}",
+      std::process::id(),
+      std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_else(|_| std::time::Duration::from_secs(0))
+        .as_nanos()
+    ));
+
+    let result = self.materialize(repo_path, rev, &scratch).and_then(|tree| {
+      let root = if self.subdir.as_os_str().is_empty() {
+        tree
+      } else {
+        tree.join(&self.subdir)
+      };
+      self.run_steps(&root, &scratch.join("target"))
+    });
+    let _ = std::fs::remove_dir_all(&scratch);
+    result?;
+
+    println!("   ✅ Split builds standalone");
+    Ok(())
+  }
+
+  /// Check out `rev` of `repo_path` into `scratch/tree`
+  fn materialize(&self, repo_path: &Path, rev: &str, scratch: &Path) -> RailResult<PathBuf> {
+    std::fs::create_dir_all(scratch)?;
+    let tree = scratch.join("tree");
+
+    let clone = Command::new("git")
+      .arg("clone")
+      .args(["--quiet", "--no-checkout", "--shared"])
+      .arg(repo_path)
+      .arg(&tree)
+      .output()
+      .context("Failed to clone split repo for build verification")?;
+    if !clone.status.success() {
+      return Err(RailError::message(format!(
+        "Failed to materialize split tree for verification: {}",
+        String::from_utf8_lossy(&clone.stderr).trim()
+      )));
+    }
+
+    let checkout = Command::new("git")
+      .arg("-C")
+      .arg(&tree)
+      .args([
+        "-c",
+        "advice.detachedHead=false",
+        "checkout",
+        "--quiet",
+        "--detach",
+        rev,
+      ])
+      .output()
+      .context("Failed to check out split tree for build verification")?;
+    if !checkout.status.success() {
+      return Err(RailError::message(format!(
+        "Failed to check out {} for verification: {}",
+        rev,
+        String::from_utf8_lossy(&checkout.stderr).trim()
+      )));
+    }
+
+    Ok(tree)
+  }
+
+  /// Run the verification steps, stopping at the first failure
+  fn run_steps(&self, tree: &Path, target_dir: &Path) -> RailResult<()> {
+    let steps: [(&str, &[&str]); 3] = [
+      (
+        "cargo metadata --offline",
+        &["metadata", "--offline", "--format-version", "1"],
+      ),
+      (
+        "cargo check --offline",
+        &[
+          "check",
+          "--offline",
+          "--workspace",
+          "--all-targets",
+          "--message-format",
+          "short",
+        ],
+      ),
+      (
+        "cargo package --list --offline",
+        &["package", "--list", "--offline", "--workspace", "--allow-dirty"],
+      ),
+    ];
+
+    for (name, args) in steps {
+      println!("      • {}", name);
+      let output = Command::new("cargo")
+        .current_dir(tree)
+        .args(args)
+        .env("CARGO_TARGET_DIR", target_dir)
+        .output()
+        .with_context(|| format!("Failed to run {}", name))?;
+
+      if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(self.failure(name, &stderr, tree));
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Build the error for a failed step, pointing at the monorepo paths involved
+  fn failure(&self, step: &str, stderr: &str, tree: &Path) -> RailError {
+    let tree_prefix = format!("{}/", tree.display());
+    // The root package's directory is quoted without a trailing slash: `app v0.1.0 (/tmp/.../tree)`
+    let stderr = stderr
+      .replace(&tree_prefix, "")
+      .replace(&format!("({})", tree.display()), "()");
+
+    let mut locations = Vec::new();
+    for line in stderr.lines() {
+      if let Some(location) = self.locate(line)
+        && !locations.contains(&location)
+      {
+        locations.push(location);
+      }
+    }
+
+    let details: Vec<&str> = stderr
+      .lines()
+      .filter(|line| line.starts_with("error") || line.contains(": error"))
+      .take(10)
+      .collect();
+
+    let mut message = format!("Split failed standalone build verification at `{}`", step);
+    if !locations.is_empty() {
+      message.push_str("\n\n   Monorepo sources involved:");
+      for location in &locations {
+        message.push_str(&format!("\n     • {}", location));
+      }
+    }
+    if !details.is_empty() {
+      message.push_str("\n\n   Errors:");
+      for line in details {
+        message.push_str(&format!("\n     {}", line));
+      }
+    }
+
+    RailError::with_help(
+      message,
+      "Nothing was pushed. Fix the sources above in the monorepo (or the split config), then re-run",
+    )
+  }
+
+  /// Extract a split-relative file location from a cargo output line and map it to the monorepo
+  ///
+  /// Handles `--message-format short` diagnostics (`src/lib.rs:3:5: error: ...`) and
+  /// manifest errors that quote the file (`` failed to parse manifest at `Cargo.toml` ``) and
+  /// resolution errors naming the package (`` required by package `app v0.1.0 (crates/app)` ``).
+  fn locate(&self, line: &str) -> Option<String> {
+    let line = line.trim();
+
+    if let Some(package) = line.strip_prefix("required by package `")
+      && let Some((_, dir)) = package.split_once(" (")
+      && let Some(dir) = dir.strip_suffix(")`")
+      && !Path::new(dir).is_absolute()
+    {
+      return Some(
+        self
+          .map_to_mono(&Path::new(dir).join("Cargo.toml"))
+          .display()
+          .to_string(),
+      );
+    }
+
+    if let Some(start) = line.find('`') {
+      let rest = &line[start + 1..];
+      if let Some(end) = rest.find('`') {
+        let quoted = &rest[..end];
+        if quoted.ends_with("Cargo.toml") && !quoted.contains(' ') {
+          return Some(self.map_to_mono(Path::new(quoted)).display().to_string());
+        }
+      }
+    }
+
+    let (file, rest) = line.split_once(':')?;
+    if !file.ends_with(".rs") || file.contains(' ') {
+      return None;
+    }
+    let position: Vec<&str> = rest.splitn(3, ':').take(2).collect();
+    let mono = self.map_to_mono(Path::new(file)).display().to_string();
+    match position.as_slice() {
+      [line_no, col] if line_no.parse::<u32>().is_ok() && col.parse::<u32>().is_ok() => {
+        Some(format!("{}:{}:{}", mono, line_no, col))
+      }
+      _ => Some(mono),
+    }
+  }
+
+  /// Map a path in the split repo to the monorepo path it came from
+  fn map_to_mono(&self, split_path: &Path) -> PathBuf {
+    match self.mode {
+      SplitMode::Single => {
+        let crate_path = &self.crate_paths[0];
+        let candidate = crate_path.join(split_path);
+        // Workspace-level files (rustfmt.toml, ...) are copied to the split root as-is
+        if self.workspace_root.join(&candidate).exists() || !self.workspace_root.join(split_path).exists() {
+          candidate
+        } else {
+          split_path.to_path_buf()
+        }
+      }
+      // Combined splits keep the monorepo layout
+      SplitMode::Combined => split_path.to_path_buf(),
+    }
+  }
+}
+
+fn short_sha(rev: &str) -> &str {
+  &rev[..rev.len().min(7)]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn verifier(mode: SplitMode, paths: &[&str]) -> BuildVerifier {
+    BuildVerifier::new(
+      PathBuf::from("/nonexistent-workspace"),
+      paths.iter().map(PathBuf::from).collect(),
+      mode,
+    )
+  }
+
+  #[test]
+  fn test_locate_short_diagnostic_single_mode() {
+    let v = verifier(SplitMode::Single, &["crates/app"]);
+    assert_eq!(
+      v.locate("src/lib.rs:3:5: error[E0425]: cannot find value `x` in this scope"),
+      Some("crates/app/src/lib.rs:3:5".to_string())
+    );
+  }
+
+  #[test]
+  fn test_locate_short_diagnostic_combined_mode() {
+    let v = verifier(SplitMode::Combined, &["crates/a", "crates/b"]);
+    assert_eq!(
+      v.locate("crates/b/src/main.rs:10:1: error: expected item"),
+      Some("crates/b/src/main.rs:10:1".to_string())
+    );
+  }
+
+  #[test]
+  fn test_locate_manifest_error() {
+    let v = verifier(SplitMode::Single, &["crates/app"]);
+    assert_eq!(
+      v.locate("error: failed to parse manifest at `Cargo.toml`"),
+      Some("crates/app/Cargo.toml".to_string())
+    );
+  }
+
+  #[test]
+  fn test_locate_unresolved_dependency() {
+    let v = verifier(SplitMode::Single, &["crates/app"]);
+    assert_eq!(
+      v.locate("required by package `app v0.1.0 ()`"),
+      Some("crates/app/Cargo.toml".to_string())
+    );
+    let v = verifier(SplitMode::Combined, &["crates/app", "crates/util"]);
+    assert_eq!(
+      v.locate("required by package `util v0.1.0 (crates/util)`"),
+      Some("crates/util/Cargo.toml".to_string())
+    );
+  }
+
+  #[test]
+  fn test_locate_ignores_unrelated_lines() {
+    let v = verifier(SplitMode::Single, &["crates/app"]);
+    assert_eq!(v.locate("    Checking app v0.1.0"), None);
+    assert_eq!(
+      v.locate("error: could not compile `app` (lib) due to 1 previous error"),
+      None
+    );
+  }
+}
@@ -0,0 +1,133 @@
+//! Gitea (and Forgejo) pull requests (REST API v1)
+
+use super::http::HttpClient;
+use super::{ForgeClient, PullRequest, PullRequestSpec, merge_pull_request_body, number_of};
+use crate::core::error::{RailError, RailResult};
+use serde_json::{Value, json};
+
+pub struct Gitea {
+  http: HttpClient,
+  repo: String,
+}
+
+impl Gitea {
+  pub fn new(api_url: &str, repo: &str, token: &str) -> Self {
+    Self {
+      http: HttpClient::new(api_url, vec![format!("Authorization: token {}", token)]),
+      repo: repo.to_string(),
+    }
+  }
+
+  /// Labels are attached by ID
+  fn label_ids(&self, names: &[String]) -> RailResult<Vec<u64>> {
+    let labels = self.http.get(&format!("/repos/{}/labels", self.repo))?;
+    let labels = labels.as_array().map(Vec::as_slice).unwrap_or_default();
+    names
+      .iter()
+      .map(|name| {
+        labels
+          .iter()
+          .find(|label| label["name"].as_str() == Some(name))
+          .ok_or_else(|| RailError::message(format!("Gitea label '{}' does not exist in {}", name, self.repo)))
+          .and_then(|label| number_of(label, "id"))
+      })
+      .collect()
+  }
+
+  fn open_pull_for(&self, head: &str) -> RailResult<Option<Value>> {
+    let open = self.http.get(&format!("/repos/{}/pulls?state=open", self.repo))?;
+    Ok(
+      open
+        .as_array()
+        .and_then(|prs| prs.iter().find(|pr| pr["head"]["ref"].as_str() == Some(head)))
+        .cloned(),
+    )
+  }
+}
+
+impl ForgeClient for Gitea {
+  fn name(&self) -> &'static str {
+    "Gitea"
+  }
+
+  fn open_or_update(&self, spec: &PullRequestSpec) -> RailResult<PullRequest> {
+    let (pr, created) = match self.open_pull_for(&spec.head)? {
+      Some(existing) => {
+        let number = number_of(&existing, "number")?;
+        let body = merge_pull_request_body(existing["body"].as_str().unwrap_or_default(), &spec.body);
+        let pr = self.http.patch(
+          &format!("/repos/{}/pulls/{}", self.repo, number),
+          &json!({ "title": spec.title, "body": body }),
+        )?;
+        (pr, false)
+      }
+      None => {
+        let pr = self.http.post(
+          &format!("/repos/{}/pulls", self.repo),
+          &json!({ "title": spec.title, "head": spec.head, "base": spec.base, "body": spec.body }),
+        )?;
+        (pr, true)
+      }
+    };
+    let number = number_of(&pr, "number")?;
+
+    if !spec.labels.is_empty() {
+      let ids = self.label_ids(&spec.labels)?;
+      self.http.post(
+        &format!("/repos/{}/issues/{}/labels", self.repo, number),
+        &json!({ "labels": ids }),
+      )?;
+    }
+    if !spec.reviewers.is_empty() {
+      self.http.post(
+        &format!("/repos/{}/pulls/{}/requested_reviewers", self.repo, number),
+        &json!({ "reviewers": spec.reviewers }),
+      )?;
+    }
+
+    Ok(PullRequest {
+      number,
+      url: pr["html_url"].as_str().unwrap_or_default().to_string(),
+      created,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::forge::tests::{StandIn, spec};
+
+  #[test]
+  fn test_updates_open_pull_request_and_labels_by_id() {
+    let server = StandIn::start(|method, path| match (method, path) {
+      ("GET", "/repos/acme/mono/pulls?state=open") => (
+        200,
+        r#"[{"number": 1, "head": {"ref": "other"}}, {"number": 9, "head": {"ref": "rail/sync/my-crate"}}]"#
+          .to_string(),
+      ),
+      ("GET", "/repos/acme/mono/labels") => (
+        200,
+        r#"[{"id": 3, "name": "bug"}, {"id": 4, "name": "sync"}]"#.to_string(),
+      ),
+      _ => (
+        200,
+        r#"{"number": 9, "html_url": "https://gitea.test/acme/mono/pulls/9"}"#.to_string(),
+      ),
+    });
+
+    let pr = Gitea::new(&server.url, "acme/mono", "s3cret")
+      .open_or_update(&spec())
+      .unwrap();
+    assert_eq!(pr.number, 9);
+    assert!(!pr.created);
+
+    let requests = server.requests();
+    assert!(requests[0].headers.contains(&"authorization: token s3cret".to_string()));
+    assert_eq!(requests[1].method, "PATCH");
+    assert_eq!(requests[1].path, "/repos/acme/mono/pulls/9");
+    assert_eq!(requests[3].path, "/repos/acme/mono/issues/9/labels");
+    assert_eq!(requests[3].body["labels"][0], 4);
+    assert_eq!(requests[4].body["reviewers"][0], "alice");
+  }
+}
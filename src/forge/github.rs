@@ -0,0 +1,168 @@
+//! GitHub pull requests (REST API v3)
+
+use super::http::{HttpClient, encode};
+use super::{ForgeClient, PullRequest, PullRequestSpec, merge_pull_request_body, number_of};
+use crate::core::error::RailResult;
+use serde_json::json;
+
+pub struct GitHub {
+  http: HttpClient,
+  repo: String,
+}
+
+impl GitHub {
+  pub fn new(api_url: &str, repo: &str, token: &str) -> Self {
+    Self {
+      http: HttpClient::new(
+        api_url,
+        vec![
+          format!("Authorization: Bearer {}", token),
+          "X-GitHub-Api-Version: 2022-11-28".to_string(),
+        ],
+      ),
+      repo: repo.to_string(),
+    }
+  }
+}
+
+impl ForgeClient for GitHub {
+  fn name(&self) -> &'static str {
+    "GitHub"
+  }
+
+  fn open_or_update(&self, spec: &PullRequestSpec) -> RailResult<PullRequest> {
+    let owner = self.repo.split('/').next().unwrap_or_default();
+    let open = self.http.get(&format!(
+      "/repos/{}/pulls?state=open&head={}",
+      self.repo,
+      encode(&format!("{}:{}", owner, spec.head))
+    ))?;
+
+    let (pr, created) = match open.as_array().and_then(|prs| prs.first()) {
+      Some(existing) => {
+        let number = number_of(existing, "number")?;
+        let body = merge_pull_request_body(existing["body"].as_str().unwrap_or_default(), &spec.body);
+        let pr = self.http.patch(
+          &format!("/repos/{}/pulls/{}", self.repo, number),
+          &json!({ "title": spec.title, "body": body }),
+        )?;
+        (pr, false)
+      }
+      None => {
+        let pr = self.http.post(
+          &format!("/repos/{}/pulls", self.repo),
+          &json!({ "title": spec.title, "head": spec.head, "base": spec.base, "body": spec.body }),
+        )?;
+        (pr, true)
+      }
+    };
+    let number = number_of(&pr, "number")?;
+
+    // PRs are issues as far as labels go
+    if !spec.labels.is_empty() {
+      self.http.post(
+        &format!("/repos/{}/issues/{}/labels", self.repo, number),
+        &json!({ "labels": spec.labels }),
+      )?;
+    }
+    if !spec.reviewers.is_empty() {
+      self.http.post(
+        &format!("/repos/{}/pulls/{}/requested_reviewers", self.repo, number),
+        &json!({ "reviewers": spec.reviewers }),
+      )?;
+    }
+
+    Ok(PullRequest {
+      number,
+      url: pr["html_url"].as_str().unwrap_or_default().to_string(),
+      created,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::forge::tests::{StandIn, spec};
+
+  #[test]
+  fn test_opens_pull_request_with_labels_and_reviewers() {
+    let server = StandIn::start(|method, path| match (method, path) {
+      ("GET", _) => (200, "[]".to_string()),
+      ("POST", "/repos/acme/mono/pulls") => (
+        201,
+        r#"{"number": 7, "html_url": "https://github.test/acme/mono/pull/7"}"#.to_string(),
+      ),
+      _ => (200, "{}".to_string()),
+    });
+
+    let pr = GitHub::new(&server.url, "acme/mono", "s3cret")
+      .open_or_update(&spec())
+      .unwrap();
+    assert_eq!(pr.number, 7);
+    assert!(pr.created);
+    assert_eq!(pr.url, "https://github.test/acme/mono/pull/7");
+
+    let requests = server.requests();
+    assert_eq!(
+      requests[0].path,
+      "/repos/acme/mono/pulls?state=open&head=acme%3Arail%2Fsync%2Fmy-crate"
+    );
+    assert!(
+      requests[0]
+        .headers
+        .contains(&"authorization: Bearer s3cret".to_string())
+    );
+    assert_eq!(requests[1].body["head"], "rail/sync/my-crate");
+    assert_eq!(requests[1].body["base"], "main");
+    assert_eq!(requests[2].path, "/repos/acme/mono/issues/7/labels");
+    assert_eq!(requests[2].body["labels"][0], "sync");
+    assert_eq!(requests[3].path, "/repos/acme/mono/pulls/7/requested_reviewers");
+    assert_eq!(requests[3].body["reviewers"][0], "alice");
+  }
+
+  #[test]
+  fn test_updates_open_pull_request_for_branch() {
+    let server = StandIn::start(|method, _| match method {
+      "GET" => (
+        200,
+        r#"[{"number": 3, "body": "Imports 1 commit(s).\n\n### Commits\n\n- `1111111` Earlier import (Dev)\n"}]"#
+          .to_string(),
+      ),
+      _ => (200, r#"{"number": 3, "html_url": "u"}"#.to_string()),
+    });
+
+    let mut spec = spec();
+    spec.body = "Imports 1 commit(s).\n\n### Commits\n\n- `2222222` New import (Dev)\n".to_string();
+    spec.labels.clear();
+    spec.reviewers.clear();
+    let pr = GitHub::new(&server.url, "acme/mono", "t")
+      .open_or_update(&spec)
+      .unwrap();
+    assert_eq!(pr.number, 3);
+    assert!(!pr.created);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "PATCH");
+    assert_eq!(requests[1].path, "/repos/acme/mono/pulls/3");
+    // Commits imported by earlier syncs stay listed
+    let body = requests[1].body["body"].as_str().unwrap();
+    assert!(body.starts_with("Imports 2 commit(s)."), "{}", body);
+    assert!(
+      body.contains("- `1111111` Earlier import (Dev)\n- `2222222` New import (Dev)\n"),
+      "{}",
+      body
+    );
+  }
+
+  #[test]
+  fn test_api_errors_carry_the_forge_message() {
+    let server = StandIn::start(|_, _| (401, r#"{"message": "Bad credentials"}"#.to_string()));
+    let err = GitHub::new(&server.url, "acme/mono", "t")
+      .open_or_update(&spec())
+      .unwrap_err();
+    assert!(err.to_string().contains("HTTP 401"), "{}", err);
+    assert!(err.to_string().contains("Bad credentials"), "{}", err);
+  }
+}
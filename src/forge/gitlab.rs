@@ -0,0 +1,124 @@
+//! GitLab merge requests (REST API v4)
+
+use super::http::{HttpClient, encode};
+use super::{ForgeClient, PullRequest, PullRequestSpec, merge_pull_request_body, number_of};
+use crate::core::error::{RailError, RailResult};
+use serde_json::json;
+
+pub struct GitLab {
+  http: HttpClient,
+  /// URL-encoded project path, GitLab's project ID in paths
+  project: String,
+}
+
+impl GitLab {
+  pub fn new(api_url: &str, repo: &str, token: &str) -> Self {
+    Self {
+      http: HttpClient::new(api_url, vec![format!("PRIVATE-TOKEN: {}", token)]),
+      project: encode(repo),
+    }
+  }
+
+  /// Reviewers are assigned by user ID
+  fn user_id(&self, username: &str) -> RailResult<u64> {
+    let users = self.http.get(&format!("/users?username={}", encode(username)))?;
+    match users.as_array().and_then(|users| users.first()) {
+      Some(user) => number_of(user, "id"),
+      None => Err(RailError::message(format!("GitLab user '{}' not found", username))),
+    }
+  }
+}
+
+impl ForgeClient for GitLab {
+  fn name(&self) -> &'static str {
+    "GitLab"
+  }
+
+  fn open_or_update(&self, spec: &PullRequestSpec) -> RailResult<PullRequest> {
+    let reviewer_ids = spec
+      .reviewers
+      .iter()
+      .map(|name| self.user_id(name))
+      .collect::<RailResult<Vec<_>>>()?;
+    let mut fields = json!({
+      "title": spec.title,
+      "description": spec.body,
+      "labels": spec.labels.join(","),
+    });
+    if !reviewer_ids.is_empty() {
+      fields["reviewer_ids"] = json!(reviewer_ids);
+    }
+
+    let open = self.http.get(&format!(
+      "/projects/{}/merge_requests?state=opened&source_branch={}",
+      self.project,
+      encode(&spec.head)
+    ))?;
+
+    let (mr, created) = match open.as_array().and_then(|mrs| mrs.first()) {
+      Some(existing) => {
+        let iid = number_of(existing, "iid")?;
+        fields["description"] = json!(merge_pull_request_body(
+          existing["description"].as_str().unwrap_or_default(),
+          &spec.body
+        ));
+        let mr = self
+          .http
+          .put(&format!("/projects/{}/merge_requests/{}", self.project, iid), &fields)?;
+        (mr, false)
+      }
+      None => {
+        fields["source_branch"] = json!(spec.head);
+        fields["target_branch"] = json!(spec.base);
+        let mr = self
+          .http
+          .post(&format!("/projects/{}/merge_requests", self.project), &fields)?;
+        (mr, true)
+      }
+    };
+
+    Ok(PullRequest {
+      number: number_of(&mr, "iid")?,
+      url: mr["web_url"].as_str().unwrap_or_default().to_string(),
+      created,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::forge::tests::{StandIn, spec};
+
+  #[test]
+  fn test_opens_merge_request_with_reviewer_ids() {
+    let server = StandIn::start(|method, path| match (method, path) {
+      ("GET", p) if p.starts_with("/users") => (200, r#"[{"id": 42}]"#.to_string()),
+      ("GET", _) => (200, "[]".to_string()),
+      _ => (
+        201,
+        r#"{"iid": 5, "web_url": "https://gitlab.test/acme/mono/-/merge_requests/5"}"#.to_string(),
+      ),
+    });
+
+    let pr = GitLab::new(&server.url, "acme/mono", "s3cret")
+      .open_or_update(&spec())
+      .unwrap();
+    assert_eq!(pr.number, 5);
+    assert!(pr.created);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/users?username=alice");
+    assert!(requests[0].headers.contains(&"private-token: s3cret".to_string()));
+    assert_eq!(
+      requests[1].path,
+      "/projects/acme%2Fmono/merge_requests?state=opened&source_branch=rail%2Fsync%2Fmy-crate"
+    );
+    let created = &requests[2];
+    assert_eq!(created.path, "/projects/acme%2Fmono/merge_requests");
+    assert_eq!(created.body["source_branch"], "rail/sync/my-crate");
+    assert_eq!(created.body["target_branch"], "main");
+    assert_eq!(created.body["labels"], "sync");
+    assert_eq!(created.body["reviewer_ids"][0], 42);
+  }
+}
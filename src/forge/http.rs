@@ -0,0 +1,130 @@
+//! Minimal JSON-over-HTTP client on top of the system `curl`
+//!
+//! Headers and request bodies are handed to curl as a config file on stdin, so API tokens
+//! never show up in the process list.
+
+use crate::core::error::{RailError, RailResult, ResultExt};
+use serde_json::Value;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// A forge API at `base_url`, sending the same headers with every request
+pub struct HttpClient {
+  base_url: String,
+  headers: Vec<String>,
+}
+
+impl HttpClient {
+  pub fn new(base_url: &str, headers: Vec<String>) -> Self {
+    Self {
+      base_url: base_url.trim_end_matches('/').to_string(),
+      headers,
+    }
+  }
+
+  pub fn get(&self, path: &str) -> RailResult<Value> {
+    self.send("GET", path, None)
+  }
+
+  pub fn post(&self, path: &str, body: &Value) -> RailResult<Value> {
+    self.send("POST", path, Some(body))
+  }
+
+  pub fn patch(&self, path: &str, body: &Value) -> RailResult<Value> {
+    self.send("PATCH", path, Some(body))
+  }
+
+  pub fn put(&self, path: &str, body: &Value) -> RailResult<Value> {
+    self.send("PUT", path, Some(body))
+  }
+
+  /// Send a request; non-2xx answers become errors carrying the forge's message
+  fn send(&self, method: &str, path: &str, body: Option<&Value>) -> RailResult<Value> {
+    let mut config = format!(
+      "url = \"{}\"\nrequest = \"{}\"\n",
+      quote(&format!("{}{}", self.base_url, path)),
+      method
+    );
+    for header in self.headers.iter().map(String::as_str).chain([
+      "Accept: application/json",
+      "Content-Type: application/json",
+      "User-Agent: cargo-rail",
+    ]) {
+      config.push_str(&format!("header = \"{}\"\n", quote(header)));
+    }
+    if let Some(body) = body {
+      config.push_str(&format!("data-binary = \"{}\"\n", quote(&body.to_string())));
+    }
+
+    let mut child = Command::new("curl")
+      .args([
+        "--silent",
+        "--show-error",
+        "--config",
+        "-",
+        "--write-out",
+        "\n%{http_code}",
+      ])
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .context("Failed to run curl (needed to talk to the forge API)")?;
+    child
+      .stdin
+      .take()
+      .expect("stdin is piped")
+      .write_all(config.as_bytes())
+      .context("Failed to pass the request to curl")?;
+    let output = child.wait_with_output().context("Failed to run curl")?;
+
+    if !output.status.success() {
+      return Err(RailError::message(format!(
+        "{} {} failed: {}",
+        method,
+        path,
+        String::from_utf8_lossy(&output.stderr).trim()
+      )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (response, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let status: u16 = status.trim().parse().unwrap_or(0);
+    if !(200..300).contains(&status) {
+      let message = serde_json::from_str::<Value>(response)
+        .ok()
+        .and_then(|v| v.get("message").map(|m| m.to_string()))
+        .unwrap_or_else(|| response.trim().to_string());
+      return Err(RailError::with_help(
+        format!("{} {} returned HTTP {}: {}", method, path, status, message),
+        "Check the repo and token in the [forge] section of rail.toml",
+      ));
+    }
+
+    if response.trim().is_empty() {
+      return Ok(Value::Null);
+    }
+    serde_json::from_str(response).with_context(|| format!("Invalid JSON from {} {}", method, path))
+  }
+}
+
+/// Escape a value for a double-quoted curl config string
+fn quote(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n")
+    .replace('\r', "\\r")
+    .replace('\t', "\\t")
+}
+
+/// Percent-encode a URL path segment or query value
+pub fn encode(value: &str) -> String {
+  value
+    .bytes()
+    .map(|b| match b {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+      _ => format!("%{:02X}", b),
+    })
+    .collect()
+}
@@ -0,0 +1,383 @@
+//! Forge integration: pull requests for the branches remote → mono syncs push
+//!
+//! When the monorepo's current branch is protected, `sync --from-remote` commits onto a PR
+//! branch and pushes it. With a `[forge]` section in rail.toml, it then opens a pull request
+//! for that branch, or updates the one already open for it.
+//!
+//! All forges implement `ForgeClient`. Requests go through the system `curl` (like git, no
+//! HTTP crate), and the API token is read from an environment variable.
+//!
+//! # Example rail.toml
+//!
+//! ```toml
+//! [forge]
+//! kind = "github"               # or "gitlab", "gitea"
+//! repo = "acme/monorepo"
+//! # api_url = "https://git.example.com/api/v1"  # required for gitea
+//! # token_env = "GITHUB_TOKEN"                 # default per kind
+//! labels = ["sync"]
+//! reviewers = ["alice"]
+//! ```
+
+mod gitea;
+mod github;
+mod gitlab;
+mod http;
+
+use crate::core::config::{ForgeConfig, ForgeKind};
+use crate::core::conflict::ConflictInfo;
+use crate::core::error::{RailError, RailResult};
+use crate::core::trust::QuarantinedCommit;
+use crate::core::vcs::CommitInfo;
+use serde_json::Value;
+
+/// The pull request to open (or update) for a PR branch
+#[derive(Debug, Clone)]
+pub struct PullRequestSpec {
+  /// Branch with the imported commits
+  pub head: String,
+  /// Protected branch it merges into
+  pub base: String,
+  pub title: String,
+  pub body: String,
+  pub labels: Vec<String>,
+  pub reviewers: Vec<String>,
+}
+
+/// A pull request on the forge
+#[derive(Debug, Clone)]
+pub struct PullRequest {
+  /// PR number (GitLab: the project-scoped MR IID)
+  pub number: u64,
+  pub url: String,
+  /// False when an already open PR was updated
+  pub created: bool,
+}
+
+/// A forge that pull requests can be opened on
+pub trait ForgeClient {
+  /// Forge name for messages
+  fn name(&self) -> &'static str;
+
+  /// Open a PR for `spec.head`, or update the one already open (its body keeps earlier entries)
+  fn open_or_update(&self, spec: &PullRequestSpec) -> RailResult<PullRequest>;
+}
+
+/// Client for the configured forge, authenticated with the token from its env var
+pub fn client(config: &ForgeConfig) -> RailResult<Box<dyn ForgeClient>> {
+  config.validate()?;
+  let api_url = config.api_url().unwrap_or_default();
+  let token = std::env::var(config.token_env())
+    .ok()
+    .filter(|token| !token.is_empty())
+    .ok_or_else(|| {
+      RailError::with_help(
+        format!("No forge token: ${} is not set", config.token_env()),
+        "Export an API token that can open pull requests, or set token_env in [forge]",
+      )
+    })?;
+
+  Ok(match config.kind {
+    ForgeKind::GitHub => Box::new(github::GitHub::new(&api_url, &config.repo, &token)),
+    ForgeKind::GitLab => Box::new(gitlab::GitLab::new(&api_url, &config.repo, &token)),
+    ForgeKind::Gitea => Box::new(gitea::Gitea::new(&api_url, &config.repo, &token)),
+  })
+}
+
+const COMMITS: &str = "### Commits";
+const CONFLICTS: &str = "### Conflicts resolved";
+const HELD_BACK: &str = "### Held back by the inbound trust policy";
+
+/// Pull request description for commits imported from a split repo
+pub fn pull_request_body(
+  crate_name: &str,
+  commits: &[CommitInfo],
+  conflicts: &[ConflictInfo],
+  quarantined: &[QuarantinedCommit],
+) -> String {
+  let commits: Vec<String> = commits
+    .iter()
+    .map(|commit| {
+      format!(
+        "`{}` {} ({})",
+        short(&commit.sha),
+        commit.message.lines().next().unwrap_or_default(),
+        commit.author
+      )
+    })
+    .collect();
+  let conflicts = conflicts
+    .iter()
+    .map(|conflict| format!("`{}`: {}", conflict.file_path.display(), conflict.message))
+    .collect();
+  let held_back = quarantined
+    .iter()
+    .map(|held| format!("`{}` {} — {}", short(&held.sha), held.summary, held.reason))
+    .collect();
+
+  render_body(
+    &format!(
+      "Imports {} commit(s) from the `{}` split repository.",
+      commits.len(),
+      crate_name
+    ),
+    [commits, conflicts, held_back],
+  )
+}
+
+/// Description for an open pull request gaining another sync's imports
+///
+/// The PR branch still carries the commits of earlier syncs, so the entries already listed
+/// in `existing` are kept and `update`'s are added after them.
+pub fn merge_pull_request_body(existing: &str, update: &str) -> String {
+  let mut sections = body_sections(existing);
+  if sections.iter().all(Vec::is_empty) {
+    return update.to_string();
+  }
+  for (section, entries) in sections.iter_mut().zip(body_sections(update)) {
+    for entry in entries {
+      if !section.contains(&entry) {
+        section.push(entry);
+      }
+    }
+  }
+
+  // The intro counts the commits listed below it
+  let intro = update.lines().next().unwrap_or_default();
+  let intro = match intro.split_once(" commit(s)") {
+    Some((_, rest)) => format!("Imports {} commit(s){}", sections[0].len(), rest),
+    None => intro.to_string(),
+  };
+  render_body(&intro, sections)
+}
+
+fn render_body(intro: &str, [commits, conflicts, held_back]: [Vec<String>; 3]) -> String {
+  let mut body = format!("{}\n\n", intro);
+  for (heading, entries) in [(COMMITS, commits), (CONFLICTS, conflicts), (HELD_BACK, held_back)] {
+    if entries.is_empty() && heading != COMMITS {
+      continue;
+    }
+    body.push_str(&format!("{}\n\n", heading));
+    for entry in entries {
+      body.push_str(&format!("- {}\n", entry));
+    }
+    body.push('\n');
+  }
+  body.push_str("_Opened by `cargo rail sync`._\n");
+  body
+}
+
+/// List entries of a body written by `render_body`: commits, conflicts, held back
+fn body_sections(body: &str) -> [Vec<String>; 3] {
+  let mut sections: [Vec<String>; 3] = Default::default();
+  let mut current = None;
+  for line in body.lines() {
+    match line.trim_end() {
+      COMMITS => current = Some(0),
+      CONFLICTS => current = Some(1),
+      HELD_BACK => current = Some(2),
+      line if line.starts_with('#') => current = None,
+      line => {
+        if let (Some(index), Some(entry)) = (current, line.strip_prefix("- ")) {
+          sections[index].push(entry.to_string());
+        }
+      }
+    }
+  }
+  sections
+}
+
+fn short(sha: &str) -> &str {
+  &sha[..7.min(sha.len())]
+}
+
+/// Numeric field of an API object
+fn number_of(object: &Value, field: &str) -> RailResult<u64> {
+  object[field]
+    .as_u64()
+    .ok_or_else(|| RailError::message(format!("Forge response has no numeric '{}': {}", field, object)))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use super::*;
+  use std::io::{BufRead, BufReader, Read, Write};
+  use std::net::TcpListener;
+  use std::path::PathBuf;
+  use std::sync::{Arc, Mutex};
+
+  /// A request the stand-in received
+  #[derive(Debug, Clone)]
+  pub struct Recorded {
+    pub method: String,
+    pub path: String,
+    /// Lowercased `name: value` lines
+    pub headers: Vec<String>,
+    pub body: Value,
+  }
+
+  /// Local HTTP stand-in for a forge API, answering each request from a route function
+  pub struct StandIn {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+  }
+
+  impl StandIn {
+    pub fn start(route: impl Fn(&str, &str) -> (u16, String) + Send + 'static) -> Self {
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let url = format!("http://{}", listener.local_addr().unwrap());
+      let requests = Arc::new(Mutex::new(Vec::new()));
+      let recorded = Arc::clone(&requests);
+
+      std::thread::spawn(move || {
+        for stream in listener.incoming() {
+          let Ok(mut stream) = stream else { break };
+          let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+          let mut request_line = String::new();
+          reader.read_line(&mut request_line).unwrap();
+          let mut parts = request_line.split_whitespace();
+          let method = parts.next().unwrap_or_default().to_string();
+          let path = parts.next().unwrap_or_default().to_string();
+
+          let mut headers = Vec::new();
+          let mut length = 0;
+          loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+              break;
+            }
+            let (name, value) = line.split_once(':').unwrap_or((line, ""));
+            let (name, value) = (name.to_lowercase(), value.trim());
+            if name == "content-length" {
+              length = value.parse().unwrap_or(0);
+            }
+            headers.push(format!("{}: {}", name, value));
+          }
+          let mut body = vec![0; length];
+          reader.read_exact(&mut body).unwrap();
+
+          let (status, response) = route(&method, &path);
+          recorded.lock().unwrap().push(Recorded {
+            method,
+            path,
+            headers,
+            body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+          });
+          let _ = write!(
+            stream,
+            "HTTP/1.1 {} Stand-in\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            response.len(),
+            response
+          );
+        }
+      });
+
+      Self { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Recorded> {
+      self.requests.lock().unwrap().clone()
+    }
+  }
+
+  pub fn spec() -> PullRequestSpec {
+    PullRequestSpec {
+      head: "rail/sync/my-crate".to_string(),
+      base: "main".to_string(),
+      title: "Sync my-crate".to_string(),
+      body: "Imports \"1\" commit\nwith\\escapes".to_string(),
+      labels: vec!["sync".to_string()],
+      reviewers: vec!["alice".to_string()],
+    }
+  }
+
+  #[test]
+  fn test_pull_request_body_lists_commits_conflicts_and_held_back() {
+    let commit = CommitInfo {
+      sha: "abcdef0123456789".to_string(),
+      author: "Dev".to_string(),
+      author_email: "dev@example.com".to_string(),
+      committer: "Dev".to_string(),
+      committer_email: "dev@example.com".to_string(),
+      message: "Fix parser\n\nDetails".to_string(),
+      timestamp: 0,
+      parent_shas: Vec::new(),
+    };
+    let conflict = ConflictInfo {
+      file_path: PathBuf::from("crates/my-crate/src/lib.rs"),
+      message: "merged both sides".to_string(),
+      resolved: true,
+      versions: None,
+    };
+    let held = QuarantinedCommit::new(&commit, "not signed");
+
+    let body = pull_request_body("my-crate", std::slice::from_ref(&commit), &[conflict], &[held]);
+    assert!(body.starts_with("Imports 1 commit(s) from the `my-crate` split repository."));
+    assert!(body.contains("- `abcdef0` Fix parser (Dev)"));
+    assert!(body.contains("- `crates/my-crate/src/lib.rs`: merged both sides"));
+    assert!(body.contains("- `abcdef0` Fix parser — not signed"));
+  }
+
+  #[test]
+  fn test_merged_body_keeps_earlier_imports() {
+    let commit = |sha: &str, message: &str| CommitInfo {
+      sha: sha.to_string(),
+      author: "Dev".to_string(),
+      author_email: "dev@example.com".to_string(),
+      committer: "Dev".to_string(),
+      committer_email: "dev@example.com".to_string(),
+      message: message.to_string(),
+      timestamp: 0,
+      parent_shas: Vec::new(),
+    };
+    let first = commit("1111111aaaa", "First change");
+    let conflict = ConflictInfo {
+      file_path: PathBuf::from("crates/my-crate/src/lib.rs"),
+      message: "merged both sides".to_string(),
+      resolved: true,
+      versions: None,
+    };
+    let existing = pull_request_body("my-crate", std::slice::from_ref(&first), &[conflict], &[]);
+    let held = QuarantinedCommit::new(&commit("3333333cccc", "Unsigned"), "not signed");
+    let update = pull_request_body("my-crate", &[commit("2222222bbbb", "Second change")], &[], &[held]);
+
+    let body = merge_pull_request_body(&existing, &update);
+    assert!(
+      body.starts_with("Imports 2 commit(s) from the `my-crate` split repository."),
+      "{}",
+      body
+    );
+    assert!(
+      body.contains("- `1111111` First change (Dev)\n- `2222222` Second change (Dev)\n"),
+      "{}",
+      body
+    );
+    assert!(
+      body.contains("- `crates/my-crate/src/lib.rs`: merged both sides"),
+      "{}",
+      body
+    );
+    assert!(body.contains("- `3333333` Unsigned — not signed"), "{}", body);
+
+    // Updating again with the same sync changes nothing
+    assert_eq!(merge_pull_request_body(&body, &update), body);
+  }
+
+  #[test]
+  fn test_client_requires_token() {
+    let config = ForgeConfig {
+      kind: ForgeKind::GitHub,
+      repo: "acme/mono".to_string(),
+      api_url: None,
+      token_env: Some("CARGO_RAIL_TEST_UNSET_FORGE_TOKEN".to_string()),
+      labels: Vec::new(),
+      reviewers: Vec::new(),
+    };
+    let err = client(&config).err().unwrap();
+    assert!(err.to_string().contains("CARGO_RAIL_TEST_UNSET_FORGE_TOKEN"), "{}", err);
+  }
+}
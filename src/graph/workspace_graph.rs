@@ -39,6 +39,9 @@ pub struct PackageNode {
   pub version: String,
   pub manifest_path: PathBuf,
   pub is_workspace_member: bool,
+  /// False when the manifest sets `publish = false` or limits `publish` to other registries
+  /// than crates.io (what split manifests resolve version dependencies against)
+  pub publishable: bool,
   /// Visibility tiers this crate belongs to (computed from ReleaseConfig)
   /// A crate can appear in multiple releases with different visibility levels
   /// TODO: Used by visibility-filtered graph commands and tier violation checks
@@ -46,6 +49,17 @@ pub struct PackageNode {
   pub visibilities: HashSet<Visibility>,
 }
 
+/// An unpublished workspace crate reached from a set of root crates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnpublishedDependency {
+  pub name: String,
+  pub manifest_path: PathBuf,
+  /// The crate whose manifest names this dependency
+  pub required_by: String,
+  /// Named only under `[dev-dependencies]` of `required_by`
+  pub dev_only: bool,
+}
+
 /// Workspace dependency graph.
 ///
 /// Built from cargo_metadata, using petgraph for efficient traversals.
@@ -148,6 +162,10 @@ impl WorkspaceGraph {
         version: package.version.to_string(),
         manifest_path: package.manifest_path.clone().into_std_path_buf(),
         is_workspace_member: workspace_pkg_ids.contains(&package.id),
+        publishable: package
+          .publish
+          .as_ref()
+          .is_none_or(|registries| registries.iter().any(|r| r == "crates-io")),
         visibilities,
       };
 
@@ -270,6 +288,48 @@ impl WorkspaceGraph {
     Ok(result)
   }
 
+  /// Find the unpublished workspace crates that `roots` depend on.
+  ///
+  /// Follows normal, build and dev edges (a split repo must resolve all of them),
+  /// walking through unpublished crates since their own dependencies come along with
+  /// them. Published crates end the walk: their registry releases carry their own deps.
+  /// The roots themselves are never reported.
+  pub fn unpublished_dependency_closure(&self, roots: &[String]) -> RailResult<Vec<UnpublishedDependency>> {
+    let root_nodes = roots
+      .iter()
+      .map(|name| self.find_node(name))
+      .collect::<RailResult<HashSet<_>>>()?;
+
+    let mut visited: HashSet<NodeIndex> = root_nodes.clone();
+    let mut queue: VecDeque<NodeIndex> = root_nodes.iter().copied().collect();
+    let mut closure = Vec::new();
+
+    while let Some(current) = queue.pop_front() {
+      let mut neighbors: Vec<_> = self.graph.neighbors_directed(current, Direction::Outgoing).collect();
+      neighbors.sort_by(|a, b| self.graph[*a].name.cmp(&self.graph[*b].name));
+
+      for neighbor_idx in neighbors {
+        let neighbor = &self.graph[neighbor_idx];
+        if !neighbor.is_workspace_member || neighbor.publishable || !visited.insert(neighbor_idx) {
+          continue;
+        }
+
+        closure.push(UnpublishedDependency {
+          name: neighbor.name.clone(),
+          manifest_path: neighbor.manifest_path.clone(),
+          required_by: self.graph[current].name.clone(),
+          dev_only: self
+            .graph
+            .edges_connecting(current, neighbor_idx)
+            .all(|edge| *edge.weight() == DependencyKind::Development),
+        });
+        queue.push_back(neighbor_idx);
+      }
+    }
+
+    Ok(closure)
+  }
+
   /// Map a file path to its owning crate.
   ///
   /// Builds path cache on first call, then O(1) lookups.
@@ -2,6 +2,7 @@ mod cargo;
 mod checks;
 mod commands;
 mod core;
+mod forge;
 mod graph;
 mod lint;
 mod quality;
@@ -10,7 +11,10 @@ mod ui;
 mod utils;
 
 use clap::{Parser, Subcommand};
+use commands::resolve::ResolveAction;
 use core::error::{RailError, print_error};
+use core::resolve::Resolution;
+use std::path::PathBuf;
 
 /// Split Rust crates from monorepos, keep them in sync
 #[derive(Parser)]
@@ -85,6 +89,15 @@ enum Commands {
     /// Override remote repository path (useful for testing)
     #[arg(long)]
     remote: Option<String>,
+    /// Verify the split builds standalone (cargo metadata/check/package --list, offline) before pushing
+    #[arg(long)]
+    verify_build: bool,
+    /// With --all: number of splits processed at once (default: one per CPU)
+    #[arg(short, long, default_value_t = 0, hide_default_value = true)]
+    jobs: usize,
+    /// With --all: stop starting new splits after the first failure
+    #[arg(long)]
+    fail_fast: bool,
     /// Actually perform the split (default: dry-run mode showing plan)
     #[arg(long)]
     apply: bool,
@@ -112,9 +125,33 @@ enum Commands {
     /// Conflict resolution strategy: ours (use monorepo), theirs (use remote), manual (create markers), union (combine both)
     #[arg(long, visible_alias = "conflict", default_value = "manual")]
     strategy: String,
+    /// Write outgoing commits and rail notes to a git bundle instead of pushing (implies --to-remote)
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["from_remote", "from_bundle"])]
+    to_bundle: Option<PathBuf>,
+    /// Import commits from a git bundle made on the split side instead of fetching (implies --from-remote)
+    #[arg(long, value_name = "FILE", conflicts_with = "to_remote")]
+    from_bundle: Option<PathBuf>,
+    /// When the remote has commits never imported: abort, import (then push) or rebase (push on top) [default: abort; import when bidirectional]
+    #[arg(long, value_name = "POLICY")]
+    on_diverged: Option<String>,
     /// Disable protected branch checks (useful for testing)
     #[arg(long)]
     no_protected_branches: bool,
+    /// Verify the split builds standalone (cargo metadata/check/package --list, offline) before pushing
+    #[arg(long)]
+    verify_build: bool,
+    /// Finish an interrupted sync from its journal (implies --apply)
+    #[arg(long, conflicts_with = "abort")]
+    resume: bool,
+    /// Roll back an interrupted sync and discard its journal
+    #[arg(long)]
+    abort: bool,
+    /// With --all: number of splits processed at once (default: one per CPU)
+    #[arg(short, long, default_value_t = 0, hide_default_value = true)]
+    jobs: usize,
+    /// With --all: stop starting new splits after the first failure
+    #[arg(long)]
+    fail_fast: bool,
     /// Actually perform the sync (default: dry-run mode showing plan)
     #[arg(long)]
     apply: bool,
@@ -123,6 +160,87 @@ enum Commands {
     json: bool,
   },
 
+  /// Settle the conflicts a sync stopped on, then continue it (lists stopped syncs if no crate is given)
+  Resolve {
+    /// Name of the crate whose sync stopped
+    crate_name: Option<String>,
+    /// Conflicted files to settle, relative to the workspace root (default: all unresolved)
+    paths: Vec<PathBuf>,
+    /// Take the monorepo version
+    #[arg(long, group = "action")]
+    ours: bool,
+    /// Take the remote version
+    #[arg(long, group = "action")]
+    theirs: bool,
+    /// Keep both versions, line by line
+    #[arg(long, group = "action")]
+    union: bool,
+    /// Open the files in $VISUAL / $EDITOR
+    #[arg(long, group = "action")]
+    edit: bool,
+    /// Run a git mergetool (mergetool.<name>.cmd; default: merge.tool)
+    #[arg(long, group = "action", num_args = 0..=1, default_missing_value = "")]
+    tool: Option<String>,
+    /// Commit the resolved commit and resume the sync
+    #[arg(long = "continue")]
+    continue_sync: bool,
+    /// Output conflicts in JSON format
+    #[arg(long)]
+    json: bool,
+  },
+
+  /// Reverse an applied split, sync or release (lists undoable plans if no ID is given)
+  Undo {
+    /// ID (or unique prefix) of the applied plan to reverse
+    plan_id: Option<String>,
+    /// Actually perform the undo (default: dry-run mode showing plan)
+    #[arg(long)]
+    apply: bool,
+    /// Output plan in JSON format (useful for CI/automation)
+    #[arg(long)]
+    json: bool,
+  },
+
+  /// Keep splits in sync: poll the monorepo and split repos, syncing whichever side changed
+  Watch {
+    /// Splits to watch (default: all configured splits)
+    crates: Vec<String>,
+    /// Seconds between polls of a split
+    #[arg(long, default_value_t = 60)]
+    interval: u64,
+    /// Longest wait between polls once syncs of a split keep failing, in seconds
+    #[arg(long, default_value_t = 900)]
+    max_interval: u64,
+    /// Conflict resolution strategy for imports: ours, theirs, manual, union
+    #[arg(long, visible_alias = "conflict", default_value = "manual")]
+    strategy: String,
+    /// Poll every split once, then exit
+    #[arg(long)]
+    once: bool,
+  },
+
+  /// Stop splitting a crate: archive its split repo and mapping notes, drop it from rail.toml
+  Retire {
+    /// Split to retire
+    crate_name: String,
+    /// Push a final commit with an archival README pointing back to the monorepo
+    #[arg(long)]
+    archive: bool,
+    /// Monorepo URL for the archival README (default: the monorepo's origin)
+    #[arg(long)]
+    mono_url: Option<String>,
+    /// Actually retire the split (default: dry-run mode showing plan)
+    #[arg(long)]
+    apply: bool,
+    /// Output plan in JSON format (useful for CI/automation)
+    #[arg(long)]
+    json: bool,
+  },
+
+  /// Managed clones of split repos ($XDG_CACHE_HOME/cargo-rail)
+  #[command(subcommand)]
+  Cache(CacheCommands),
+
   // ============================================================================
   // Graph Orchestration (Pillar 1)
   // ============================================================================
@@ -161,6 +279,29 @@ enum Commands {
   },
 }
 
+// Cache subcommands
+#[derive(Subcommand)]
+enum CacheCommands {
+  /// List cached clones of this workspace's split repos
+  Ls {
+    /// Include clones made by other workspaces
+    #[arg(long)]
+    all: bool,
+    /// Output in JSON format
+    #[arg(long)]
+    json: bool,
+  },
+  /// Remove clones no configured split uses, and clones of deleted workspaces
+  Prune {
+    /// Also remove this workspace's clones in use (cloned again on the next sync)
+    #[arg(long)]
+    all: bool,
+    /// Actually remove them (default: dry-run listing what would go)
+    #[arg(long)]
+    apply: bool,
+  },
+}
+
 // Graph subcommands (Pillar 1)
 #[derive(Subcommand)]
 enum GraphCommands {
@@ -389,17 +530,38 @@ fn main() {
       crate_name,
       all,
       remote,
+      verify_build,
+      jobs,
+      fail_fast,
       apply,
       json,
-    } => commands::run_split(&ctx, crate_name, all, remote, apply, json),
+    } => commands::run_split(
+      &ctx,
+      crate_name,
+      all,
+      remote,
+      verify_build,
+      jobs,
+      fail_fast,
+      apply,
+      json,
+    ),
     Commands::Sync {
       crate_name,
       all,
       remote,
       from_remote,
       to_remote,
+      to_bundle,
+      from_bundle,
       strategy,
+      on_diverged,
       no_protected_branches,
+      verify_build,
+      resume,
+      abort,
+      jobs,
+      fail_fast,
       apply,
       json,
     } => commands::run_sync(
@@ -407,12 +569,77 @@ fn main() {
       crate_name,
       all,
       remote,
-      from_remote,
-      to_remote,
+      from_remote || from_bundle.is_some(),
+      to_remote || to_bundle.is_some(),
       strategy,
+      on_diverged,
+      to_bundle.or(from_bundle),
       no_protected_branches,
+      verify_build,
+      resume,
+      abort,
+      jobs,
+      fail_fast,
+      apply,
+      json,
+    ),
+    Commands::Resolve {
+      crate_name,
+      paths,
+      ours,
+      theirs,
+      union,
+      edit,
+      tool,
+      continue_sync,
+      json,
+    } => {
+      let action = if ours {
+        Some(ResolveAction::Pick(Resolution::Ours))
+      } else if theirs {
+        Some(ResolveAction::Pick(Resolution::Theirs))
+      } else if union {
+        Some(ResolveAction::Pick(Resolution::Union))
+      } else if edit {
+        Some(ResolveAction::Edit)
+      } else {
+        tool.map(|name| ResolveAction::Tool(Some(name).filter(|n| !n.is_empty())))
+      };
+      commands::run_resolve(&ctx, crate_name, paths, action, continue_sync, json)
+    }
+    Commands::Undo { plan_id, apply, json } => commands::run_undo(&ctx, plan_id, apply, json),
+    Commands::Watch {
+      crates,
+      interval,
+      max_interval,
+      strategy,
+      once,
+    } => commands::run_watch(
+      &ctx,
+      commands::watch::WatchParams {
+        crates,
+        interval,
+        max_interval,
+        strategy_str: strategy,
+        once,
+      },
+    ),
+
+    Commands::Retire {
+      crate_name,
+      archive,
+      mono_url,
       apply,
       json,
+    } => commands::run_retire(
+      &ctx,
+      commands::retire::RetireParams {
+        crate_name,
+        archive,
+        mono_url,
+        apply,
+        json,
+      },
     ),
 
     // Graph Commands (Pillar 1) - New grouped interface
@@ -461,6 +688,11 @@ fn main() {
       LintCommands::Manifest { json, strict } => commands::run_lint_manifest(&ctx, json, strict),
     },
 
+    Commands::Cache(cache_cmd) => match cache_cmd {
+      CacheCommands::Ls { all, json } => commands::run_cache_ls(&ctx, all, json),
+      CacheCommands::Prune { all, apply } => commands::run_cache_prune(&ctx, all, apply),
+    },
+
     // Release Commands (Pillar 4)
     Commands::Release(release_cmd) => match release_cmd {
       ReleaseCommands::Plan { name, all, json } => commands::run_release_plan(&ctx, name, all, json),
@@ -510,6 +742,7 @@ fn try_minimal_context(workspace_root: &std::path::Path) -> core::error::RailRes
 
   Ok(core::context::WorkspaceContext {
     root: workspace_root.to_path_buf(),
+    transform: crate::cargo::transform::CargoTransform::new(metadata.clone()),
     metadata,
     graph,
     config: None, // No config - this is minimal mode
@@ -1,8 +1,12 @@
 mod helpers;
 mod test_affected;
+mod test_cache;
+mod test_doctor;
 mod test_init;
 mod test_lint;
 mod test_release;
+mod test_retire;
 mod test_split;
 mod test_sync;
+mod test_watch;
 mod test_workflow;
@@ -0,0 +1,162 @@
+//! Tests for the managed split repo cache (`cargo rail cache`)
+
+use crate::helpers::*;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Run cargo-rail with the cache under `cache_home`
+fn run_cached(workspace: &TestWorkspace, cache_home: &Path, args: &[&str]) -> Result<Output> {
+  let output = Command::new(env!("CARGO_BIN_EXE_cargo-rail"))
+    .current_dir(&workspace.path)
+    .env("XDG_CACHE_HOME", cache_home)
+    .args(args)
+    .output()?;
+  anyhow::ensure!(
+    output.status.success(),
+    "cargo rail {} failed\nstdout: {}\nstderr: {}",
+    args.join(" "),
+    String::from_utf8_lossy(&output.stdout),
+    String::from_utf8_lossy(&output.stderr)
+  );
+  Ok(output)
+}
+
+fn cache_ls(workspace: &TestWorkspace, cache_home: &Path) -> Result<Vec<serde_json::Value>> {
+  let output = run_cached(workspace, cache_home, &["rail", "cache", "ls", "--json"])?;
+  Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+/// A split whose remote is a `file://` URL, so its clone is managed in the cache
+///
+/// Split and sync run with `--json`, which skips the preflight checks a non-local remote
+/// would otherwise trigger.
+fn remote_split(workspace: &TestWorkspace, fetch: &str) -> Result<PathBuf> {
+  workspace.add_crate("my-crate", "0.1.0", &[])?;
+  workspace.commit("Add my-crate")?;
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+
+  let remote = workspace.path.join("remotes").join("my-crate.git");
+  std::fs::create_dir_all(&remote)?;
+  git(&remote, &["init", "-q", "--bare", "--initial-branch=main"])?;
+
+  let config = workspace.read_file("rail.toml")?.replacen(
+    "remote = \"\"",
+    &format!("remote = \"file://{}\"", remote.display()),
+    1,
+  );
+  let config = format!("{}\n[cache]\nfetch = \"{}\"\n", config, fetch);
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  std::fs::write(workspace.path.join(".gitignore"), "remotes/\n")?;
+  workspace.commit("Configure remote split")?;
+  Ok(remote)
+}
+
+#[test]
+fn test_remote_split_is_cloned_into_the_cache() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let cache_home = tempfile::TempDir::new()?;
+  let remote = remote_split(&workspace, "partial")?;
+
+  // The split repo is created in the cache, not next to the workspace
+  run_cached(
+    &workspace,
+    cache_home.path(),
+    &["rail", "split", "my-crate", "--apply", "--json"],
+  )?;
+  let clones = cache_ls(&workspace, cache_home.path())?;
+  assert_eq!(clones.len(), 1, "{:?}", clones);
+  assert_eq!(clones[0]["split"], "my-crate");
+  assert_eq!(clones[0]["usage"], "in_use");
+  let clone = PathBuf::from(clones[0]["path"].as_str().unwrap());
+  assert!(clone.starts_with(cache_home.path().join("cargo-rail")));
+  assert!(!workspace.path.join("..").join("my-crate").exists());
+
+  // Dropping the clone (as on a fresh CI machine) gets it cloned again, partially, on the next sync
+  run_cached(
+    &workspace,
+    cache_home.path(),
+    &["rail", "cache", "prune", "--all", "--apply"],
+  )?;
+  assert!(!clone.exists());
+
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn new() {}\n")?;
+  workspace.commit("Update in monorepo")?;
+  run_cached(
+    &workspace,
+    cache_home.path(),
+    &["rail", "sync", "my-crate", "--to-remote", "--apply", "--json"],
+  )?;
+  assert!(clone.join(".git").exists());
+  let filter = git(&clone, &["config", "remote.origin.partialclonefilter"])?;
+  assert_eq!(String::from_utf8_lossy(&filter.stdout).trim(), "blob:none");
+
+  let log = git(&remote, &["log", "-1", "--format=%s", "main"])?;
+  assert_eq!(String::from_utf8_lossy(&log.stdout).trim(), "Update in monorepo");
+
+  Ok(())
+}
+
+#[test]
+fn test_cache_prune_removes_unconfigured_clones() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let cache_home = tempfile::TempDir::new()?;
+  remote_split(&workspace, "shallow")?;
+  run_cached(
+    &workspace,
+    cache_home.path(),
+    &["rail", "split", "my-crate", "--apply", "--json"],
+  )?;
+
+  // A clone left behind by a split that was since removed from rail.toml
+  let clone = PathBuf::from(cache_ls(&workspace, cache_home.path())?[0]["path"].as_str().unwrap());
+  let leftover = clone.with_file_name("old-crate");
+  git(
+    clone.parent().unwrap(),
+    &["clone", "-q", &clone.display().to_string(), "old-crate"],
+  )?;
+
+  let clones = cache_ls(&workspace, cache_home.path())?;
+  assert_eq!(clones.len(), 2);
+  let old = clones.iter().find(|c| c["split"] == "old-crate").unwrap();
+  assert_eq!(old["usage"], "unconfigured");
+
+  // Dry-run first
+  let output = run_cached(&workspace, cache_home.path(), &["rail", "cache", "prune"])?;
+  assert!(String::from_utf8_lossy(&output.stdout).contains("Would remove"));
+  assert!(leftover.exists());
+
+  run_cached(&workspace, cache_home.path(), &["rail", "cache", "prune", "--apply"])?;
+  assert!(!leftover.exists());
+  assert!(clone.exists());
+
+  // Clones with unpushed commits are kept
+  std::fs::write(clone.join("NOTES.md"), "local only\n")?;
+  git(&clone, &["add", "NOTES.md"])?;
+  git(&clone, &["commit", "-q", "-m", "Local only"])?;
+  let output = run_cached(
+    &workspace,
+    cache_home.path(),
+    &["rail", "cache", "prune", "--all", "--apply"],
+  )?;
+  assert!(String::from_utf8_lossy(&output.stdout).contains("1 unpushed commit(s)"));
+  assert!(clone.exists());
+
+  // So are clones with local changes, and directories git can't inspect
+  git(&clone, &["reset", "-q", "--hard", "HEAD~1"])?;
+  std::fs::write(clone.join("scratch.rs"), "// work in progress\n")?;
+  let broken = clone.with_file_name("not-a-repo");
+  std::fs::create_dir(&broken)?;
+  let output = run_cached(
+    &workspace,
+    cache_home.path(),
+    &["rail", "cache", "prune", "--all", "--apply"],
+  )?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("uncommitted changes"), "{}", stdout);
+  assert!(stdout.contains("couldn't check it for unpushed commits"), "{}", stdout);
+  assert!(clone.exists());
+  assert!(broken.exists());
+
+  Ok(())
+}
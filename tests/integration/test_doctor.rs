@@ -0,0 +1,57 @@
+//! Tests for the `doctor` command
+
+use crate::helpers::*;
+use anyhow::Result;
+
+/// Result of the `ssh-keys` check from `doctor --json`, run with `env` set
+fn credentials_check(workspace: &TestWorkspace, env: &[(&str, &str)]) -> Result<(serde_json::Value, String)> {
+  let output = std::process::Command::new(env!("CARGO_BIN_EXE_cargo-rail"))
+    .current_dir(&workspace.path)
+    .args(["rail", "doctor", "--json"])
+    .env_remove("RAIL_TEST_HTTPS_TOKEN")
+    .envs(env.iter().copied())
+    .output()?;
+  let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+  let results: serde_json::Value = serde_json::from_str(&stdout)?;
+  let check = results
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|r| r["check_name"] == "ssh-keys")
+    .cloned()
+    .unwrap();
+  Ok((check, stdout))
+}
+
+#[test]
+fn test_doctor_checks_https_token_instead_of_ssh_keys() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  workspace.add_crate("my-crate", "0.1.0", &[])?;
+  workspace.commit("Add my-crate")?;
+
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+  let config = workspace.read_file("rail.toml")?.replacen(
+    "remote = \"\"",
+    "remote = \"https://git.example.com/you/my-crate.git\"\nauth = \"https\"\ntoken_env = \"RAIL_TEST_HTTPS_TOKEN\"",
+    1,
+  );
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+
+  let (check, _) = credentials_check(&workspace, &[])?;
+  assert_eq!(check["passed"], false, "check: {}", check);
+  assert!(
+    check["message"]
+      .as_str()
+      .unwrap()
+      .contains("$RAIL_TEST_HTTPS_TOKEN is not set"),
+    "check: {}",
+    check
+  );
+
+  let (check, stdout) = credentials_check(&workspace, &[("RAIL_TEST_HTTPS_TOKEN", "s3cr3t-t0ken")])?;
+  assert_eq!(check["passed"], true, "check: {}", check);
+  assert!(check["message"].as_str().unwrap().contains("No SSH keys needed"));
+  assert!(!stdout.contains("s3cr3t-t0ken"));
+
+  Ok(())
+}
@@ -1,6 +1,6 @@
 //! Integration tests for `cargo rail release` commands
 
-use crate::helpers::{TestWorkspace, run_cargo_rail};
+use crate::helpers::{TestWorkspace, git, run_cargo_rail};
 use anyhow::Result;
 
 #[test]
@@ -161,6 +161,58 @@ last_date = "2024-01-01T00:00:00Z"
   Ok(())
 }
 
+#[test]
+fn test_release_apply_can_be_undone() -> Result<()> {
+  let ws = TestWorkspace::new()?;
+  ws.add_crate("lib-a", "0.1.0", &[])?;
+  let initial_sha = ws.commit("feat: Initial release")?;
+
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[releases]]
+name = "lib-a"
+crate = "crates/lib-a"
+last_version = "0.1.0"
+last_sha = "{}"
+last_date = "2024-01-01T00:00:00Z"
+"#,
+    initial_sha
+  );
+  std::fs::write(ws.path.join("rail.toml"), &rail_toml)?;
+  ws.modify_file("lib-a", "src/lib.rs", "pub fn new_fn() {}")?;
+  ws.commit("feat: Add new function")?;
+  let cargo_toml_before = ws.read_file("crates/lib-a/Cargo.toml")?;
+
+  let output = run_cargo_rail(&ws.path, &["rail", "release", "apply", "lib-a"])?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert_ne!(ws.read_file("crates/lib-a/Cargo.toml")?, cargo_toml_before);
+
+  // The apply output names the recorded plan
+  let plan_id = stdout
+    .lines()
+    .find_map(|line| line.trim().strip_prefix("Recorded as plan "))
+    .and_then(|rest| rest.split_whitespace().next())
+    .expect("release apply should print its plan ID")
+    .to_string();
+
+  let output = run_cargo_rail(&ws.path, &["rail", "undo", &plan_id])?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("Restore"), "{}", stdout);
+  assert!(stdout.contains("Delete tag lib-a-v"), "{}", stdout);
+
+  run_cargo_rail(&ws.path, &["rail", "undo", &plan_id, "--apply"])?;
+
+  assert_eq!(ws.read_file("crates/lib-a/Cargo.toml")?, cargo_toml_before);
+  assert_eq!(std::fs::read_to_string(ws.path.join("rail.toml"))?, rail_toml);
+  let tags = git(&ws.path, &["tag", "--list", "lib-a-v*"])?;
+  assert!(String::from_utf8_lossy(&tags.stdout).trim().is_empty());
+
+  Ok(())
+}
+
 #[test]
 fn test_release_detects_conventional_commits() -> Result<()> {
   // Setup workspace
@@ -0,0 +1,164 @@
+//! Tests for the `retire` command
+
+use crate::helpers::*;
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+
+/// Split `my-crate` to `remote` and link a release to the split
+fn retirable_split(workspace: &TestWorkspace, remote: &str) -> Result<()> {
+  workspace.add_crate("my-crate", "0.1.0", &[])?;
+  workspace.commit("Add my-crate")?;
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+
+  let config = workspace
+    .read_file("rail.toml")?
+    .replacen("remote = \"\"", &format!("remote = \"{}\"", remote), 1)
+    .replacen("releases = []\n", "", 1);
+  let config = format!(
+    "{}\n[[releases]]\nname = \"my-crate\"\ncrate = \"crates/my-crate\"\nsplit = \"my-crate\"\n",
+    config
+  );
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  workspace.commit("Configure split")?;
+  Ok(())
+}
+
+fn has_ref(repo: &Path, name: &str) -> Result<bool> {
+  Ok(
+    Command::new("git")
+      .current_dir(repo)
+      .args(["rev-parse", "--verify", "--quiet", name])
+      .output()?
+      .status
+      .success(),
+  )
+}
+
+#[test]
+fn test_retire_archives_notes_and_drops_the_split() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = workspace.path.join("split-repos").join("my-crate-split");
+  retirable_split(&workspace, &split_dir.display().to_string())?;
+  run_cargo_rail(&workspace.path, &["rail", "split", "my-crate", "--apply"])?;
+  let config_before = workspace.read_file("rail.toml")?;
+
+  // Dry-run shows the plan and changes nothing
+  let output = run_cargo_rail(&workspace.path, &["rail", "retire", "my-crate"])?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("DRY-RUN"), "{}", stdout);
+  assert!(stdout.contains("refs/notes/rail-archive/my-crate"), "{}", stdout);
+  assert!(
+    stdout.contains("remove [[splits]] entry 'my-crate', unlink release 'my-crate'"),
+    "{}",
+    stdout
+  );
+  assert!(!stdout.contains("archival README"), "{}", stdout);
+  assert_eq!(workspace.read_file("rail.toml")?, config_before);
+  assert!(has_ref(&workspace.path, "refs/notes/rail/my-crate")?);
+
+  run_cargo_rail(&workspace.path, &["rail", "retire", "my-crate", "--apply"])?;
+
+  assert!(!has_ref(&workspace.path, "refs/notes/rail/my-crate")?);
+  assert!(has_ref(&workspace.path, "refs/notes/rail-archive/my-crate")?);
+  let config = workspace.read_file("rail.toml")?;
+  assert!(!config.contains("[[splits]]"), "{}", config);
+  assert!(config.contains("[[releases]]\nname = \"my-crate\""), "{}", config);
+  assert!(!config.contains("split = \"my-crate\""), "{}", config);
+
+  // Without --archive the split repo is left alone
+  let log = git(&split_dir, &["log", "-1", "--format=%s"])?;
+  assert!(!String::from_utf8_lossy(&log.stdout).contains("Archive"));
+
+  // The split is gone, so syncing it is an error
+  let err = run_cargo_rail(&workspace.path, &["rail", "sync", "my-crate"]).expect_err("sync of a retired split");
+  assert!(err.to_string().contains("my-crate"), "{}", err);
+
+  Ok(())
+}
+
+#[test]
+fn test_retire_archive_pushes_readme_to_split_remote() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let cache_home = tempfile::TempDir::new()?;
+  let remote = workspace.path.join("remotes").join("my-crate.git");
+  std::fs::create_dir_all(&remote)?;
+  git(&remote, &["init", "-q", "--bare", "--initial-branch=main"])?;
+  std::fs::write(workspace.path.join(".gitignore"), "remotes/\n")?;
+  retirable_split(&workspace, &format!("file://{}", remote.display()))?;
+
+  let run = |args: &[&str]| -> Result<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-rail"))
+      .current_dir(&workspace.path)
+      .env("XDG_CACHE_HOME", cache_home.path())
+      .args(args)
+      .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    anyhow::ensure!(
+      output.status.success(),
+      "cargo {} failed\nstdout: {}\nstderr: {}",
+      args.join(" "),
+      stdout,
+      String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(stdout)
+  };
+
+  // --json skips the preflight checks a non-local remote triggers
+  run(&["rail", "split", "my-crate", "--apply", "--json"])?;
+
+  // An unsynced mono commit is pointed out before the split repo is archived
+  workspace.modify_file("my-crate", "src/lib.rs", "pub fn unsynced() {}\n")?;
+  workspace.commit("Never synced")?;
+  let has_ref = |name: &str| -> Result<bool> {
+    let output = Command::new("git")
+      .current_dir(&remote)
+      .args(["rev-parse", "--verify", "--quiet", name])
+      .output()?;
+    Ok(output.status.success())
+  };
+  assert!(has_ref("refs/notes/rail/my-crate")?);
+
+  // A fresh clone has no notes of its own; they're fetched from the remote before archiving
+  std::fs::remove_dir_all(cache_home.path())?;
+  let stdout = run(&["rail", "retire", "my-crate", "--archive"])?;
+  assert!(stdout.contains("Commit archival README.md"), "{}", stdout);
+  assert!(stdout.contains("1 monorepo commit(s) were never synced"), "{}", stdout);
+
+  let stdout = run(&[
+    "rail",
+    "retire",
+    "my-crate",
+    "--archive",
+    "--mono-url",
+    "https://git.example.com/org/mono",
+    "--apply",
+  ])?;
+  assert!(stdout.contains("cargo rail cache prune"), "{}", stdout);
+
+  let log = git(&remote, &["log", "-1", "--format=%s", "main"])?;
+  assert_eq!(
+    String::from_utf8_lossy(&log.stdout).trim(),
+    "Archive my-crate: development continues in the monorepo"
+  );
+  let readme = git(&remote, &["show", "main:README.md"])?;
+  let readme = String::from_utf8_lossy(&readme.stdout);
+  assert!(readme.starts_with("# my-crate (archived)"), "{}", readme);
+  assert!(
+    readme.contains("[its monorepo](https://git.example.com/org/mono), under `crates/my-crate`"),
+    "{}",
+    readme
+  );
+  assert!(!workspace.read_file("rail.toml")?.contains("[[splits]]"));
+
+  // The remote's mapping notes are archived too
+  assert!(!has_ref("refs/notes/rail/my-crate")?);
+  assert!(has_ref("refs/notes/rail-archive/my-crate")?);
+
+  // The clone is no longer used by any split
+  let ls = run(&["rail", "cache", "ls", "--json"])?;
+  let clones: Vec<serde_json::Value> = serde_json::from_str(&ls)?;
+  assert_eq!(clones[0]["usage"], "unconfigured");
+
+  Ok(())
+}
@@ -188,3 +188,686 @@ fn test_split_copies_auxiliary_files() -> Result<()> {
 
   Ok(())
 }
+
+#[test]
+fn test_split_combined_workspace_mode_generates_root_manifest() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+
+  workspace.add_crate("core-lib", "0.1.0", &[("anyhow", "{ workspace = true }")])?;
+  workspace.add_crate(
+    "core-cli",
+    "0.1.0",
+    &[("core-lib", "{ path = \"../core-lib\", version = \"0.1\" }")],
+  )?;
+  workspace.commit("Add core crates")?;
+
+  let split_dir = workspace.path.join("split-repos").join("core-split");
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "core"
+remote = "{}"
+branch = "main"
+mode = "combined"
+workspace_mode = "workspace"
+paths = [{{ crate = "crates/core-lib" }}, {{ crate = "crates/core-cli" }}]
+"#,
+    split_dir.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+
+  run_cargo_rail(&workspace.path, &["rail", "split", "core", "--apply"])?;
+
+  // Root manifest lists members and only the workspace deps the members inherit
+  let root_manifest = std::fs::read_to_string(split_dir.join("Cargo.toml"))?;
+  assert!(root_manifest.contains("[workspace]"), "got:\n{}", root_manifest);
+  assert!(root_manifest.contains("crates/core-lib"));
+  assert!(root_manifest.contains("crates/core-cli"));
+  assert!(root_manifest.contains("[workspace.package]"));
+  assert!(root_manifest.contains("[workspace.dependencies]"));
+  assert!(root_manifest.contains("anyhow"));
+  assert!(
+    !root_manifest.contains("serde"),
+    "unused workspace deps should be trimmed"
+  );
+
+  // Members keep inheritance and inter-crate path deps
+  let lib_manifest = std::fs::read_to_string(split_dir.join("crates/core-lib/Cargo.toml"))?;
+  assert!(
+    lib_manifest.contains("edition.workspace = true"),
+    "got:\n{}",
+    lib_manifest
+  );
+  assert!(lib_manifest.contains("workspace = true"));
+
+  let cli_manifest = std::fs::read_to_string(split_dir.join("crates/core-cli/Cargo.toml"))?;
+  assert!(
+    cli_manifest.contains("path = \"../core-lib\""),
+    "got:\n{}",
+    cli_manifest
+  );
+
+  Ok(())
+}
+
+/// Mark a test crate as `publish = false`
+fn mark_unpublished(workspace: &TestWorkspace, name: &str) -> Result<()> {
+  let manifest_path = workspace.path.join("crates").join(name).join("Cargo.toml");
+  let manifest = std::fs::read_to_string(&manifest_path)?;
+  std::fs::write(
+    &manifest_path,
+    manifest.replace(
+      "authors.workspace = true\n",
+      "authors.workspace = true\npublish = false\n",
+    ),
+  )?;
+  Ok(())
+}
+
+#[test]
+fn test_split_fails_on_unpublished_workspace_dep() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+
+  workspace.add_crate("internal-util", "0.1.0", &[])?;
+  mark_unpublished(&workspace, "internal-util")?;
+  workspace.add_crate("app", "0.1.0", &[("internal-util", "{ path = \"../internal-util\" }")])?;
+  workspace.commit("Add crates")?;
+
+  let split_dir = workspace.path.join("split-repos").join("app-split");
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "app"
+remote = "{}"
+branch = "main"
+mode = "single"
+paths = [{{ crate = "crates/app" }}]
+"#,
+    split_dir.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+
+  let err = run_cargo_rail(&workspace.path, &["rail", "split", "app", "--json"])
+    .expect_err("planning should fail on unpublished deps")
+    .to_string();
+  assert!(err.contains("unpublished workspace crates"), "got:\n{}", err);
+  assert!(err.contains("internal-util"));
+  assert!(err.contains("required by app"));
+  assert!(!split_dir.exists());
+
+  Ok(())
+}
+
+#[test]
+fn test_split_fails_on_dev_and_private_registry_deps() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+
+  workspace.add_crate("test-support", "0.1.0", &[])?;
+  mark_unpublished(&workspace, "test-support")?;
+  workspace.add_crate("private-util", "0.1.0", &[])?;
+  let private_manifest = workspace.path.join("crates/private-util/Cargo.toml");
+  let manifest = std::fs::read_to_string(&private_manifest)?.replace(
+    "authors.workspace = true\n",
+    "authors.workspace = true\npublish = [\"my-registry\"]\n",
+  );
+  std::fs::write(&private_manifest, manifest)?;
+  workspace.add_crate("app", "0.1.0", &[("private-util", "{ path = \"../private-util\" }")])?;
+  let app_manifest = workspace.path.join("crates/app/Cargo.toml");
+  let manifest = std::fs::read_to_string(&app_manifest)?;
+  std::fs::write(
+    &app_manifest,
+    format!(
+      "{}\n[dev-dependencies]\ntest-support = {{ path = \"../test-support\" }}\n",
+      manifest
+    ),
+  )?;
+  workspace.commit("Add crates")?;
+
+  let split_dir = workspace.path.join("split-repos").join("app-split");
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "app"
+remote = "{}"
+branch = "main"
+mode = "single"
+paths = [{{ crate = "crates/app" }}]
+"#,
+    split_dir.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+
+  let err = run_cargo_rail(&workspace.path, &["rail", "split", "app", "--json"])
+    .expect_err("planning should fail on unpublished deps")
+    .to_string();
+  // Only on a private registry: crates.io can't resolve it either
+  assert!(
+    err.contains("private-util (crates/private-util) ← required by app"),
+    "got:\n{}",
+    err
+  );
+  // The split repo resolves dev-dependencies too
+  assert!(
+    err.contains("test-support (crates/test-support) ← dev-dependency of app"),
+    "got:\n{}",
+    err
+  );
+  assert!(!split_dir.exists());
+
+  Ok(())
+}
+
+#[test]
+fn test_split_includes_unpublished_workspace_dep() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+
+  workspace.add_crate("internal-util", "0.1.0", &[])?;
+  mark_unpublished(&workspace, "internal-util")?;
+  workspace.add_crate("app", "0.1.0", &[("internal-util", "{ path = \"../internal-util\" }")])?;
+  workspace.commit("Add crates")?;
+
+  let split_dir = workspace.path.join("split-repos").join("app-split");
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "app"
+remote = "{}"
+branch = "main"
+mode = "combined"
+unpublished_deps = "include"
+paths = [{{ crate = "crates/app" }}]
+"#,
+    split_dir.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+
+  let output = run_cargo_rail(&workspace.path, &["rail", "split", "app", "--json"])?;
+  let plan = String::from_utf8_lossy(&output.stdout);
+  assert!(plan.contains("\"kind\": \"bundled\""), "got:\n{}", plan);
+  assert!(plan.contains("crates/internal-util"));
+
+  run_cargo_rail(&workspace.path, &["rail", "split", "app", "--apply"])?;
+
+  assert!(split_dir.join("crates/internal-util/src/lib.rs").exists());
+  let app_manifest = std::fs::read_to_string(split_dir.join("crates/app/Cargo.toml"))?;
+  assert!(
+    app_manifest.contains("path = \"../internal-util\""),
+    "bundled deps should keep their path, got:\n{}",
+    app_manifest
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_split_uses_git_deps_for_unpublished_workspace_dep() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+
+  workspace.add_crate("internal-util", "0.1.0", &[])?;
+  mark_unpublished(&workspace, "internal-util")?;
+  workspace.add_crate("app", "0.1.0", &[("internal-util", "{ path = \"../internal-util\" }")])?;
+  workspace.commit("Add crates")?;
+
+  let util_dir = workspace.path.join("split-repos").join("util-split");
+  let app_dir = workspace.path.join("split-repos").join("app-split");
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "internal-util"
+remote = "{}"
+branch = "trunk"
+mode = "single"
+paths = [{{ crate = "crates/internal-util" }}]
+
+[[splits]]
+name = "app"
+remote = "{}"
+branch = "main"
+mode = "single"
+unpublished_deps = "git"
+paths = [{{ crate = "crates/app" }}]
+"#,
+    util_dir.display(),
+    app_dir.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+
+  run_cargo_rail(&workspace.path, &["rail", "split", "app", "--apply"])?;
+
+  let app_manifest = std::fs::read_to_string(app_dir.join("Cargo.toml"))?;
+  assert!(
+    app_manifest.contains("git = \"file://"),
+    "expected a git dependency, got:\n{}",
+    app_manifest
+  );
+  assert!(app_manifest.contains("util-split"));
+  assert!(app_manifest.contains("branch = \"trunk\""));
+  assert!(!app_manifest.contains("path = "));
+
+  Ok(())
+}
+
+#[test]
+fn test_split_verify_build_passes_for_standalone_crate() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  workspace.add_crate("app", "0.1.0", &[])?;
+  workspace.commit("Add app")?;
+
+  let split_dir = workspace.path.join("split-repos").join("app-split");
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "app"
+remote = "{}"
+branch = "main"
+mode = "single"
+paths = [{{ crate = "crates/app" }}]
+"#,
+    split_dir.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+
+  let output = run_cargo_rail(&workspace.path, &["rail", "split", "app", "--verify-build", "--apply"])?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("Split builds standalone"), "got:\n{}", stdout);
+
+  Ok(())
+}
+
+#[test]
+fn test_split_verify_build_maps_failure_to_mono_path() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  workspace.add_crate("app", "0.1.0", &[])?;
+
+  // Reads a file outside the crate: fine in the monorepo, missing in the split
+  std::fs::create_dir_all(workspace.path.join("shared"))?;
+  std::fs::write(workspace.path.join("shared/data.txt"), "data")?;
+  workspace.modify_file(
+    "app",
+    "src/lib.rs",
+    "pub const DATA: &str = include_str!(\"../../../shared/data.txt\");\n",
+  )?;
+  workspace.commit("Add app")?;
+
+  let split_dir = workspace.path.join("split-repos").join("app-split");
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "app"
+remote = "{}"
+branch = "main"
+mode = "single"
+paths = [{{ crate = "crates/app" }}]
+"#,
+    split_dir.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+
+  let err = run_cargo_rail(&workspace.path, &["rail", "split", "app", "--verify-build", "--apply"])
+    .expect_err("verification should fail")
+    .to_string();
+  assert!(err.contains("cargo check --offline"), "got:\n{}", err);
+  assert!(err.contains("crates/app/src/lib.rs:1:"), "got:\n{}", err);
+  assert!(err.contains("Nothing was pushed"));
+
+  // No mapping notes point at the rejected split commits
+  assert!(
+    git(
+      &workspace.path,
+      &["rev-parse", "--verify", "--quiet", "refs/notes/rail/app"]
+    )
+    .is_err(),
+    "mappings were saved for a failed split"
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_split_verify_build_checks_dependencies_resolve() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+
+  // Resolves in the monorepo only, through its root [patch] section
+  let vendored = workspace.path.join("vendor").join("rail-vendored");
+  std::fs::create_dir_all(vendored.join("src"))?;
+  std::fs::write(
+    vendored.join("Cargo.toml"),
+    "[package]\nname = \"rail-vendored\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+  )?;
+  std::fs::write(vendored.join("src/lib.rs"), "")?;
+  let root_manifest = workspace
+    .read_file("Cargo.toml")?
+    .replace("resolver = \"2\"", "resolver = \"2\"\nexclude = [\"vendor\"]");
+  std::fs::write(
+    workspace.path.join("Cargo.toml"),
+    format!(
+      "{}\n[patch.crates-io]\nrail-vendored = {{ path = \"vendor/rail-vendored\" }}\n",
+      root_manifest
+    ),
+  )?;
+  workspace.add_crate("app", "0.1.0", &[("rail-vendored", "\"1\"")])?;
+  workspace.commit("Add app")?;
+
+  let split_dir = workspace.path.join("split-repos").join("app-split");
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "app"
+remote = "{}"
+branch = "main"
+mode = "single"
+paths = [{{ crate = "crates/app" }}]
+"#,
+    split_dir.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+
+  let err = run_cargo_rail(&workspace.path, &["rail", "split", "app", "--verify-build", "--apply"])
+    .expect_err("verification should fail")
+    .to_string();
+  assert!(err.contains("at `cargo metadata --offline`"), "got:\n{}", err);
+  assert!(err.contains("rail-vendored"), "got:\n{}", err);
+  assert!(err.contains("crates/app/Cargo.toml"), "got:\n{}", err);
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_verify_build_failure_saves_no_mappings() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  workspace.add_crate("app", "0.1.0", &[])?;
+  workspace.commit("Add app")?;
+
+  let split_dir = workspace.path.join("split-repos").join("app-split");
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "app"
+remote = "{}"
+branch = "main"
+mode = "single"
+paths = [{{ crate = "crates/app" }}]
+"#,
+    split_dir.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+  run_cargo_rail(&workspace.path, &["rail", "split", "app", "--apply"])?;
+
+  // Reads a file outside the crate: fine in the monorepo, missing in the split
+  std::fs::create_dir_all(workspace.path.join("shared"))?;
+  std::fs::write(workspace.path.join("shared/data.txt"), "data")?;
+  workspace.modify_file(
+    "app",
+    "src/lib.rs",
+    "pub const DATA: &str = include_str!(\"../../../shared/data.txt\");\n",
+  )?;
+  let broken = workspace.commit("Read shared data")?;
+
+  let err = run_cargo_rail(
+    &workspace.path,
+    &["rail", "sync", "app", "--to-remote", "--verify-build", "--apply"],
+  )
+  .expect_err("verification should fail")
+  .to_string();
+  assert!(err.contains("crates/app/src/lib.rs:1:"), "got:\n{}", err);
+
+  // The rejected commit isn't recorded as synced, so the next sync replays and verifies it again
+  for repo in [&workspace.path, &split_dir] {
+    let notes = git(repo, &["notes", "--ref", "refs/notes/rail/app", "list"])?;
+    assert!(
+      !String::from_utf8_lossy(&notes.stdout).contains(&broken),
+      "mapping saved in {} for a commit that failed verification",
+      repo.display()
+    );
+  }
+
+  Ok(())
+}
+
+fn write_bin_split_config(workspace: &TestWorkspace, lockfile: Option<bool>) -> Result<std::path::PathBuf> {
+  let split_dir = workspace.path.join("split-repos").join("app-split");
+  let lockfile_line = lockfile.map(|l| format!("lockfile = {}\n", l)).unwrap_or_default();
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "app"
+remote = "{}"
+branch = "main"
+mode = "single"
+{}paths = [{{ crate = "crates/app" }}]
+"#,
+    split_dir.display(),
+    lockfile_line
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+  Ok(split_dir)
+}
+
+fn add_bin_crate_with_lockfile(workspace: &TestWorkspace) -> Result<()> {
+  workspace.add_crate("app", "0.1.0", &[])?;
+  workspace.add_crate("other", "0.1.0", &[])?;
+  workspace.modify_file("app", "src/main.rs", "fn main() {}\n")?;
+  let status = std::process::Command::new("cargo")
+    .current_dir(&workspace.path)
+    .args(["generate-lockfile", "--offline"])
+    .status()?;
+  assert!(status.success());
+  workspace.commit("Add app")?;
+  Ok(())
+}
+
+#[test]
+fn test_split_derives_lockfile_for_bin_crate() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  add_bin_crate_with_lockfile(&workspace)?;
+  let split_dir = write_bin_split_config(&workspace, None)?;
+
+  run_cargo_rail(&workspace.path, &["rail", "split", "app", "--apply"])?;
+
+  let lock = std::fs::read_to_string(split_dir.join("Cargo.lock"))?;
+  assert!(lock.contains("name = \"app\""), "got:\n{}", lock);
+  assert!(!lock.contains("name = \"other\""), "got:\n{}", lock);
+
+  // The lockfile is part of the replayed history, not a separate commit
+  let tracked = std::process::Command::new("git")
+    .current_dir(&split_dir)
+    .args(["ls-files", "Cargo.lock"])
+    .output()?;
+  assert_eq!(String::from_utf8_lossy(&tracked.stdout).trim(), "Cargo.lock");
+
+  Ok(())
+}
+
+#[test]
+fn test_split_lockfile_can_be_disabled() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  add_bin_crate_with_lockfile(&workspace)?;
+  let split_dir = write_bin_split_config(&workspace, Some(false))?;
+
+  run_cargo_rail(&workspace.path, &["rail", "split", "app", "--apply"])?;
+
+  assert!(split_dir.join("Cargo.toml").exists());
+  assert!(!split_dir.join("Cargo.lock").exists());
+
+  Ok(())
+}
+
+/// Point each `[[splits]]` entry written by `init --all` at its own remote, in order
+fn set_split_remotes(workspace: &TestWorkspace, remotes: &[&std::path::Path]) -> Result<()> {
+  let mut config = workspace.read_file("rail.toml")?;
+  for remote in remotes {
+    config = config.replacen("remote = \"\"", &format!("remote = \"{}\"", remote.display()), 1);
+  }
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  Ok(())
+}
+
+/// The JSON report printed after the summary table
+fn batch_json(stdout: &[u8]) -> Result<serde_json::Value> {
+  let stdout = String::from_utf8_lossy(stdout);
+  let start = stdout.rfind("\n{").map(|i| i + 1).unwrap_or(0);
+  let end = stdout.rfind('}').map(|i| i + 1).unwrap_or(stdout.len());
+  Ok(serde_json::from_str(&stdout[start..end])?)
+}
+
+#[test]
+fn test_split_all_runs_in_parallel_and_reports() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  workspace.add_crate("lib-a", "0.1.0", &[])?;
+  workspace.add_crate("lib-b", "0.1.0", &[])?;
+  workspace.commit("Add crates")?;
+
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+  let split_a = workspace.path.join("split-repos").join("lib-a");
+  let split_b = workspace.path.join("split-repos").join("lib-b");
+  set_split_remotes(&workspace, &[&split_a, &split_b])?;
+
+  let output = run_cargo_rail(
+    &workspace.path,
+    &["rail", "split", "--all", "--jobs", "2", "--json", "--apply"],
+  )?;
+
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("Split summary"), "stdout: {}", stdout);
+  let report = batch_json(&output.stdout)?;
+  let entries = report["entries"].as_array().unwrap();
+  assert_eq!(entries.len(), 2);
+  for entry in entries {
+    assert_eq!(entry["status"], "ok", "entry: {}", entry);
+    assert!(entry["commits"].as_u64().unwrap() > 0, "entry: {}", entry);
+  }
+  assert!(split_a.join("Cargo.toml").exists());
+  assert!(split_b.join("Cargo.toml").exists());
+
+  Ok(())
+}
+
+#[test]
+fn test_split_all_continues_past_failures_unless_fail_fast() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  workspace.add_crate("lib-a", "0.1.0", &[])?;
+  workspace.add_crate("lib-b", "0.1.0", &[])?;
+  workspace.commit("Add crates")?;
+
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+  // A regular file where lib-a's remote directory should go
+  let blocker = workspace.path.join("blocker");
+  std::fs::write(&blocker, "not a directory")?;
+  let split_b = workspace.path.join("split-repos").join("lib-b");
+  set_split_remotes(&workspace, &[&blocker.join("lib-a"), &split_b])?;
+
+  let run = |args: &[&str]| {
+    std::process::Command::new(env!("CARGO_BIN_EXE_cargo-rail"))
+      .current_dir(&workspace.path)
+      .args(args)
+      .output()
+  };
+
+  let output = run(&["rail", "split", "--all", "--jobs", "1", "--json", "--apply"])?;
+  assert!(!output.status.success());
+  let report = batch_json(&output.stdout)?;
+  assert_eq!(report["entries"][0]["status"], "failed");
+  assert!(report["entries"][0]["error"].is_string());
+  assert_eq!(report["entries"][1]["status"], "ok");
+  assert!(split_b.join("Cargo.toml").exists());
+
+  std::fs::remove_dir_all(&split_b)?;
+  let output = run(&[
+    "rail",
+    "split",
+    "--all",
+    "--jobs",
+    "1",
+    "--fail-fast",
+    "--json",
+    "--apply",
+  ])?;
+  assert!(!output.status.success());
+  let report = batch_json(&output.stdout)?;
+  assert_eq!(report["entries"][0]["status"], "failed");
+  assert_eq!(report["entries"][1]["status"], "skipped");
+  assert!(!split_b.exists());
+
+  Ok(())
+}
+
+#[test]
+fn test_split_pushes_to_mirrors_and_reports_failures() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  workspace.add_crate("my-crate", "0.1.0", &[])?;
+  workspace.commit("Add my-crate")?;
+
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+  let primary = workspace.path.join("split-repos").join("my-crate");
+  let mirror = workspace.path.join("mirror.git");
+  let broken = workspace.path.join("missing").join("broken.git");
+  git(
+    &workspace.path,
+    &["init", "-q", "--bare", &mirror.display().to_string()],
+  )?;
+  let config = workspace.read_file("rail.toml")?.replacen(
+    "remote = \"\"",
+    &format!(
+      "remotes = [\n  {{ name = \"primary\", url = \"{}\", primary = true }},\n  {{ name = \"mirror\", url = \"{}\" }},\n  {{ name = \"broken\", url = \"{}\" }},\n]",
+      primary.display(),
+      mirror.display(),
+      broken.display()
+    ),
+    1,
+  );
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+
+  let output = std::process::Command::new(env!("CARGO_BIN_EXE_cargo-rail"))
+    .current_dir(&workspace.path)
+    .args(["rail", "split", "my-crate", "--apply"])
+    .output()?;
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(!output.status.success());
+  assert!(stderr.contains("1 of 2 mirror(s) failed: broken"), "stderr: {}", stderr);
+
+  // The primary and the reachable mirror both got the split
+  let split_head = git(&primary, &["rev-parse", "HEAD"])?.stdout;
+  let mirror_head = git(&mirror, &["rev-parse", "refs/heads/main"])?.stdout;
+  assert_eq!(split_head, mirror_head);
+  git(&mirror, &["show-ref", "refs/notes/rail/my-crate"])?;
+
+  let output = run_cargo_rail(&workspace.path, &["rail", "status", "--json"])?;
+  let statuses: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+  let mirrors = statuses[0]["mirrors"].as_array().unwrap();
+  assert_eq!(mirrors.len(), 2);
+  assert_eq!(mirrors[0]["name"], "mirror");
+  assert_eq!(mirrors[0]["drift"], "in_sync");
+  assert_eq!(mirrors[1]["drift"], "unreachable");
+
+  Ok(())
+}
@@ -451,8 +451,9 @@ fn test_conflict_resolution_manual_strategy() -> Result<()> {
   git(&split_dir, &["add", "."])?;
   git(&split_dir, &["commit", "-m", "Split change"])?;
 
-  // Sync from remote with --strategy=manual (should create conflict markers)
-  run_cargo_rail(
+  // Sync from remote with --strategy=manual (should stop with conflict markers)
+  let mono_head = head_of(&workspace.path)?;
+  let stopped = run_cargo_rail(
     &workspace.path,
     &[
       "rail",
@@ -465,15 +466,113 @@ fn test_conflict_resolution_manual_strategy() -> Result<()> {
       "--no-protected-branches",
       "--apply",
     ],
-  )?;
+  );
+  let err = stopped.expect_err("sync should stop on conflicts").to_string();
+  assert!(err.contains("stopped on conflicts"), "{}", err);
 
-  // Verify conflict markers are present
+  // Verify conflict markers are present and nothing was committed
   let lib = workspace.read_file("crates/my-crate/src/lib.rs")?;
   assert!(lib.contains("<<<<<<<"));
   assert!(lib.contains("======="));
   assert!(lib.contains(">>>>>>>"));
   // Should contain both versions in the conflict markers
   assert!(lib.contains("Monorepo version v2") || lib.contains("Split version"));
+  assert_eq!(head_of(&workspace.path)?, mono_head);
+
+  Ok(())
+}
+
+#[test]
+fn test_resolve_stopped_sync_and_continue() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo version\npub fn mono() {}")?;
+  workspace.commit("Monorepo change")?;
+  run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--to-remote",
+      "--apply",
+    ],
+  )?;
+
+  // Conflicting change on both sides, plus a later remote commit the sync must still pick up
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo version v2\npub fn mono() {}")?;
+  workspace.commit("Monorepo change v2")?;
+  std::fs::write(split_dir.join("src/lib.rs"), "// Split version\npub fn split() {}")?;
+  std::fs::write(split_dir.join("README.md"), "split readme\n")?;
+  git(&split_dir, &["add", "."])?;
+  git(&split_dir, &["commit", "-m", "Split change"])?;
+  let stopped_remote = head_of(&split_dir)?;
+  std::fs::write(split_dir.join("NOTES.md"), "later\n")?;
+  git(&split_dir, &["add", "."])?;
+  git(&split_dir, &["commit", "-m", "Later split change"])?;
+
+  let sync_args = [
+    "rail",
+    "sync",
+    "my-crate",
+    "--remote",
+    &remote,
+    "--from-remote",
+    "--no-protected-branches",
+    "--apply",
+  ];
+  assert!(run_cargo_rail(&workspace.path, &sync_args).is_err());
+
+  // The stop is listed, and blocks new syncs until resolved
+  let listed = run_cargo_rail(&workspace.path, &["rail", "resolve", "--json"])?;
+  let listed: serde_json::Value = serde_json::from_slice(&listed.stdout)?;
+  assert_eq!(listed[0]["crate"], "my-crate");
+  assert_eq!(listed[0]["summary"], "Split change");
+  assert_eq!(listed[0]["files"][0]["path"], "crates/my-crate/src/lib.rs");
+  assert_eq!(listed[0]["files"][0]["resolved"], false);
+  let blocked = run_cargo_rail(&workspace.path, &sync_args).expect_err("sync should be blocked");
+  assert!(
+    blocked.to_string().contains("cargo rail resolve my-crate"),
+    "{}",
+    blocked
+  );
+
+  // Continuing with markers left fails; picking a side settles the file
+  assert!(run_cargo_rail(&workspace.path, &["rail", "resolve", "my-crate", "--continue"]).is_err());
+  run_cargo_rail(
+    &workspace.path,
+    &["rail", "resolve", "my-crate", "crates/my-crate/src/lib.rs", "--theirs"],
+  )?;
+  assert_eq!(
+    workspace.read_file("crates/my-crate/src/lib.rs")?,
+    "// Split version\npub fn split() {}"
+  );
+  run_cargo_rail(&workspace.path, &["rail", "resolve", "my-crate", "--continue"])?;
+
+  // The stopped commit is committed with its origin trailer and mapping, then the rest follows
+  let log = git(&workspace.path, &["log", "-2", "--format=%s"])?;
+  let log = String::from_utf8_lossy(&log.stdout);
+  assert_eq!(log.lines().collect::<Vec<_>>(), ["Later split change", "Split change"]);
+  let stopped_commit = git(&workspace.path, &["rev-parse", "HEAD~1"])?;
+  let stopped_commit = String::from_utf8_lossy(&stopped_commit.stdout).trim().to_string();
+  let message = git(&workspace.path, &["log", "-1", "--format=%B", &stopped_commit])?;
+  assert!(String::from_utf8_lossy(&message.stdout).contains(&format!("Rail-Origin: remote@{}", stopped_remote)));
+  assert!(workspace.path.join("crates/my-crate/README.md").exists());
+  assert!(workspace.path.join("crates/my-crate/NOTES.md").exists());
+
+  let listed = run_cargo_rail(&workspace.path, &["rail", "resolve", "--json"])?;
+  assert_eq!(
+    serde_json::from_slice::<serde_json::Value>(&listed.stdout)?,
+    serde_json::json!([])
+  );
+
+  // Nothing left to replay
+  let output = run_cargo_rail(&workspace.path, &sync_args)?;
+  assert!(String::from_utf8_lossy(&output.stdout).contains("No new commits to sync"));
 
   Ok(())
 }
@@ -617,3 +716,1179 @@ fn test_no_conflict_with_non_overlapping_changes() -> Result<()> {
 
   Ok(())
 }
+
+#[test]
+fn test_sync_dry_run_previews_commits_and_conflicts() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+
+  // Establish a sync baseline, then change the same lines on both sides
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo version\npub fn mono() {}")?;
+  workspace.commit("Monorepo change")?;
+  run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--to-remote",
+      "--apply",
+    ],
+  )?;
+
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo version v2\npub fn mono() {}")?;
+  workspace.commit("Monorepo change v2")?;
+  std::fs::write(split_dir.join("src/lib.rs"), "// Split version\npub fn split() {}")?;
+  git(&split_dir, &["add", "."])?;
+  git(&split_dir, &["commit", "-m", "Split change"])?;
+
+  let mono_head = head_of(&workspace.path)?;
+  let split_head = head_of(&split_dir)?;
+
+  let output = run_cargo_rail(
+    &workspace.path,
+    &["rail", "sync", "my-crate", "--remote", &remote, "--json"],
+  )?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let json_start = stdout.find("\n{").map(|i| i + 1).unwrap_or(0);
+  let plan: serde_json::Value = serde_json::from_str(&stdout[json_start..]).expect("Should be valid JSON");
+
+  let preview = &plan["sync_preview"];
+  let to_remote = preview["to_remote"].as_array().expect("to_remote commits");
+  assert_eq!(to_remote.len(), 1, "{}", preview);
+  assert_eq!(to_remote[0]["summary"], "Monorepo change v2");
+  assert_eq!(to_remote[0]["files"][0]["path"], "src/lib.rs");
+
+  let from_remote = preview["from_remote"].as_array().expect("from_remote commits");
+  assert_eq!(from_remote.len(), 1, "{}", preview);
+  assert_eq!(from_remote[0]["summary"], "Split change");
+  assert_eq!(from_remote[0]["files"][0]["path"], "crates/my-crate/src/lib.rs");
+  assert_eq!(
+    from_remote[0]["conflicts"],
+    serde_json::json!(["crates/my-crate/src/lib.rs"])
+  );
+
+  // Nothing was touched
+  assert_eq!(head_of(&workspace.path)?, mono_head);
+  assert_eq!(head_of(&split_dir)?, split_head);
+  assert!(
+    workspace
+      .read_file("crates/my-crate/src/lib.rs")?
+      .contains("Monorepo version v2")
+  );
+  let status = git(&workspace.path, &["status", "--porcelain", "--", "crates"])?;
+  let status = String::from_utf8_lossy(&status.stdout);
+  assert!(status.trim().is_empty(), "{}", status);
+
+  // Human-readable dry-run shows the same prediction
+  let output = run_cargo_rail(&workspace.path, &["rail", "sync", "my-crate", "--remote", &remote])?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("Split change"), "{}", stdout);
+  assert!(stdout.contains("conflict: crates/my-crate/src/lib.rs"), "{}", stdout);
+
+  Ok(())
+}
+
+#[test]
+fn test_split_and_sync_into_target_dir_of_existing_repo() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  workspace.add_crate("my-crate", "0.1.0", &[])?;
+  workspace.commit("Add my-crate")?;
+
+  // An existing multi-language repo we don't control the layout of
+  let target = workspace.path.join("split-repos").join("poly");
+  std::fs::create_dir_all(target.join("bindings/python"))?;
+  git(&target, &["init", "--initial-branch=main"])?;
+  git(&target, &["config", "user.name", "Test User"])?;
+  git(&target, &["config", "user.email", "test@example.com"])?;
+  std::fs::write(target.join("README.md"), "# Poly\n")?;
+  std::fs::write(target.join("bindings/python/setup.py"), "# python\n")?;
+  git(&target, &["add", "."])?;
+  git(&target, &["commit", "-m", "Existing history"])?;
+
+  let rail_toml = format!(
+    r#"
+[workspace]
+root = "."
+
+[[splits]]
+name = "my-crate"
+remote = "{}"
+branch = "main"
+mode = "single"
+target_dir = "bindings/rust"
+paths = [{{ crate = "crates/my-crate" }}]
+"#,
+    target.display()
+  );
+  std::fs::write(workspace.path.join("rail.toml"), rail_toml)?;
+
+  run_cargo_rail(&workspace.path, &["rail", "split", "my-crate", "--apply"])?;
+
+  // Split lands under the prefix on top of the existing history
+  assert!(target.join("bindings/rust/Cargo.toml").exists());
+  assert!(target.join("bindings/rust/src/lib.rs").exists());
+  assert_eq!(std::fs::read_to_string(target.join("README.md"))?, "# Poly\n");
+  let log = git(&target, &["log", "--format=%s"])?;
+  assert!(String::from_utf8_lossy(&log.stdout).contains("Existing history"));
+
+  // Mono → remote keeps writing under the prefix
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn new() {}")?;
+  workspace.commit("Update in monorepo")?;
+  run_cargo_rail(&workspace.path, &["rail", "sync", "my-crate", "--to-remote", "--apply"])?;
+  let split_lib = std::fs::read_to_string(target.join("bindings/rust/src/lib.rs"))?;
+  assert!(split_lib.contains("Monorepo change"));
+  assert!(!target.join("src/lib.rs").exists());
+
+  // Remote → mono only imports changes inside the prefix
+  std::fs::write(target.join("bindings/python/setup.py"), "# python changed\n")?;
+  std::fs::write(
+    target.join("bindings/rust/src/lib.rs"),
+    "// Split change\npub fn split() {}",
+  )?;
+  git(&target, &["add", "."])?;
+  git(&target, &["commit", "-m", "Update bindings"])?;
+  run_cargo_rail(
+    &workspace.path,
+    &["rail", "sync", "my-crate", "--from-remote", "--apply"],
+  )?;
+
+  let mono_lib = workspace.read_file("crates/my-crate/src/lib.rs")?;
+  assert!(mono_lib.contains("Split change"));
+  assert!(!workspace.path.join("crates/my-crate/bindings").exists());
+  assert!(!workspace.path.join("crates/my-crate/python").exists());
+  assert!(!workspace.path.join("bindings").exists());
+
+  Ok(())
+}
+
+/// Split `my-crate` into a local split repo, returning its path
+fn split_my_crate(workspace: &TestWorkspace) -> Result<std::path::PathBuf> {
+  workspace.add_crate("my-crate", "0.1.0", &[])?;
+  workspace.commit("Add my-crate")?;
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+  let split_dir = workspace.path.join("split-repos").join("my-crate-split");
+  run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "split",
+      "my-crate",
+      "--remote",
+      &split_dir.display().to_string(),
+      "--apply",
+    ],
+  )?;
+  Ok(split_dir)
+}
+
+fn head_of(repo: &std::path::Path) -> Result<String> {
+  let out = git(repo, &["rev-parse", "HEAD"])?;
+  Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// Leave behind the journal an interrupted mono → remote sync would have written
+fn write_journal(
+  workspace: &TestWorkspace,
+  split_dir: &std::path::Path,
+  start_head: &str,
+  applied: &[(String, String)],
+) -> Result<std::path::PathBuf> {
+  let rail_dir = workspace.path.join(".git").join("rail");
+  std::fs::create_dir_all(&rail_dir)?;
+  let journal = serde_json::json!({
+    "crate_name": "my-crate",
+    "direction": "to_remote",
+    "target_repo_path": split_dir,
+    "start_head": start_head,
+    "applied": applied
+      .iter()
+      .map(|(mono, remote)| serde_json::json!({ "mono": mono, "remote": remote }))
+      .collect::<Vec<_>>(),
+  });
+  let path = rail_dir.join("sync-my-crate.json");
+  std::fs::write(&path, serde_json::to_string_pretty(&journal)?)?;
+  Ok(path)
+}
+
+#[test]
+fn test_sync_resume_discards_unjournaled_commit() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn new() {}")?;
+  workspace.commit("Update in monorepo")?;
+
+  // Crash right after a commit was created in the split repo but before it was journaled
+  let start_head = head_of(&split_dir)?;
+  let journal_path = write_journal(&workspace, &split_dir, &start_head, &[])?;
+  std::fs::write(split_dir.join("src/lib.rs"), "// half-applied")?;
+  git(&split_dir, &["commit", "-am", "Update in monorepo"])?;
+
+  // A plain sync refuses to run over the interrupted one
+  let err = run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--to-remote",
+      "--apply",
+    ],
+  )
+  .unwrap_err()
+  .to_string();
+  assert!(err.contains("interrupted sync"), "got:\n{}", err);
+  assert!(err.contains("--resume"), "got:\n{}", err);
+
+  run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--to-remote",
+      "--resume",
+    ],
+  )?;
+
+  // Exactly one copy of the commit, with the real content, and the journal is gone
+  let log = git(&split_dir, &["log", "--format=%s", &format!("{}..HEAD", start_head)])?;
+  let subjects: Vec<String> = String::from_utf8_lossy(&log.stdout).lines().map(String::from).collect();
+  assert_eq!(subjects, vec!["Update in monorepo".to_string()]);
+  let split_lib = std::fs::read_to_string(split_dir.join("src/lib.rs"))?;
+  assert!(split_lib.contains("Monorepo change"));
+  assert!(!journal_path.exists());
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_abort_rolls_back_interrupted_sync() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+  let start_head = head_of(&split_dir)?;
+
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn new() {}")?;
+  workspace.commit("Update in monorepo")?;
+  let mono_sha = head_of(&workspace.path)?;
+
+  // Sync, then pretend it was interrupted before it could finish
+  run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--to-remote",
+      "--apply",
+    ],
+  )?;
+  let synced_head = head_of(&split_dir)?;
+  assert_ne!(synced_head, start_head);
+  let journal_path = write_journal(&workspace, &split_dir, &start_head, &[(mono_sha, synced_head)])?;
+
+  run_cargo_rail(&workspace.path, &["rail", "sync", "my-crate", "--abort"])?;
+
+  assert_eq!(head_of(&split_dir)?, start_head);
+  assert!(!journal_path.exists());
+
+  // With the mapping rolled back too, the next sync applies the commit again
+  run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--to-remote",
+      "--apply",
+    ],
+  )?;
+  let log = git(&split_dir, &["log", "-1", "--format=%s"])?;
+  assert!(String::from_utf8_lossy(&log.stdout).contains("Update in monorepo"));
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_abort_only_resets_the_branch_it_wrote_to() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+
+  workspace.commit("Configure rail")?;
+
+  // An import sync was interrupted on its branch, then the user went back to main
+  let start_head = head_of(&workspace.path)?;
+  git(&workspace.path, &["checkout", "-q", "-b", "rail-import"])?;
+  workspace.modify_file("my-crate", "src/lib.rs", "// Imported\npub fn imported() {}")?;
+  let imported = workspace.commit("Imported from remote")?;
+  git(&workspace.path, &["checkout", "-q", "main"])?;
+  workspace.modify_file("my-crate", "README.md", "Work on main")?;
+  let main_head = workspace.commit("Work on main")?;
+
+  let rail_dir = workspace.path.join(".git").join("rail");
+  std::fs::create_dir_all(&rail_dir)?;
+  let journal = serde_json::json!({
+    "crate_name": "my-crate",
+    "direction": "from_remote",
+    "target_repo_path": split_dir,
+    "start_head": start_head,
+    "branch": "rail-import",
+    "applied": [{ "mono": imported, "remote": "0000000000000000000000000000000000000000" }],
+  });
+  let journal_path = rail_dir.join("sync-my-crate.json");
+  std::fs::write(&journal_path, serde_json::to_string_pretty(&journal)?)?;
+
+  let err = run_cargo_rail(&workspace.path, &["rail", "sync", "my-crate", "--abort"])
+    .unwrap_err()
+    .to_string();
+  assert!(err.contains("Check out 'rail-import'"), "got:\n{}", err);
+  assert_eq!(head_of(&workspace.path)?, main_head);
+  assert!(journal_path.exists());
+
+  // Uncommitted changes on the sync's branch stop the rollback too
+  git(&workspace.path, &["checkout", "-q", "rail-import"])?;
+  workspace.modify_file("my-crate", "src/lib.rs", "// Unsaved edit")?;
+  let err = run_cargo_rail(&workspace.path, &["rail", "sync", "my-crate", "--abort"])
+    .unwrap_err()
+    .to_string();
+  assert!(err.contains("uncommitted changes"), "got:\n{}", err);
+  assert_eq!(workspace.read_file("crates/my-crate/src/lib.rs")?, "// Unsaved edit");
+
+  git(&workspace.path, &["checkout", "--", "."])?;
+  run_cargo_rail(&workspace.path, &["rail", "sync", "my-crate", "--abort"])?;
+  assert_eq!(head_of(&workspace.path)?, start_head);
+  let main = git(&workspace.path, &["rev-parse", "main"])?;
+  assert_eq!(String::from_utf8_lossy(&main.stdout).trim(), main_head);
+  assert!(!journal_path.exists());
+
+  Ok(())
+}
+
+/// Full ID of the most recently applied plan of the given type
+fn applied_plan_id(workspace: &TestWorkspace, operation_type: &str) -> Result<String> {
+  let output = run_cargo_rail(&workspace.path, &["rail", "undo", "--json"])?;
+  let records: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+  let record = records
+    .as_array()
+    .and_then(|records| records.iter().find(|r| r["operation_type"] == operation_type))
+    .ok_or_else(|| anyhow::anyhow!("no applied {} plan recorded", operation_type))?;
+  Ok(record["id"].as_str().unwrap_or_default().to_string())
+}
+
+#[test]
+fn test_undo_unpushed_sync_resets_split_and_drops_notes() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+  let start_head = head_of(&split_dir)?;
+
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn new() {}")?;
+  workspace.commit("Update in monorepo")?;
+  let mono_sha = head_of(&workspace.path)?;
+
+  let sync = [
+    "rail",
+    "sync",
+    "my-crate",
+    "--remote",
+    &remote,
+    "--to-remote",
+    "--apply",
+  ];
+  run_cargo_rail(&workspace.path, &sync)?;
+  assert_ne!(head_of(&split_dir)?, start_head);
+
+  let plan_id = applied_plan_id(&workspace, "sync")?;
+  let short_id = &plan_id[..12];
+
+  // Dry-run shows the undo plan without touching anything
+  let output = run_cargo_rail(&workspace.path, &["rail", "undo", short_id])?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("Plan: undo"), "{}", stdout);
+  assert!(stdout.contains("Reset main"), "{}", stdout);
+  assert!(stdout.contains("Remove 1 git-notes"), "{}", stdout);
+  assert_ne!(head_of(&split_dir)?, start_head);
+
+  run_cargo_rail(&workspace.path, &["rail", "undo", short_id, "--apply"])?;
+
+  assert_eq!(head_of(&split_dir)?, start_head);
+  let note = git(
+    &workspace.path,
+    &["notes", "--ref", "refs/notes/rail/my-crate", "show", &mono_sha],
+  );
+  assert!(note.is_err(), "mapping note should be removed");
+  assert!(run_cargo_rail(&workspace.path, &["rail", "undo", short_id]).is_err());
+
+  // The sync can be applied again afterwards
+  run_cargo_rail(&workspace.path, &sync)?;
+  let log = git(&split_dir, &["log", "-1", "--format=%s"])?;
+  assert!(String::from_utf8_lossy(&log.stdout).contains("Update in monorepo"));
+
+  Ok(())
+}
+
+#[test]
+fn test_undo_reverts_sync_that_has_commits_on_top() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn new() {}")?;
+  workspace.commit("Update in monorepo")?;
+  run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--to-remote",
+      "--apply",
+    ],
+  )?;
+
+  // Someone builds on the synced commit in the split repo
+  std::fs::write(split_dir.join("NOTES.md"), "split-only\n")?;
+  git(&split_dir, &["add", "NOTES.md"])?;
+  git(&split_dir, &["commit", "-m", "Split-only change"])?;
+
+  let plan_id = applied_plan_id(&workspace, "sync")?;
+
+  // Local changes on another branch stop the undo before it checks out main
+  git(&split_dir, &["checkout", "-q", "-b", "scratch"])?;
+  std::fs::write(split_dir.join("NOTES.md"), "uncommitted\n")?;
+  let err = run_cargo_rail(&workspace.path, &["rail", "undo", &plan_id, "--apply"]).expect_err("dirty split repo");
+  assert!(err.to_string().contains("uncommitted changes"), "{}", err);
+  let branch = git(&split_dir, &["branch", "--show-current"])?;
+  assert_eq!(String::from_utf8_lossy(&branch.stdout).trim(), "scratch");
+  git(&split_dir, &["checkout", "-q", "--", "NOTES.md"])?;
+
+  run_cargo_rail(&workspace.path, &["rail", "undo", &plan_id, "--apply"])?;
+
+  // The synced change is reverted, the later commit is kept
+  let lib = std::fs::read_to_string(split_dir.join("src/lib.rs"))?;
+  assert!(!lib.contains("Monorepo change"), "{}", lib);
+  assert!(split_dir.join("NOTES.md").exists());
+  let log = git(&split_dir, &["log", "-1", "--format=%B"])?;
+  let message = String::from_utf8_lossy(&log.stdout);
+  assert!(message.starts_with("Revert \"Update in monorepo\""), "{}", message);
+  assert!(message.contains(&format!("Rail-Undo: {}", plan_id)), "{}", message);
+
+  Ok(())
+}
+
+/// Commit straight to the split repo and change the monorepo, so the two have diverged
+fn diverge(workspace: &TestWorkspace, split_dir: &std::path::Path) -> Result<()> {
+  std::fs::write(split_dir.join("README.md"), "# Pushed to the split directly")?;
+  git(split_dir, &["add", "."])?;
+  git(split_dir, &["commit", "-m", "Direct split change"])?;
+
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn new() {}")?;
+  workspace.commit("Update in monorepo")?;
+  Ok(())
+}
+
+fn sync_to_remote(workspace: &TestWorkspace, remote: &str, extra: &[&str]) -> Result<std::process::Output> {
+  let mut args = vec!["rail", "sync", "my-crate", "--remote", remote, "--to-remote", "--apply"];
+  args.extend_from_slice(extra);
+  Ok(
+    std::process::Command::new(env!("CARGO_BIN_EXE_cargo-rail"))
+      .current_dir(&workspace.path)
+      .args(&args)
+      .output()?,
+  )
+}
+
+#[test]
+fn test_sync_to_diverged_remote_aborts_by_default() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+  diverge(&workspace, &split_dir)?;
+  let split_head = head_of(&split_dir)?;
+
+  let output = sync_to_remote(&workspace, &remote, &[])?;
+  assert!(!output.status.success());
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(stdout.contains("Direct split change"), "stdout: {}", stdout);
+  assert!(stderr.contains("diverged"), "stderr: {}", stderr);
+  assert!(stderr.contains("--on-diverged"), "stderr: {}", stderr);
+
+  // Nothing was replayed, and no journal blocks the next sync
+  assert_eq!(head_of(&split_dir)?, split_head);
+  assert!(!workspace.path.join(".git/rail/sync-my-crate.json").exists());
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_to_diverged_remote_imports_first() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+  diverge(&workspace, &split_dir)?;
+
+  let output = sync_to_remote(&workspace, &remote, &["--on-diverged", "import"])?;
+  assert!(
+    output.status.success(),
+    "stderr: {}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+
+  // The direct change reached the monorepo, and the mono change the split on top of it
+  let readme = workspace.read_file("crates/my-crate/README.md")?;
+  assert!(readme.contains("Pushed to the split directly"));
+  let split_lib = std::fs::read_to_string(split_dir.join("src/lib.rs"))?;
+  assert!(split_lib.contains("Monorepo change"));
+  let log = git(&split_dir, &["log", "--format=%s", "-2"])?;
+  let log = String::from_utf8_lossy(&log.stdout);
+  assert_eq!(
+    log.lines().collect::<Vec<_>>(),
+    ["Update in monorepo", "Direct split change"]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_to_diverged_remote_rebases_outgoing_commits() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+  diverge(&workspace, &split_dir)?;
+
+  let output = sync_to_remote(&workspace, &remote, &["--on-diverged", "rebase"])?;
+  assert!(
+    output.status.success(),
+    "stderr: {}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+
+  // Outgoing commit lands on top; the direct change stays for a later import
+  let split_readme = std::fs::read_to_string(split_dir.join("README.md"))?;
+  assert!(split_readme.contains("Pushed to the split directly"));
+  let log = git(&split_dir, &["log", "--format=%s", "-2"])?;
+  let log = String::from_utf8_lossy(&log.stdout);
+  assert_eq!(
+    log.lines().collect::<Vec<_>>(),
+    ["Update in monorepo", "Direct split change"]
+  );
+  let readme = workspace.read_file("crates/my-crate/README.md")?;
+  assert!(!readme.contains("Pushed to the split directly"));
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_through_bundles() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+  let remote = split_dir.display().to_string();
+
+  // The air-gapped copy of the split repo
+  let far = workspace.path.join("split-repos").join("far");
+  git(&workspace.path, &["clone", "-q", &remote, &far.display().to_string()])?;
+  git(&far, &["config", "user.name", "Far User"])?;
+  git(&far, &["config", "user.email", "far@example.com"])?;
+
+  // Mono → bundle → far side
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn new() {}")?;
+  workspace.commit("Update in monorepo")?;
+  let out_bundle = workspace.path.join("bundles").join("out.bundle");
+  run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--to-bundle",
+      &out_bundle.display().to_string(),
+      "--apply",
+    ],
+  )?;
+  assert!(out_bundle.exists());
+
+  let out = out_bundle.display().to_string();
+  git(&far, &["pull", "-q", "--ff-only", &out, "main"])?;
+  git(
+    &far,
+    &["fetch", "-q", &out, "refs/notes/rail/my-crate:refs/notes/rail/my-crate"],
+  )?;
+  let log = git(&far, &["log", "-1", "--format=%B"])?;
+  let log = String::from_utf8_lossy(&log.stdout);
+  assert!(log.contains("Update in monorepo"));
+  assert!(log.contains("Rail-Origin: mono@"));
+  assert!(
+    !git(&far, &["notes", "--ref=refs/notes/rail/my-crate", "list"])?
+      .stdout
+      .is_empty()
+  );
+
+  // Far side → bundle → mono
+  std::fs::write(far.join("README.md"), "# Changed on the far side")?;
+  git(&far, &["commit", "-qam", "Far change"])?;
+  let in_bundle = workspace.path.join("bundles").join("in.bundle");
+  git(
+    &far,
+    &["bundle", "create", "-q", &in_bundle.display().to_string(), "main"],
+  )?;
+  run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--from-bundle",
+      &in_bundle.display().to_string(),
+      "--apply",
+    ],
+  )?;
+
+  let readme = workspace.read_file("crates/my-crate/README.md")?;
+  assert!(readme.contains("Changed on the far side"));
+  assert_eq!(head_of(&split_dir)?, head_of(&far)?);
+
+  // The imported commit is mapped, so it doesn't travel back
+  let output = run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &remote,
+      "--to-bundle",
+      &out_bundle.display().to_string(),
+      "--apply",
+    ],
+  )?;
+  assert!(String::from_utf8_lossy(&output.stdout).contains("no bundle written"));
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_quarantines_commits_failing_inbound_policy() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+
+  let config = workspace.read_file("rail.toml")?;
+  assert!(config.contains("[security]\n"));
+  let config = config.replacen(
+    "[security]\n",
+    "[security]\ninbound_allowed_authors = [\"@example.com\"]\n",
+    1,
+  );
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  workspace.commit("Restrict inbound authors")?;
+
+  // Trusted, untrusted, then trusted again on top of the untrusted one
+  let lib = split_dir.join("src/lib.rs");
+  std::fs::write(&lib, "pub fn trusted() {}\n")?;
+  git(&split_dir, &["commit", "-am", "Trusted change"])?;
+  std::fs::write(&lib, "pub fn trusted() {}\npub fn sneaky() {}\n")?;
+  git(
+    &split_dir,
+    &[
+      "-c",
+      "user.email=mallory@evil.test",
+      "commit",
+      "-am",
+      "Untrusted change",
+    ],
+  )?;
+  std::fs::write(&lib, "pub fn trusted() {}\npub fn sneaky() {}\npub fn later() {}\n")?;
+  git(&split_dir, &["commit", "-am", "Follow-up change"])?;
+
+  let output = run_cargo_rail(
+    &workspace.path,
+    &[
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &split_dir.display().to_string(),
+      "--from-remote",
+      "--apply",
+    ],
+  )?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("2 remote commit(s) held back"), "{}", stdout);
+
+  // Only the trusted commit landed
+  let mono_lib = workspace.read_file("crates/my-crate/src/lib.rs")?;
+  assert!(mono_lib.contains("trusted"));
+  assert!(!mono_lib.contains("sneaky"));
+
+  // Held commits are on the review branch, with a reason each
+  let quarantine = git(&workspace.path, &["rev-parse", "refs/heads/rail/quarantine/my-crate"])?;
+  assert_eq!(String::from_utf8_lossy(&quarantine.stdout).trim(), head_of(&split_dir)?);
+
+  let report: serde_json::Value = serde_json::from_str(&workspace.read_file(".git/rail/quarantine-my-crate.json")?)?;
+  let commits = report["commits"].as_array().unwrap();
+  assert_eq!(commits.len(), 2);
+  assert_eq!(commits[0]["summary"], "Untrusted change");
+  assert!(commits[0]["reason"].as_str().unwrap().contains("mallory@evil.test"));
+  assert!(
+    commits[1]["reason"]
+      .as_str()
+      .unwrap()
+      .starts_with("builds on held-back commit")
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_rejects_commits_touching_protected_paths() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+
+  let config = workspace.read_file("rail.toml")?.replacen(
+    "[security]\n",
+    "[security]\ninbound_protected_paths = [\"build.rs\"]\ninbound_protected_action = \"reject\"\n",
+    1,
+  );
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  workspace.commit("Protect build scripts")?;
+
+  std::fs::write(split_dir.join("build.rs"), "fn main() {}\n")?;
+  git(&split_dir, &["add", "."])?;
+  git(&split_dir, &["commit", "-m", "Add build script"])?;
+
+  let output = std::process::Command::new(env!("CARGO_BIN_EXE_cargo-rail"))
+    .current_dir(&workspace.path)
+    .args([
+      "rail",
+      "sync",
+      "my-crate",
+      "--remote",
+      &split_dir.display().to_string(),
+      "--from-remote",
+      "--apply",
+    ])
+    .output()?;
+  assert!(!output.status.success());
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(
+    stderr.contains("crates/my-crate/build.rs (protected by 'build.rs')"),
+    "{}",
+    stderr
+  );
+
+  // The build script never reached the monorepo, and the rejection is on record
+  assert!(!workspace.file_exists("crates/my-crate/build.rs"));
+  let report = workspace.read_file(".git/rail/quarantine-my-crate.json")?;
+  assert!(report.contains("crates/my-crate/build.rs"), "{}", report);
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_reuses_and_rebases_stable_pr_branch() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+
+  let config = workspace.read_file("rail.toml")?;
+  assert!(config.contains("\"rail/sync/{crate}/{timestamp}\""));
+  let config = config.replace("\"rail/sync/{crate}/{timestamp}\"", "\"rail/sync/{crate}\"");
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  workspace.commit("Use a stable PR branch")?;
+
+  let sync = || {
+    run_cargo_rail(
+      &workspace.path,
+      &[
+        "rail",
+        "sync",
+        "my-crate",
+        "--remote",
+        &split_dir.display().to_string(),
+        "--from-remote",
+        "--apply",
+      ],
+    )
+  };
+  let note = |sha: &str| -> Result<String> {
+    let out = git(
+      &workspace.path,
+      &["notes", "--ref", "refs/notes/rail/my-crate", "show", sha],
+    )?;
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+  };
+
+  let lib = split_dir.join("src/lib.rs");
+  std::fs::write(&lib, "pub fn first() {}\n")?;
+  git(&split_dir, &["commit", "-am", "First split change"])?;
+  let first_remote = head_of(&split_dir)?;
+  let stdout = String::from_utf8_lossy(&sync()?.stdout).to_string();
+  assert!(stdout.contains("Creating PR branch: rail/sync/my-crate"), "{}", stdout);
+  let first_import = head_of(&workspace.path)?;
+
+  // main moves on while the PR is open; the next import lands on the same, rebased branch
+  git(&workspace.path, &["checkout", "main"])?;
+  std::fs::write(workspace.path.join("NOTES.md"), "Unrelated\n")?;
+  workspace.commit("Unrelated mono change")?;
+  let main_tip = head_of(&workspace.path)?;
+
+  std::fs::write(&lib, "pub fn first() {}\npub fn second() {}\n")?;
+  git(&split_dir, &["commit", "-am", "Second split change"])?;
+  let stdout = String::from_utf8_lossy(&sync()?.stdout).to_string();
+  assert!(
+    stdout.contains("Rebased PR branch rail/sync/my-crate onto main"),
+    "{}",
+    stdout
+  );
+
+  let branch = git(&workspace.path, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+  assert_eq!(String::from_utf8_lossy(&branch.stdout).trim(), "rail/sync/my-crate");
+  let log = workspace.git_log(3)?;
+  assert!(log[0].contains("Second split change"));
+  assert!(log[1].contains("First split change"));
+  let rebased_first = String::from_utf8_lossy(&git(&workspace.path, &["rev-parse", "HEAD~1"])?.stdout)
+    .trim()
+    .to_string();
+  let parent = String::from_utf8_lossy(&git(&workspace.path, &["rev-parse", "HEAD~2"])?.stdout)
+    .trim()
+    .to_string();
+  assert_eq!(parent, main_tip);
+
+  // The mapping moved with the rebased commit
+  assert_ne!(rebased_first, first_import);
+  assert_eq!(note(&rebased_first)?, first_remote);
+  assert!(note(&first_import).is_err());
+
+  // Once merged, the branch starts over from main
+  git(&workspace.path, &["checkout", "main"])?;
+  git(
+    &workspace.path,
+    &["merge", "--no-ff", "-m", "Merge sync", "rail/sync/my-crate"],
+  )?;
+  let merged = head_of(&workspace.path)?;
+  std::fs::write(&lib, "pub fn first() {}\npub fn second() {}\npub fn third() {}\n")?;
+  git(&split_dir, &["commit", "-am", "Third split change"])?;
+  let stdout = String::from_utf8_lossy(&sync()?.stdout).to_string();
+  assert!(stdout.contains("was merged; starting it over from main"), "{}", stdout);
+  let parent = String::from_utf8_lossy(&git(&workspace.path, &["rev-parse", "HEAD~1"])?.stdout)
+    .trim()
+    .to_string();
+  assert_eq!(parent, merged);
+
+  Ok(())
+}
+
+#[test]
+fn test_squash_merged_pr_branch_is_not_synced_back() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = split_my_crate(&workspace)?;
+
+  let config = workspace
+    .read_file("rail.toml")?
+    .replace("\"rail/sync/{crate}/{timestamp}\"", "\"rail/sync/{crate}\"");
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  workspace.commit("Use a stable PR branch")?;
+
+  let sync = |direction: &str| {
+    run_cargo_rail(
+      &workspace.path,
+      &[
+        "rail",
+        "sync",
+        "my-crate",
+        "--remote",
+        &split_dir.display().to_string(),
+        direction,
+        "--apply",
+      ],
+    )
+  };
+
+  let lib = split_dir.join("src/lib.rs");
+  std::fs::write(&lib, "pub fn first() {}\n")?;
+  git(&split_dir, &["commit", "-am", "First split change"])?;
+  let first_remote = head_of(&split_dir)?;
+  sync("--from-remote")?;
+
+  // The forge squashes the PR into main, dropping the Rail-Origin trailer
+  git(&workspace.path, &["checkout", "main"])?;
+  git(&workspace.path, &["merge", "--squash", "rail/sync/my-crate"])?;
+  git(&workspace.path, &["commit", "-m", "Sync my-crate (#12)"])?;
+  let squash = head_of(&workspace.path)?;
+
+  let split_head = head_of(&split_dir)?;
+  let stdout = String::from_utf8_lossy(&sync("--to-remote")?.stdout).to_string();
+  assert!(stdout.contains("Recognized 1 merged import(s)"), "{}", stdout);
+  assert_eq!(head_of(&split_dir)?, split_head, "squash commit was echoed back");
+
+  let note = git(
+    &workspace.path,
+    &["notes", "--ref", "refs/notes/rail/my-crate", "show", &squash],
+  )?;
+  assert_eq!(String::from_utf8_lossy(&note.stdout).trim(), first_remote);
+
+  // The next import starts the branch over from main instead of stacking on the old one
+  std::fs::write(&lib, "pub fn first() {}\npub fn second() {}\n")?;
+  git(&split_dir, &["commit", "-am", "Second split change"])?;
+  let stdout = String::from_utf8_lossy(&sync("--from-remote")?.stdout).to_string();
+  assert!(
+    stdout.contains("was squash-merged; starting it over from main"),
+    "{}",
+    stdout
+  );
+  let parent = git(&workspace.path, &["rev-parse", "HEAD~1"])?;
+  assert_eq!(String::from_utf8_lossy(&parent.stdout).trim(), squash);
+  assert!(workspace.git_log(1)?[0].contains("Second split change"));
+
+  Ok(())
+}
+
+#[test]
+fn test_status_reports_pending_commits_and_stopped_syncs() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  workspace.add_crate("my-crate", "0.1.0", &[])?;
+  workspace.commit("Add my-crate")?;
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+  let split_dir = workspace.path.join("split-repos").join("my-crate-split");
+  let config =
+    workspace
+      .read_file("rail.toml")?
+      .replacen("remote = \"\"", &format!("remote = \"{}\"", split_dir.display()), 1);
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  workspace.commit("Configure split remote")?;
+  run_cargo_rail(&workspace.path, &["rail", "split", "my-crate", "--apply"])?;
+
+  let status = || -> Result<serde_json::Value> {
+    let output = run_cargo_rail(&workspace.path, &["rail", "status", "--json"])?;
+    let statuses: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    Ok(statuses[0].clone())
+  };
+
+  let fresh = status()?;
+  assert_eq!(fresh["unmapped_mono_commits"], serde_json::json!([]));
+  assert_eq!(fresh["unmapped_remote_commits"], serde_json::json!([]));
+  assert_eq!(fresh["last_sync"], serde_json::Value::Null);
+  assert_eq!(fresh["pr_branches"], serde_json::json!([]));
+  assert_eq!(fresh["stopped"], serde_json::Value::Null);
+  assert_eq!(fresh["target_clone"], "local");
+
+  // One new commit on each side
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn mono() {}\n")?;
+  workspace.commit("Update in monorepo")?;
+  std::fs::write(split_dir.join("README.md"), "# my-crate\n")?;
+  git(&split_dir, &["add", "README.md"])?;
+  git(&split_dir, &["commit", "-m", "Add README in split"])?;
+  let remote_head = head_of(&split_dir)?;
+
+  let pending = status()?;
+  let mono = pending["unmapped_mono_commits"].as_array().unwrap();
+  assert_eq!(mono.len(), 1);
+  assert_eq!(mono[0]["summary"], "Update in monorepo");
+  let remote = pending["unmapped_remote_commits"].as_array().unwrap();
+  assert_eq!(remote.len(), 1);
+  assert_eq!(remote[0]["sha"], remote_head.as_str());
+
+  // Importing lands on a PR branch, which stays listed until merged
+  run_cargo_rail(
+    &workspace.path,
+    &["rail", "sync", "my-crate", "--from-remote", "--apply"],
+  )?;
+  let pr_branch = String::from_utf8_lossy(&git(&workspace.path, &["rev-parse", "--abbrev-ref", "HEAD"])?.stdout)
+    .trim()
+    .to_string();
+  git(&workspace.path, &["checkout", "main"])?;
+
+  let synced = status()?;
+  assert_eq!(synced["unmapped_remote_commits"], serde_json::json!([]));
+  assert_eq!(synced["unmapped_mono_commits"].as_array().unwrap().len(), 1);
+  assert_eq!(synced["pr_branches"], serde_json::json!([pr_branch]));
+  assert!(synced["last_sync"]["plan_id"].is_string());
+  assert!(synced["last_sync"]["applied_at"].is_string());
+
+  // An interrupted sync is reported until it's resumed or aborted
+  write_journal(&workspace, &split_dir, &head_of(&split_dir)?, &[])?;
+  let stopped = status()?;
+  assert_eq!(stopped["stopped"]["state"], "interrupted");
+  assert_eq!(stopped["stopped"]["applied"], 0);
+
+  let output = run_cargo_rail(&workspace.path, &["rail", "status"])?;
+  let table = String::from_utf8_lossy(&output.stdout);
+  assert!(table.contains("pending: 1 to remote, 0 to monorepo"), "{}", table);
+  assert!(table.contains(&format!("PR branches: {}", pr_branch)), "{}", table);
+  assert!(table.contains("interrupted after 0 commit(s)"), "{}", table);
+
+  Ok(())
+}
+
+/// Method and JSON body of each request a forge stand-in received
+type ForgeRequests = std::sync::Arc<std::sync::Mutex<Vec<(String, serde_json::Value)>>>;
+
+/// A GitHub API stand-in holding one pull request; returns its URL and the requests it got
+fn github_stand_in() -> Result<(String, ForgeRequests)> {
+  use std::io::{BufRead, BufReader, Read, Write};
+
+  let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+  let url = format!("http://{}", listener.local_addr()?);
+  let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+  let recorded = std::sync::Arc::clone(&requests);
+
+  std::thread::spawn(move || {
+    let mut pr_body: Option<String> = None;
+    for stream in listener.incoming() {
+      let Ok(mut stream) = stream else { break };
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+      let mut request_line = String::new();
+      reader.read_line(&mut request_line).unwrap();
+      let method = request_line.split_whitespace().next().unwrap_or_default().to_string();
+      let mut length = 0;
+      loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line.trim_end().is_empty() {
+          break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+          && name.eq_ignore_ascii_case("content-length")
+        {
+          length = value.trim().parse().unwrap_or(0);
+        }
+      }
+      let mut body = vec![0; length];
+      reader.read_exact(&mut body).unwrap();
+      let body: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+
+      let response = match method.as_str() {
+        "GET" => match pr_body {
+          Some(ref pr_body) => serde_json::json!([{ "number": 1, "body": pr_body }]),
+          None => serde_json::json!([]),
+        },
+        _ => {
+          pr_body = body["body"].as_str().map(String::from);
+          serde_json::json!({ "number": 1, "html_url": "https://github.test/acme/mono/pull/1" })
+        }
+      }
+      .to_string();
+      recorded.lock().unwrap().push((method, body));
+      let _ = write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.len(),
+        response
+      );
+    }
+  });
+
+  Ok((url, requests))
+}
+
+#[test]
+fn test_sync_pull_request_body_lists_imports_of_earlier_syncs() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let cache_home = tempfile::TempDir::new()?;
+  let (api_url, requests) = github_stand_in()?;
+
+  // A split remote and a monorepo origin the PR branch is pushed to
+  let remotes = workspace.path.join("remotes");
+  let split_remote = remotes.join("my-crate.git");
+  let mono_remote = remotes.join("mono.git");
+  for bare in [&split_remote, &mono_remote] {
+    std::fs::create_dir_all(bare)?;
+    git(bare, &["init", "-q", "--bare", "--initial-branch=main"])?;
+  }
+  std::fs::write(workspace.path.join(".gitignore"), "remotes/\n")?;
+  workspace.add_crate("my-crate", "0.1.0", &[])?;
+  workspace.commit("Add my-crate")?;
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+  let config = workspace
+    .read_file("rail.toml")?
+    .replacen(
+      "remote = \"\"",
+      &format!("remote = \"file://{}\"", split_remote.display()),
+      1,
+    )
+    .replace("\"rail/sync/{crate}/{timestamp}\"", "\"rail/sync/{crate}\"");
+  let config = format!(
+    "{}\n[forge]\nkind = \"github\"\nrepo = \"acme/mono\"\napi_url = \"{}\"\ntoken_env = \"CARGO_RAIL_TEST_FORGE_TOKEN\"\n",
+    config, api_url
+  );
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  workspace.commit("Configure split")?;
+  git(
+    &workspace.path,
+    &["remote", "add", "origin", &mono_remote.display().to_string()],
+  )?;
+  git(&workspace.path, &["push", "-q", "origin", "main"])?;
+
+  let run = |args: &[&str]| -> Result<String> {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_cargo-rail"))
+      .current_dir(&workspace.path)
+      .env("XDG_CACHE_HOME", cache_home.path())
+      .env("CARGO_RAIL_TEST_FORGE_TOKEN", "t")
+      .args(args)
+      .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    anyhow::ensure!(
+      output.status.success(),
+      "cargo {} failed\nstdout: {}\nstderr: {}",
+      args.join(" "),
+      stdout,
+      String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(stdout)
+  };
+  // --json skips the preflight checks a non-local remote triggers
+  run(&["rail", "split", "my-crate", "--apply", "--json"])?;
+
+  let split_work = workspace.path.join("remotes").join("my-crate-work");
+  git(
+    &workspace.path,
+    &[
+      "clone",
+      "-q",
+      &split_remote.display().to_string(),
+      &split_work.display().to_string(),
+    ],
+  )?;
+  git(&split_work, &["config", "user.name", "Split Dev"])?;
+  git(&split_work, &["config", "user.email", "split@example.com"])?;
+  let import = |content: &str, message: &str| -> Result<String> {
+    std::fs::write(split_work.join("src/lib.rs"), content)?;
+    git(&split_work, &["commit", "-qam", message])?;
+    git(&split_work, &["push", "-q", "origin", "main"])?;
+    git(&workspace.path, &["checkout", "-q", "main"])?;
+    run(&["rail", "sync", "my-crate", "--from-remote", "--apply", "--json"])
+  };
+
+  import("pub fn first() {}\n", "First split change")?;
+  import("pub fn first() {}\npub fn second() {}\n", "Second split change")?;
+
+  // The second sync updated the PR opened by the first, keeping its import listed
+  let requests = requests.lock().unwrap();
+  let (method, update) = requests.last().unwrap();
+  assert_eq!(method, "PATCH");
+  let body = update["body"].as_str().unwrap_or_default();
+  assert!(body.starts_with("Imports 2 commit(s)"), "{}", body);
+  assert!(body.contains("First split change"), "{}", body);
+  assert!(body.contains("Second split change"), "{}", body);
+
+  Ok(())
+}
@@ -0,0 +1,116 @@
+//! Tests for the `watch` command
+
+use crate::helpers::*;
+use anyhow::Result;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Split `my-crate` and point its rail.toml entry at the split repo (watch has no --remote)
+fn watched_split(workspace: &TestWorkspace) -> Result<std::path::PathBuf> {
+  workspace.add_crate("my-crate", "0.1.0", &[])?;
+  workspace.commit("Add my-crate")?;
+  run_cargo_rail(&workspace.path, &["rail", "init", "--all"])?;
+  let split_dir = workspace.path.join("split-repos").join("my-crate-split");
+
+  let config = workspace.read_file("rail.toml")?;
+  let config = config.replacen("remote = \"\"", &format!("remote = \"{}\"", split_dir.display()), 1);
+  std::fs::write(workspace.path.join("rail.toml"), config)?;
+  workspace.commit("Configure split remote")?;
+
+  run_cargo_rail(&workspace.path, &["rail", "split", "my-crate", "--apply"])?;
+  Ok(split_dir)
+}
+
+fn watch_status(workspace: &TestWorkspace) -> Result<serde_json::Value> {
+  Ok(serde_json::from_str(&workspace.read_file(".git/rail/watch.json")?)?)
+}
+
+#[test]
+fn test_watch_once_syncs_both_directions() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  let split_dir = watched_split(&workspace)?;
+
+  workspace.modify_file("my-crate", "src/lib.rs", "// Monorepo change\npub fn mono() {}\n")?;
+  workspace.commit("Update in monorepo")?;
+  std::fs::write(split_dir.join("README.md"), "# my-crate\n")?;
+  git(&split_dir, &["add", "README.md"])?;
+  git(&split_dir, &["commit", "-m", "Add README in split"])?;
+
+  let output = run_cargo_rail(&workspace.path, &["rail", "watch", "--once"])?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("Watching 1 split(s)"), "{}", stdout);
+
+  // Mono → remote
+  let log = git(&split_dir, &["log", "--format=%s"])?;
+  assert!(String::from_utf8_lossy(&log.stdout).contains("Update in monorepo"));
+
+  // Remote → mono lands on a PR branch, and the watcher returns to main afterwards
+  let branch = git(&workspace.path, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+  assert_eq!(String::from_utf8_lossy(&branch.stdout).trim(), "main");
+  let branches = git(
+    &workspace.path,
+    &["branch", "--list", "rail/sync/*", "--format=%(refname:short)"],
+  )?;
+  let pr_branch = String::from_utf8_lossy(&branches.stdout).trim().to_string();
+  assert!(!pr_branch.is_empty(), "no PR branch created");
+  let log = git(&workspace.path, &["log", "-1", "--format=%s", &pr_branch])?;
+  assert!(String::from_utf8_lossy(&log.stdout).contains("Add README in split"));
+
+  let status = watch_status(&workspace)?;
+  assert_eq!(status["running"], false);
+  let split = &status["splits"][0];
+  assert_eq!(split["name"], "my-crate");
+  assert_eq!(split["state"], "synced");
+  assert_eq!(split["commits_synced"], 2);
+  assert_eq!(split["failures"], 0);
+  assert!(split["last_sync"].is_string());
+
+  // Nothing left to do on the next poll
+  run_cargo_rail(&workspace.path, &["rail", "watch", "--once"])?;
+  let status = watch_status(&workspace)?;
+  assert_eq!(status["splits"][0]["state"], "idle", "{}", status);
+
+  Ok(())
+}
+
+#[test]
+fn test_watch_stops_cleanly_on_sigterm() -> Result<()> {
+  let workspace = TestWorkspace::new()?;
+  watched_split(&workspace)?;
+
+  let mut child = Command::new(env!("CARGO_BIN_EXE_cargo-rail"))
+    .current_dir(&workspace.path)
+    .args(["rail", "watch", "--interval", "1"])
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .spawn()?;
+
+  // Wait for the first poll to finish
+  let deadline = Instant::now() + Duration::from_secs(60);
+  while !workspace.file_exists(".git/rail/watch.json") {
+    assert!(Instant::now() < deadline, "watcher never wrote its status");
+    std::thread::sleep(Duration::from_millis(100));
+  }
+  assert_eq!(watch_status(&workspace)?["running"], true);
+
+  // Only one watcher per workspace
+  let second = run_cargo_rail(&workspace.path, &["rail", "watch", "--once"]);
+  let err = second.expect_err("second watcher started").to_string();
+  assert!(err.contains("already running"), "{}", err);
+
+  Command::new("kill").args(["-TERM", &child.id().to_string()]).status()?;
+  let deadline = Instant::now() + Duration::from_secs(60);
+  let exit = loop {
+    if let Some(exit) = child.try_wait()? {
+      break exit;
+    }
+    assert!(Instant::now() < deadline, "watcher didn't stop");
+    std::thread::sleep(Duration::from_millis(100));
+  };
+  assert!(exit.success(), "{:?}", exit);
+
+  assert_eq!(watch_status(&workspace)?["running"], false);
+  assert!(!workspace.file_exists(".git/rail/watch.lock"));
+
+  Ok(())
+}